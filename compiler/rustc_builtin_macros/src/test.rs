@@ -6,7 +6,9 @@
 use rustc_ast::attr;
 use rustc_ast::ptr::P;
 use rustc_ast_pretty::pprust;
+use rustc_errors::Applicability;
 use rustc_expand::base::*;
+use rustc_session::parse::feature_err;
 use rustc_session::Session;
 use rustc_span::symbol::{sym, Ident, Symbol};
 use rustc_span::Span;
@@ -34,6 +36,10 @@ pub fn expand_test_case(
 
     let sp = ecx.with_def_site_ctxt(attr_sp);
     let mut item = anno_item.expect_item();
+    let name = Symbol::intern(&item_path(&ecx.current_expansion.module.mod_path[1..], &item.ident));
+    let (ignore, ignore_message) = should_ignore(ecx, &item);
+    let expected_panic = should_panic(ecx, &item);
+    let marker = mk_test_marker(ecx, sp, name, ignore, ignore_message, expected_panic);
     item = item.map(|mut item| {
         item.vis = ast::Visibility {
             span: item.vis.span,
@@ -41,7 +47,7 @@ pub fn expand_test_case(
             tokens: None,
         };
         item.ident.span = item.ident.span.with_ctxt(sp.ctxt());
-        item.attrs.push(ecx.attribute(ecx.meta_word(sp, sym::rustc_test_marker)));
+        item.attrs.push(marker);
         item
     });
 
@@ -150,6 +156,14 @@ pub fn expand_test_or_bench(
     // creates $name: $expr
     let field = |name, expr| cx.field_imm(sp, Ident::from_str_and_span(name, sp), expr);
 
+    let name = Symbol::intern(&item_path(
+        // skip the name of the root module
+        &cx.current_expansion.module.mod_path[1..],
+        &item.ident,
+    ));
+    let (ignore, ignore_message) = should_ignore(cx, &item);
+    let expected_panic = should_panic(cx, &item);
+
     let test_fn = if is_bench {
         // A simple ident for a lambda
         let b = Ident::from_str_and_span("b", attr_sp);
@@ -208,8 +222,8 @@ pub fn expand_test_or_bench(
                 Ident::new(sym::cfg, attr_sp),
                 vec![attr::mk_nested_word_item(Ident::new(sym::test, attr_sp))],
             )),
-            // #[rustc_test_marker]
-            cx.attribute(cx.meta_word(attr_sp, sym::rustc_test_marker)),
+            // #[rustc_test_marker(...)]
+            mk_test_marker(cx, attr_sp, name, ignore, ignore_message, expected_panic),
         ],
         // const $ident: test::TestDescAndFn =
         ast::ItemKind::Const(
@@ -234,20 +248,21 @@ pub fn expand_test_or_bench(
                                         cx.expr_call(
                                             sp,
                                             cx.expr_path(test_path("StaticTestName")),
-                                            vec![cx.expr_str(
-                                                sp,
-                                                Symbol::intern(&item_path(
-                                                    // skip the name of the root module
-                                                    &cx.current_expansion.module.mod_path[1..],
-                                                    &item.ident,
-                                                )),
-                                            )],
+                                            vec![cx.expr_str(sp, name)],
                                         ),
                                     ),
                                     // ignore: true | false
+                                    field("ignore", cx.expr_bool(sp, ignore)),
+                                    // ignore_message: Some("...") | None
                                     field(
-                                        "ignore",
-                                        cx.expr_bool(sp, should_ignore(&cx.sess, &item)),
+                                        "ignore_message",
+                                        match ignore_message {
+                                            Some(msg) => cx.expr_some(sp, cx.expr_str(sp, msg)),
+                                            None => cx.expr_path(cx.path_global(
+                                                sp,
+                                                cx.std_path(&[sym::option, sym::Option, sym::None]),
+                                            )),
+                                        },
                                     ),
                                     // allow_fail: true | false
                                     field(
@@ -257,7 +272,7 @@ pub fn expand_test_or_bench(
                                     // should_panic: ...
                                     field(
                                         "should_panic",
-                                        match should_panic(cx, &item) {
+                                        match expected_panic {
                                             // test::ShouldPanic::No
                                             ShouldPanic::No => {
                                                 cx.expr_path(should_panic_path("No"))
@@ -343,13 +358,120 @@ fn item_path(mod_path: &[Ident], item_ident: &Ident) -> String {
         .join("::")
 }
 
+/// Creates the `#[rustc_test_marker(...)]` attribute the test harness looks for.
+/// Besides marking the test case, it records what the harness hands to runners
+/// that ask for descriptors, as in
+/// `#[rustc_test_marker(name = "...", module_path = "...", ignore = "...", should_panic)]`.
+///
+/// Beware, this is parsed in `test_harness.rs`, so make sure to keep them in sync.
+fn mk_test_marker(
+    cx: &ExtCtxt<'_>,
+    sp: Span,
+    name: Symbol,
+    ignore: bool,
+    ignore_message: Option<Symbol>,
+    should_panic: ShouldPanic,
+) -> ast::Attribute {
+    let ident = |name| Ident::new(name, sp);
+    let name_value = |name, value| {
+        ast::NestedMetaItem::MetaItem(attr::mk_name_value_item_str(ident(name), value, sp))
+    };
+
+    let module_path = cx.current_expansion.module.mod_path.iter().map(|x| x.to_string());
+    let mut items = vec![
+        name_value(sym::name, name),
+        name_value(sym::module_path, Symbol::intern(&module_path.collect::<Vec<_>>().join("::"))),
+    ];
+    match (ignore, ignore_message) {
+        (false, _) => {}
+        (true, None) => items.push(attr::mk_nested_word_item(ident(sym::ignore))),
+        (true, Some(msg)) => items.push(name_value(sym::ignore, msg)),
+    }
+    match should_panic {
+        ShouldPanic::No => {}
+        ShouldPanic::Yes(None) => items.push(attr::mk_nested_word_item(ident(sym::should_panic))),
+        ShouldPanic::Yes(Some(msg)) => items.push(name_value(sym::should_panic, msg)),
+    }
+
+    cx.attribute(attr::mk_list_item(ident(sym::rustc_test_marker), items))
+}
+
+#[derive(Clone, Copy)]
 enum ShouldPanic {
     No,
     Yes(Option<Symbol>),
 }
 
-fn should_ignore(sess: &Session, i: &ast::Item) -> bool {
-    sess.contains_name(&i.attrs, sym::ignore)
+/// Returns whether the test is ignored, and the reason for it if one was given.
+///
+/// Besides `#[ignore]` and `#[ignore = "reason"]`, this handles
+/// `#[ignore(cfg(predicate), reason = "reason")]`, whose predicate is evaluated
+/// against the cfg set of the current compilation.
+fn should_ignore(cx: &ExtCtxt<'_>, i: &ast::Item) -> (bool, Option<Symbol>) {
+    let attr = match cx.sess.find_by_name(&i.attrs, sym::ignore) {
+        Some(attr) => attr,
+        None => return (false, None),
+    };
+    let list = match attr.meta_item_list() {
+        Some(list) => list,
+        // #[ignore] or #[ignore = "reason"]
+        None => return (true, attr.value_str()),
+    };
+
+    if !cx.ecfg.features.map_or(true, |features| features.test_ignore_cfg) {
+        feature_err(
+            &cx.sess.parse_sess,
+            sym::test_ignore_cfg,
+            attr.span,
+            "conditionally ignoring tests is experimental",
+        )
+        .emit();
+    }
+
+    let mut predicate = None;
+    let mut reason = None;
+    let mut malformed = false;
+    for item in &list {
+        if item.has_name(sym::cfg) && predicate.is_none() {
+            match item.meta_item_list() {
+                Some([single]) if single.meta_item().is_some() => {
+                    predicate = single.meta_item().cloned();
+                    continue;
+                }
+                _ => {}
+            }
+        } else if item.has_name(sym::reason) && reason.is_none() {
+            if let Some(value) = item.value_str() {
+                reason = Some(value);
+                continue;
+            }
+        }
+        malformed = true;
+    }
+
+    let predicate = match predicate {
+        Some(predicate) if !malformed => predicate,
+        _ => {
+            cx.sess
+                .parse_sess
+                .span_diagnostic
+                .struct_span_err(attr.span, "malformed `ignore` attribute input")
+                .span_suggestion(
+                    attr.span,
+                    "must be of the form",
+                    "#[ignore(cfg(predicate), reason = \"reason\")]".to_string(),
+                    Applicability::HasPlaceholders,
+                )
+                .emit();
+            return (false, None);
+        }
+    };
+
+    if rustc_attr::cfg_matches(&predicate, &cx.sess.parse_sess, cx.ecfg.features) {
+        (true, reason)
+    } else {
+        (false, None)
+    }
 }
 
 fn should_fail(sess: &Session, i: &ast::Item) -> bool {
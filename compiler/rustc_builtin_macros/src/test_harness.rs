@@ -10,7 +10,7 @@
 use rustc_feature::Features;
 use rustc_session::Session;
 use rustc_span::hygiene::{AstPass, SyntaxContext, Transparency};
-use rustc_span::symbol::{sym, Ident, Symbol};
+use rustc_span::symbol::{kw, sym, Ident, Symbol};
 use rustc_span::{Span, DUMMY_SP};
 use rustc_target::spec::PanicStrategy;
 use smallvec::{smallvec, SmallVec};
@@ -21,6 +21,43 @@
 struct Test {
     span: Span,
     ident: Ident,
+    name: Symbol,
+    module_path: Symbol,
+    ignore: bool,
+    ignore_message: Option<Symbol>,
+    should_panic: Option<Option<Symbol>>,
+}
+
+impl Test {
+    /// Reads the metadata recorded in the `#[rustc_test_marker(...)]` attribute
+    /// of a test case. A bare `#[rustc_test_marker]` only provides the name.
+    ///
+    /// Beware, the attribute is created by `test::mk_test_marker`, so make sure
+    /// to keep them in sync.
+    fn from_marker(item: &ast::Item, marker: &ast::Attribute) -> Test {
+        let mut test = Test {
+            span: item.span,
+            ident: item.ident,
+            name: item.ident.name,
+            module_path: kw::Empty,
+            ignore: false,
+            ignore_message: None,
+            should_panic: None,
+        };
+        for meta in marker.meta_item_list().unwrap_or_default() {
+            if meta.has_name(sym::name) {
+                test.name = meta.value_str().unwrap_or(test.name);
+            } else if meta.has_name(sym::module_path) {
+                test.module_path = meta.value_str().unwrap_or(kw::Empty);
+            } else if meta.has_name(sym::ignore) {
+                test.ignore = true;
+                test.ignore_message = meta.value_str();
+            } else if meta.has_name(sym::should_panic) {
+                test.should_panic = Some(meta.value_str());
+            }
+        }
+        test
+    }
 }
 
 struct TestCtxt<'a> {
@@ -30,6 +67,9 @@ struct TestCtxt<'a> {
     test_cases: Vec<Test>,
     reexport_test_harness_main: Option<Symbol>,
     test_runner: Option<ast::Path>,
+    // Whether the test runner takes `TestCase` descriptors, as requested with
+    // `#![test_runner(path, descriptors)]`.
+    descriptors: bool,
 }
 
 // Traverse the crate, collecting all the test functions, eliding any
@@ -48,7 +88,10 @@ pub fn inject(sess: &Session, resolver: &mut dyn ResolverExpand, krate: &mut ast
 
     // Do this here so that the test_runner crate attribute gets marked as used
     // even in non-test builds
-    let test_runner = get_test_runner(sess, span_diagnostic, &krate);
+    let (test_runner, descriptors) = match get_test_runner(sess, span_diagnostic, &krate) {
+        Some((test_runner, descriptors)) => (Some(test_runner), descriptors),
+        None => (None, false),
+    };
 
     if sess.opts.test {
         let panic_strategy = match (panic_strategy, sess.opts.debugging_opts.panic_abort_tests) {
@@ -75,6 +118,7 @@ pub fn inject(sess: &Session, resolver: &mut dyn ResolverExpand, krate: &mut ast
             &sess.features_untracked(),
             panic_strategy,
             test_runner,
+            descriptors,
         )
     }
 }
@@ -94,10 +138,11 @@ fn visit_crate(&mut self, c: &mut ast::Crate) {
 
     fn flat_map_item(&mut self, i: P<ast::Item>) -> SmallVec<[P<ast::Item>; 1]> {
         let mut item = i.into_inner();
-        if is_test_case(&self.cx.ext_cx.sess, &item) {
+        let sess = self.cx.ext_cx.sess;
+        if let Some(marker) = sess.find_by_name(&item.attrs, sym::rustc_test_marker) {
             debug!("this is a test item");
 
-            let test = Test { span: item.span, ident: item.ident };
+            let test = Test::from_marker(&item, marker);
             self.tests.push(test);
         }
 
@@ -211,6 +256,7 @@ fn generate_test_harness(
     features: &Features,
     panic_strategy: PanicStrategy,
     test_runner: Option<ast::Path>,
+    descriptors: bool,
 ) {
     let mut econfig = ExpansionConfig::default("test".to_string());
     econfig.features = Some(features);
@@ -220,7 +266,7 @@ fn generate_test_harness(
     let expn_id = ext_cx.resolver.expansion_for_ast_pass(
         DUMMY_SP,
         AstPass::TestHarness,
-        &[sym::test, sym::rustc_attrs],
+        &[sym::test, sym::rustc_attrs, sym::custom_test_frameworks],
         None,
     );
     let def_site = DUMMY_SP.with_def_site_ctxt(expn_id);
@@ -236,6 +282,7 @@ fn generate_test_harness(
         test_cases: Vec::new(),
         reexport_test_harness_main,
         test_runner,
+        descriptors,
     };
 
     TestHarnessGenerator { cx, tests: Vec::new() }.visit_crate(krate);
@@ -270,7 +317,8 @@ fn generate_test_harness(
 ///
 /// [`TestCtxt::reexport_test_harness_main`] provides a different name for the `main`
 /// function and [`TestCtxt::test_runner`] provides a path that replaces
-/// `test::test_main_static`.
+/// `test::test_main_static`. If [`TestCtxt::descriptors`] is set, that runner is
+/// passed `core::test_case::TestCase`s, see `mk_tests_slice`.
 fn mk_main(cx: &mut TestCtxt<'_>) -> P<ast::Item> {
     let sp = cx.def_site;
     let ecx = &cx.ext_cx;
@@ -304,9 +352,15 @@ fn mk_main(cx: &mut TestCtxt<'_>) -> P<ast::Item> {
     // pub fn main() { ... }
     let main_ret_ty = ecx.ty(sp, ast::TyKind::Tup(vec![]));
 
-    // If no test runner is provided we need to import the test crate
+    // If no test runner is provided we need to import the test crate, and
+    // descriptors need `core::test_case`
     let main_body = if cx.test_runner.is_none() {
         ecx.block(sp, vec![test_extern_stmt, call_test_main])
+    } else if cx.descriptors {
+        let core_id = Ident::new(sym::core, sp);
+        let core_extern_stmt =
+            ecx.stmt_item(sp, ecx.item(sp, core_id, vec![], ast::ItemKind::ExternCrate(None)));
+        ecx.block(sp, vec![core_extern_stmt, call_test_main])
     } else {
         ecx.block(sp, vec![call_test_main])
     };
@@ -340,6 +394,9 @@ fn mk_main(cx: &mut TestCtxt<'_>) -> P<ast::Item> {
 
 /// Creates a slice containing every test like so:
 /// &[&test1, &test2]
+///
+/// or, if the test runner takes descriptors:
+/// &[&core::test_case::TestCase { name: "test1", ..., test: &test1 }, ...]
 fn mk_tests_slice(cx: &TestCtxt<'_>, sp: Span) -> P<ast::Expr> {
     debug!("building test vector from {} tests", cx.test_cases.len());
     let ecx = &cx.ext_cx;
@@ -349,30 +406,103 @@ fn mk_tests_slice(cx: &TestCtxt<'_>, sp: Span) -> P<ast::Expr> {
         cx.test_cases
             .iter()
             .map(|test| {
-                ecx.expr_addr_of(test.span, ecx.expr_path(ecx.path(test.span, vec![test.ident])))
+                let test_ref = ecx
+                    .expr_addr_of(test.span, ecx.expr_path(ecx.path(test.span, vec![test.ident])));
+                if cx.descriptors {
+                    ecx.expr_addr_of(test.span, mk_test_case(cx, sp, test, test_ref))
+                } else {
+                    test_ref
+                }
             })
             .collect(),
     )
 }
 
-fn is_test_case(sess: &Session, i: &ast::Item) -> bool {
-    sess.contains_name(&i.attrs, sym::rustc_test_marker)
+/// Creates a `core::test_case::TestCase` describing `test`.
+fn mk_test_case(cx: &TestCtxt<'_>, sp: Span, test: &Test, test_ref: P<ast::Expr>) -> P<ast::Expr> {
+    let ecx = &cx.ext_cx;
+
+    // creates core::$path, resolved through the `extern crate core` in `main`
+    let core_path = |names: &[Symbol]| {
+        let idents = iter::once(sym::core).chain(names.iter().copied());
+        ecx.path(sp, idents.map(|name| Ident::new(name, sp)).collect())
+    };
+    // creates core::test_case::$name
+    let test_case_path = |names: &[&str]| {
+        let names = iter::once(sym::test_case).chain(names.iter().map(|name| Symbol::intern(name)));
+        core_path(&names.collect::<Vec<_>>())
+    };
+    let field = |name, expr| ecx.field_imm(sp, Ident::from_str_and_span(name, sp), expr);
+    let option = |value: Option<P<ast::Expr>>| match value {
+        Some(value) => ecx.expr_call(
+            sp,
+            ecx.expr_path(core_path(&[sym::option, sym::Option, sym::Some])),
+            vec![value],
+        ),
+        None => ecx.expr_path(core_path(&[sym::option, sym::Option, sym::None])),
+    };
+
+    let loc = ecx.source_map().lookup_char_pos(test.span.lo());
+    let file = Symbol::intern(&loc.file.name.prefer_remapped().to_string_lossy());
+
+    ecx.expr_struct(
+        sp,
+        test_case_path(&["TestCase"]),
+        vec![
+            field("name", ecx.expr_str(sp, test.name)),
+            field("module_path", ecx.expr_str(sp, test.module_path)),
+            field("file", ecx.expr_str(sp, file)),
+            field("line", ecx.expr_u32(sp, loc.line as u32)),
+            field("column", ecx.expr_u32(sp, loc.col.0 as u32 + 1)),
+            field("ignore", ecx.expr_bool(sp, test.ignore)),
+            field("ignore_message", option(test.ignore_message.map(|msg| ecx.expr_str(sp, msg)))),
+            field(
+                "should_panic",
+                match test.should_panic {
+                    None => ecx.expr_path(test_case_path(&["ShouldPanic", "No"])),
+                    Some(None) => ecx.expr_path(test_case_path(&["ShouldPanic", "Yes"])),
+                    Some(Some(msg)) => ecx.expr_call(
+                        sp,
+                        ecx.expr_path(test_case_path(&["ShouldPanic", "YesWithMessage"])),
+                        vec![ecx.expr_str(sp, msg)],
+                    ),
+                },
+            ),
+            field("test", test_ref),
+        ],
+    )
 }
 
+/// Returns the path given in `#![test_runner(..)]`, and whether the runner
+/// takes `TestCase` descriptors.
 fn get_test_runner(
     sess: &Session,
     sd: &rustc_errors::Handler,
     krate: &ast::Crate,
-) -> Option<ast::Path> {
+) -> Option<(ast::Path, bool)> {
     let test_attr = sess.find_by_name(&krate.attrs, sym::test_runner)?;
     let meta_list = test_attr.meta_item_list()?;
     let span = test_attr.span;
-    match &*meta_list {
-        [single] => match single.meta_item() {
-            Some(meta_item) if meta_item.is_word() => return Some(meta_item.path.clone()),
-            _ => sd.struct_span_err(span, "`test_runner` argument must be a path").emit(),
-        },
-        _ => sd.struct_span_err(span, "`#![test_runner(..)]` accepts exactly 1 argument").emit(),
+    let (runner, descriptors) = match &*meta_list {
+        [runner] => (runner, false),
+        [runner, flag] if flag.is_word() && flag.has_name(sym::descriptors) => (runner, true),
+        [_, flag] => {
+            sd.struct_span_err(flag.span(), "unknown `test_runner` argument")
+                .help("the only argument allowed after the path is `descriptors`")
+                .emit();
+            return None;
+        }
+        _ => {
+            sd.struct_span_err(span, "`#![test_runner(..)]` accepts a path and at most 1 option")
+                .emit();
+            return None;
+        }
+    };
+    match runner.meta_item() {
+        Some(meta_item) if meta_item.is_word() => Some((meta_item.path.clone(), descriptors)),
+        _ => {
+            sd.struct_span_err(span, "`test_runner` argument must be a path").emit();
+            None
+        }
     }
-    None
 }
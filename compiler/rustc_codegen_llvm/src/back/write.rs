@@ -710,7 +710,11 @@ pub(crate) unsafe fn codegen(
     module: ModuleCodegen<ModuleLlvm>,
     config: &ModuleConfig,
 ) -> Result<CompiledModule, FatalError> {
-    let _timer = cgcx.prof.generic_activity_with_arg("LLVM_module_codegen", &module.name[..]);
+    let _timer = cgcx.prof.json_verbose_generic_activity_with_args(
+        "LLVM_module_codegen",
+        &module.name,
+        &[module.name.clone()],
+    );
     {
         let llmod = module.module_llvm.llmod();
         let llcx = &*module.module_llvm.llcx;
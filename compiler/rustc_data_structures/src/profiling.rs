@@ -85,6 +85,7 @@
 use crate::fx::FxHashMap;
 
 use std::borrow::Borrow;
+use std::cell::Cell;
 use std::collections::hash_map::Entry;
 use std::convert::Into;
 use std::error::Error;
@@ -95,7 +96,8 @@
 use std::time::{Duration, Instant};
 
 use measureme::{EventId, EventIdBuilder, Profiler, SerializableString, StringId};
-use parking_lot::RwLock;
+use parking_lot::{const_rwlock, RwLock};
+use rustc_serialize::json::as_json;
 
 bitflags::bitflags! {
     struct EventFilter: u32 {
@@ -134,6 +136,15 @@ struct EventFilter: u32 {
     ("llvm", EventFilter::LLVM),
 ];
 
+/// The format of the entries printed by `-Z time-passes`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TimePassesFormat {
+    /// A line of text per pass.
+    Text,
+    /// A JSON object per pass, on its own line, without the `time: ` prefix.
+    Json,
+}
+
 /// Something that uniquely identifies a query invocation.
 pub struct QueryInvocationId(pub u32);
 
@@ -150,8 +161,8 @@ pub struct SelfProfilerRef {
     // actually enabled.
     event_filter_mask: EventFilter,
 
-    // Print verbose generic activities to stdout
-    print_verbose_generic_activities: bool,
+    // Print verbose generic activities to stdout, in this format
+    print_verbose_generic_activities: Option<TimePassesFormat>,
 
     // Print extra verbose generic activities to stdout
     print_extra_verbose_generic_activities: bool,
@@ -160,7 +171,7 @@ pub struct SelfProfilerRef {
 impl SelfProfilerRef {
     pub fn new(
         profiler: Option<Arc<SelfProfiler>>,
-        print_verbose_generic_activities: bool,
+        print_verbose_generic_activities: Option<TimePassesFormat>,
         print_extra_verbose_generic_activities: bool,
     ) -> SelfProfilerRef {
         // If there is no SelfProfiler then the filter mask is set to NONE,
@@ -210,8 +221,11 @@ pub fn verbose_generic_activity<'a>(
         &'a self,
         event_label: &'static str,
     ) -> VerboseTimingGuard<'a> {
-        let message =
-            if self.print_verbose_generic_activities { Some(event_label.to_owned()) } else { None };
+        let message = self.print_verbose_generic_activities.map(|format| VerboseTimingMessage {
+            format,
+            label: event_label,
+            arg: None,
+        });
 
         VerboseTimingGuard::start(message, self.generic_activity(event_label))
     }
@@ -228,15 +242,46 @@ pub fn extra_verbose_generic_activity<'a, A>(
     where
         A: Borrow<str> + Into<String>,
     {
-        let message = if self.print_extra_verbose_generic_activities {
-            Some(format!("{}({})", event_label, event_arg.borrow()))
-        } else {
-            None
-        };
+        let message = self.extra_verbose_message(event_label, event_arg.borrow());
 
         VerboseTimingGuard::start(message, self.generic_activity_with_arg(event_label, event_arg))
     }
 
+    /// Like `extra_verbose_generic_activity`, but the entry is only printed with
+    /// -Ztime-passes-format=json, and the measureme event has `event_args`.
+    /// This is meant for activities like the ones which run for every codegen
+    /// unit, which would make the text output too long.
+    pub fn json_verbose_generic_activity_with_args<'a>(
+        &'a self,
+        event_label: &'static str,
+        printed_arg: &str,
+        event_args: &[String],
+    ) -> VerboseTimingGuard<'a> {
+        let message = match self.print_verbose_generic_activities {
+            Some(TimePassesFormat::Json) => self.extra_verbose_message(event_label, printed_arg),
+            _ => None,
+        };
+
+        VerboseTimingGuard::start(message, self.generic_activity_with_args(event_label, event_args))
+    }
+
+    fn extra_verbose_message(
+        &self,
+        event_label: &'static str,
+        event_arg: &str,
+    ) -> Option<VerboseTimingMessage> {
+        match self.print_verbose_generic_activities {
+            Some(format) if self.print_extra_verbose_generic_activities => {
+                Some(VerboseTimingMessage {
+                    format,
+                    label: event_label,
+                    arg: Some(event_arg.to_owned()),
+                })
+            }
+            _ => None,
+        }
+    }
+
     /// Start profiling a generic activity. Profiling continues until the
     /// TimingGuard returned from this call is dropped.
     #[inline(always)]
@@ -553,14 +598,39 @@ pub fn run<R>(self, f: impl FnOnce() -> R) -> R {
     }
 }
 
+thread_local! {
+    /// The number of `VerboseTimingGuard`s which print an entry on this thread
+    /// and haven't been dropped yet.
+    static TIME_PASSES_DEPTH: Cell<usize> = Cell::new(0);
+}
+
+/// The name of the crate being compiled, for the entries of the `json` format.
+static TIME_PASSES_CRATE_NAME: RwLock<Option<String>> = const_rwlock(None);
+
+/// Sets the name of the crate in the entries of `-Z time-passes-format=json`
+/// printed from now on.
+pub fn set_time_passes_crate_name(crate_name: Option<String>) {
+    *TIME_PASSES_CRATE_NAME.write() = crate_name;
+}
+
+/// What a `VerboseTimingGuard` prints when it is dropped.
+struct VerboseTimingMessage {
+    format: TimePassesFormat,
+    label: &'static str,
+    arg: Option<String>,
+}
+
 #[must_use]
 pub struct VerboseTimingGuard<'a> {
-    start_and_message: Option<(Instant, Option<usize>, String)>,
+    start_and_message: Option<(Instant, Option<usize>, VerboseTimingMessage)>,
     _guard: TimingGuard<'a>,
 }
 
 impl<'a> VerboseTimingGuard<'a> {
-    pub fn start(message: Option<String>, _guard: TimingGuard<'a>) -> Self {
+    fn start(message: Option<VerboseTimingMessage>, _guard: TimingGuard<'a>) -> Self {
+        if message.is_some() {
+            TIME_PASSES_DEPTH.with(|depth| depth.set(depth.get() + 1));
+        }
         VerboseTimingGuard {
             _guard,
             start_and_message: message.map(|msg| (Instant::now(), get_resident_set_size(), msg)),
@@ -578,17 +648,58 @@ impl Drop for VerboseTimingGuard<'_> {
     fn drop(&mut self) {
         if let Some((start_time, start_rss, ref message)) = self.start_and_message {
             let end_rss = get_resident_set_size();
-            print_time_passes_entry(&message[..], start_time.elapsed(), start_rss, end_rss);
+            TIME_PASSES_DEPTH.with(|depth| depth.set(depth.get() - 1));
+            print_entry(
+                message.label,
+                message.arg.as_deref(),
+                start_time.elapsed(),
+                start_rss,
+                end_rss,
+                message.format,
+            );
         }
     }
 }
 
+/// Prints the entry of `-Z time-passes` for `what`, which is nested in the
+/// passes currently running on this thread.
 pub fn print_time_passes_entry(
     what: &str,
     dur: Duration,
     start_rss: Option<usize>,
     end_rss: Option<usize>,
+    format: TimePassesFormat,
 ) {
+    print_entry(what, None, dur, start_rss, end_rss, format)
+}
+
+fn print_entry(
+    what: &str,
+    arg: Option<&str>,
+    dur: Duration,
+    start_rss: Option<usize>,
+    end_rss: Option<usize>,
+    format: TimePassesFormat,
+) {
+    if format == TimePassesFormat::Json {
+        let depth = TIME_PASSES_DEPTH.with(|depth| depth.get());
+        eprintln!(
+            r#"{{"crate":{},"pass":{},"arg":{},"depth":{},"secs":{},"start_rss":{},"end_rss":{}}}"#,
+            as_json(&*TIME_PASSES_CRATE_NAME.read()),
+            as_json(&what),
+            as_json(&arg),
+            depth,
+            dur.as_secs_f64(),
+            as_json(&start_rss),
+            as_json(&end_rss),
+        );
+        return;
+    }
+
+    let what = match arg {
+        Some(arg) => format!("{}({})", what, arg),
+        None => what.to_owned(),
+    };
     let rss_to_mb = |rss| (rss as f64 / 1_000_000.0).round() as usize;
     let rss_change_to_mb = |rss| (rss as f64 / 1_000_000.0).round() as i128;
 
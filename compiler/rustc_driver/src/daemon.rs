@@ -0,0 +1,645 @@
+//! The compiler daemon of `--daemon`, and its client, `--daemon-connect`.
+//!
+//! A daemon listens on a Unix socket which only its user can connect to, and
+//! each connection to it is a compiler invocation. The client sends its
+//! standard streams and the pipe of its jobserver, if it has one, to the daemon,
+//! followed by its arguments, working directory and environment, then waits for
+//! the wait status of the compilation. For each invocation, the daemon forks a
+//! process which receives all of this, takes over the streams, the jobserver,
+//! the working directory and the environment of the client, and runs the
+//! compiler with the arguments. Since the compiler writes to the streams of the
+//! client directly, and the client exits like the process did, the result is
+//! the same as if the client had run the compiler.
+//!
+//! The daemon keeps the metadata blobs of the crates its compilations loaded in
+//! `rustc_metadata::metadata_cache`. When a process exits, the daemon loads the
+//! crate files it read into the cache, and the processes forked for the next
+//! invocations inherit the blobs of the files which haven't changed since. The
+//! rest of the state of a compilation session, including the `CrateMetadata`
+//! decoded from the blobs, is built again by every invocation.
+
+use crate::{catch_with_exit_code, run_compiler, Callbacks};
+
+use rustc_codegen_ssa::traits::CodegenBackend;
+use rustc_data_structures::fx::FxHashMap;
+use rustc_interface::util::get_codegen_backend;
+use rustc_metadata::metadata_cache;
+use rustc_middle::middle::cstore::MetadataLoaderDyn;
+use rustc_serialize::json::{Json, ToJson};
+use rustc_session::config::{self, ErrorOutputType};
+use rustc_session::{early_error, early_warn, filesearch, getopts};
+use rustc_span::source_map::FileLoader;
+use rustc_target::spec::Target;
+
+use std::collections::BTreeMap;
+use std::env;
+use std::ffi::OsStr;
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::mem;
+use std::net::Shutdown;
+use std::ops::Range;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::io::{AsRawFd, FromRawFd, RawFd};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::Path;
+use std::process;
+use std::ptr;
+use std::sync::atomic::{AtomicI32, Ordering};
+
+/// The streams a client passes to the daemon: stdin, stdout and stderr.
+const STREAMS: [RawFd; 3] = [0, 1, 2];
+
+/// The environment variables which may pass a jobserver to the compiler, and the
+/// flags in them which name its file descriptors, in the order the `jobserver`
+/// crate looks for them.
+const JOBSERVER_VARS: [&str; 3] = ["CARGO_MAKEFLAGS", "MAKEFLAGS", "MFLAGS"];
+const JOBSERVER_FLAGS: [&str; 2] = ["--jobserver-fds=", "--jobserver-auth="];
+
+type MakeCodegenBackend =
+    Option<Box<dyn FnOnce(&config::Options) -> Box<dyn CodegenBackend> + Send>>;
+
+/// Handles `--daemon` and `--daemon-connect`. `args` are all the arguments of
+/// the compiler, with `@path` arguments expanded.
+pub(crate) fn run(
+    matches: &getopts::Matches,
+    args: &[String],
+    sopts: &config::Options,
+    callbacks: &mut (dyn Callbacks + Send),
+    file_loader: Option<Box<dyn FileLoader + Send + Sync>>,
+    emitter: Option<Box<dyn Write + Send>>,
+    make_codegen_backend: MakeCodegenBackend,
+) -> ! {
+    let output = sopts.error_format;
+    if let Some(socket) = matches.opt_str("daemon-connect") {
+        match connect(Path::new(&socket), &forwarded_args(args)) {
+            Ok(status) => exit_like(status),
+            Err(e) => early_error(
+                output,
+                &format!("failed to compile with the daemon at `{}`: {}", socket, e),
+            ),
+        }
+    }
+
+    let socket = matches.opt_str("daemon").unwrap();
+    let err =
+        serve(Path::new(&socket), sopts, callbacks, file_loader, emitter, make_codegen_backend)
+            .unwrap_err();
+    early_error(output, &format!("failed to run the daemon at `{}`: {}", socket, err))
+}
+
+/// Returns `args` without `--daemon-connect`, for the compiler run by the daemon.
+fn forwarded_args(args: &[String]) -> Vec<String> {
+    let mut forwarded = Vec::with_capacity(args.len());
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if arg == "--daemon-connect" {
+            args.next();
+        } else if !arg.starts_with("--daemon-connect=") {
+            forwarded.push(arg.clone());
+        }
+    }
+    forwarded
+}
+
+/// Sends the invocation of the compiler with `args` to the daemon listening on
+/// `socket`, and returns the wait status of the compilation.
+fn connect(socket: &Path, args: &[String]) -> io::Result<libc::c_int> {
+    let mut stream = UnixStream::connect(socket)?;
+
+    let cwd = env::current_dir()?;
+    let cwd = cwd.to_str().ok_or_else(|| invalid_data("the working directory is not Unicode"))?;
+    // Like the compiler, ignore the environment variables which aren't Unicode.
+    let env = env::vars_os()
+        .filter_map(|(key, value)| Some((key.into_string().ok()?, value.into_string().ok()?)))
+        .collect::<BTreeMap<_, _>>();
+
+    // Pass the jobserver the compiler would use along with the streams, if the
+    // environment names one which this process has.
+    let mut fds = STREAMS.to_vec();
+    if let Some((_, _, jobserver)) = find_jobserver(|var| env.get(var).map(|value| &value[..])) {
+        if jobserver.iter().all(|&fd| unsafe { libc::fcntl(fd, libc::F_GETFD) } >= 0) {
+            fds.extend_from_slice(&jobserver);
+        }
+    }
+    send_fds(&stream, &fds)?;
+
+    let mut request = BTreeMap::new();
+    request.insert("args".to_string(), args.to_json());
+    request.insert("cwd".to_string(), cwd.to_json());
+    request.insert("env".to_string(), env.to_json());
+    stream.write_all(Json::Object(request).to_string().as_bytes())?;
+    stream.shutdown(Shutdown::Write)?;
+
+    let mut status = [0; 4];
+    stream.read_exact(&mut status).map_err(|e| match e.kind() {
+        io::ErrorKind::UnexpectedEof => {
+            invalid_data("the daemon stopped before the compilation finished")
+        }
+        _ => e,
+    })?;
+    Ok(libc::c_int::from_le_bytes(status))
+}
+
+/// Exits like the process of the daemon whose wait status is `status`.
+fn exit_like(status: libc::c_int) -> ! {
+    if libc::WIFSIGNALED(status) {
+        // Die from the same signal, with its default action, like a compilation
+        // in this process would have.
+        let signal = libc::WTERMSIG(status);
+        unsafe {
+            libc::signal(signal, libc::SIG_DFL);
+            libc::raise(signal);
+        }
+        process::exit(128 + signal);
+    }
+    process::exit(libc::WEXITSTATUS(status))
+}
+
+struct Request {
+    args: Vec<String>,
+    cwd: String,
+    env: Vec<(String, String)>,
+}
+
+impl Request {
+    fn decode(body: &str) -> Result<Request, String> {
+        let json = Json::from_str(body).map_err(|e| e.to_string())?;
+        let args = json
+            .find("args")
+            .and_then(Json::as_array)
+            .and_then(|args| args.iter().map(|arg| Some(arg.as_string()?.to_string())).collect())
+            .ok_or("invalid `args`")?;
+        let cwd = json.find("cwd").and_then(Json::as_string).ok_or("invalid `cwd`")?.to_string();
+        let env = json
+            .find("env")
+            .and_then(Json::as_object)
+            .and_then(|env| {
+                env.iter()
+                    .map(|(key, value)| Some((key.clone(), value.as_string()?.to_string())))
+                    .collect::<Option<Vec<_>>>()
+            })
+            .ok_or("invalid `env`")?;
+        Ok(Request { args, cwd, env })
+    }
+
+    /// Makes the jobserver flag of the environment name `jobserver`, the file
+    /// descriptors of the jobserver of the client in this process, or no valid
+    /// ones if the client didn't pass any.
+    fn forward_jobserver(&mut self, jobserver: &[RawFd]) {
+        let env = &self.env;
+        let found = find_jobserver(|var| {
+            env.iter().find(|(key, _)| key == var).map(|(_, value)| &value[..])
+        });
+        if let Some((var, auth, _)) = found {
+            let fds = match jobserver {
+                [read, write] => format!("{},{}", read, write),
+                _ => "-1,-1".to_string(),
+            };
+            let (_, value) = self.env.iter_mut().find(|(key, _)| key == var).unwrap();
+            value.replace_range(auth, &fds);
+        }
+    }
+}
+
+/// Finds the jobserver flag in the environment, like the `jobserver` crate does,
+/// with `var` giving the value of a variable. Returns the variable which has the
+/// flag, where the file descriptors are in its value, and the descriptors.
+fn find_jobserver<'a>(
+    var: impl Fn(&str) -> Option<&'a str>,
+) -> Option<(&'static str, Range<usize>, [RawFd; 2])> {
+    let (var, value) = JOBSERVER_VARS.iter().find_map(|&name| Some((name, var(name)?)))?;
+    let start = JOBSERVER_FLAGS.iter().find_map(|flag| Some(value.find(flag)? + flag.len()))?;
+    let end = value[start..].find(' ').map_or(value.len(), |len| start + len);
+    let (read, write) = value[start..end].split_once(',')?;
+    Some((var, start..end, [read.parse().ok()?, write.parse().ok()?]))
+}
+
+/// A process forked to run the compiler for a client.
+struct Child {
+    /// The connection of the client, which gets the wait status of the process.
+    stream: UnixStream,
+    /// The pipe on which the process reports the crate files it loaded metadata
+    /// from, until it is closed.
+    report: Option<File>,
+    /// The report read so far: paths, each followed by a null byte.
+    loaded: Vec<u8>,
+}
+
+impl Child {
+    /// Reads what the process has reported since the last call.
+    fn read_report(&mut self) {
+        if let Some(report) = &mut self.report {
+            match report.read_to_end(&mut self.loaded) {
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => {}
+                // The process closed the pipe, or it can't be read anymore.
+                _ => self.report = None,
+            }
+        }
+    }
+}
+
+/// What the daemon loads metadata into the cache with: the target and the
+/// metadata loader of its own options.
+type CacheLoader = (Target, Box<MetadataLoaderDyn>);
+
+/// The write end of the pipe on which the `SIGCHLD` handler wakes up the daemon.
+static SIGCHLD_PIPE: AtomicI32 = AtomicI32::new(-1);
+
+extern "C" fn on_sigchld(_: libc::c_int) {
+    let byte = 0u8;
+    // If the pipe is full, the daemon will wake up anyway.
+    unsafe { libc::write(SIGCHLD_PIPE.load(Ordering::Relaxed), (&byte as *const u8).cast(), 1) };
+}
+
+/// Serves the invocations sent to `socket` until the daemon is killed, running the compiler
+/// like `run_compiler` does with the other arguments. Only returns on errors.
+fn serve(
+    socket: &Path,
+    sopts: &config::Options,
+    callbacks: &mut (dyn Callbacks + Send),
+    mut file_loader: Option<Box<dyn FileLoader + Send + Sync>>,
+    mut emitter: Option<Box<dyn Write + Send>>,
+    mut make_codegen_backend: MakeCodegenBackend,
+) -> io::Result<()> {
+    let listener = bind(socket)?;
+    let uid = unsafe { libc::geteuid() };
+
+    // Custom drivers create their codegen backend themselves, for a single
+    // compilation, so there is no metadata loader to fill the cache with.
+    let cache_loader = if make_codegen_backend.is_none() { cache_loader(sopts) } else { None };
+    if cache_loader.is_some() {
+        metadata_cache::enable();
+    }
+
+    // The processes are reaped as soon as they exit, when `SIGCHLD` wakes up the
+    // daemon. A client which went away only makes sending the wait status fail.
+    let (sigchld, sigchld_write) = pipe()?;
+    set_nonblocking(&sigchld)?;
+    set_nonblocking(&sigchld_write)?;
+    SIGCHLD_PIPE.store(sigchld_write.as_raw_fd(), Ordering::Relaxed);
+    let mut old_sigchld: libc::sigaction = unsafe { mem::zeroed() };
+    let old_sigpipe = unsafe {
+        let mut action: libc::sigaction = mem::zeroed();
+        action.sa_sigaction = on_sigchld as extern "C" fn(libc::c_int) as libc::sighandler_t;
+        action.sa_flags = libc::SA_RESTART | libc::SA_NOCLDSTOP;
+        if libc::sigaction(libc::SIGCHLD, &action, &mut old_sigchld) < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        libc::signal(libc::SIGPIPE, libc::SIG_IGN)
+    };
+
+    let mut children: FxHashMap<libc::pid_t, Child> = FxHashMap::default();
+    loop {
+        let reporting = children
+            .iter()
+            .filter_map(|(&pid, child)| Some((pid, child.report.as_ref()?.as_raw_fd())))
+            .collect::<Vec<_>>();
+        let mut polled = [listener.as_raw_fd(), sigchld.as_raw_fd()]
+            .iter()
+            .copied()
+            .chain(reporting.iter().map(|&(_, fd)| fd))
+            .map(|fd| libc::pollfd { fd, events: libc::POLLIN, revents: 0 })
+            .collect::<Vec<_>>();
+        if unsafe { libc::poll(polled.as_mut_ptr(), polled.len() as libc::nfds_t, -1) } < 0 {
+            let err = io::Error::last_os_error();
+            if err.kind() == io::ErrorKind::Interrupted {
+                continue;
+            }
+            return Err(err);
+        }
+
+        for (&(pid, _), fd) in reporting.iter().zip(&polled[2..]) {
+            if fd.revents != 0 {
+                children.get_mut(&pid).unwrap().read_report();
+            }
+        }
+        if polled[1].revents != 0 {
+            let _ = (&sigchld).read_to_end(&mut Vec::new());
+            reap_children(&mut children, cache_loader.as_ref());
+        }
+        if polled[0].revents == 0 {
+            continue;
+        }
+
+        let stream = match listener.accept() {
+            Ok((stream, _)) => stream,
+            Err(e) => {
+                early_warn(ErrorOutputType::default(), &format!("failed to accept: {}", e));
+                continue;
+            }
+        };
+        match peer_uid(&stream) {
+            Ok(peer) if peer == uid => {}
+            Ok(peer) => {
+                let msg = format!("refused an invocation from the user {}", peer);
+                early_warn(ErrorOutputType::default(), &msg);
+                continue;
+            }
+            Err(e) => {
+                let msg = format!("failed to check the user of an invocation: {}", e);
+                early_warn(ErrorOutputType::default(), &msg);
+                continue;
+            }
+        }
+        // The process reads the invocation itself, so that a client which is slow
+        // to send it doesn't hold up the others.
+        let pipe = pipe().and_then(|(report, report_write)| {
+            set_nonblocking(&report)?;
+            Ok((report, report_write))
+        });
+
+        match pipe {
+            Ok((report, report_write)) => match unsafe { libc::fork() } {
+                -1 => {
+                    let err = io::Error::last_os_error();
+                    early_warn(ErrorOutputType::default(), &format!("failed to fork: {}", err));
+                }
+                0 => {
+                    // The child only runs the compiler, and reports the crate
+                    // files it loaded metadata from.
+                    unsafe {
+                        libc::sigaction(libc::SIGCHLD, &old_sigchld, ptr::null_mut());
+                        libc::signal(libc::SIGPIPE, old_sigpipe);
+                        libc::close(listener.as_raw_fd());
+                        libc::close(sigchld.as_raw_fd());
+                        libc::close(sigchld_write.as_raw_fd());
+                    }
+                    drop(report);
+                    children.clear();
+                    let code = match receive_request(&stream) {
+                        Ok((request, fds)) => run_request(
+                            request,
+                            &fds,
+                            callbacks,
+                            file_loader.take(),
+                            emitter.take(),
+                            make_codegen_backend.take(),
+                        ),
+                        Err(e) => {
+                            let msg = format!("invalid invocation: {}", e);
+                            early_warn(ErrorOutputType::default(), &msg);
+                            1
+                        }
+                    };
+                    let mut loaded = Vec::new();
+                    for path in metadata_cache::take_loaded() {
+                        loaded.extend_from_slice(path.as_os_str().as_bytes());
+                        loaded.push(0);
+                    }
+                    let _ = (&report_write).write_all(&loaded);
+                    let _ = io::stdout().flush();
+                    process::exit(code);
+                }
+                pid => {
+                    children
+                        .insert(pid, Child { stream, report: Some(report), loaded: Vec::new() });
+                }
+            },
+            Err(e) => {
+                early_warn(ErrorOutputType::default(), &format!("failed to create a pipe: {}", e));
+            }
+        }
+    }
+}
+
+/// Binds `socket`, with mode 0600 so that only the user of the daemon can
+/// connect to it.
+fn bind(socket: &Path) -> io::Result<UnixListener> {
+    // Replace the socket of a daemon which is not running anymore, but not the
+    // one of a running daemon.
+    if socket.exists() && UnixStream::connect(socket).is_err() {
+        fs::remove_file(socket)?;
+    }
+    // Create the socket with its mode, rather than changing it once others may
+    // have connected already.
+    let umask = unsafe { libc::umask(0o177) };
+    let listener = UnixListener::bind(socket);
+    unsafe { libc::umask(umask) };
+    listener
+}
+
+/// Returns what to load metadata into the cache with, like the compilations
+/// with the options `sopts` would.
+fn cache_loader(sopts: &config::Options) -> Option<CacheLoader> {
+    let sysroot = sopts.maybe_sysroot.clone().unwrap_or_else(filesearch::get_or_default_sysroot);
+    let target = Target::search(&sopts.target_triple, &sysroot).ok()?;
+    Some((target, get_codegen_backend(sopts).metadata_loader()))
+}
+
+/// Receives the invocation of the client at the other end of `stream`, in the
+/// forked process, and returns it with the standard streams of the client.
+fn receive_request(stream: &UnixStream) -> Result<(Request, Vec<RawFd>), String> {
+    let mut fds = receive_fds(stream, STREAMS.len() + 2).map_err(|e| e.to_string())?;
+    if fds.len() != STREAMS.len() && fds.len() != STREAMS.len() + 2 {
+        return Err("expected the standard streams of the client".to_string());
+    }
+    let mut body = String::new();
+    (&*stream).read_to_string(&mut body).map_err(|e| e.to_string())?;
+    let mut request = Request::decode(&body)?;
+    // The jobserver stays open for the compiler, which finds it in the
+    // environment. The daemon doesn't create any session, so the jobserver
+    // client of the compiler is only created in this process.
+    request.forward_jobserver(&fds[STREAMS.len()..]);
+    fds.truncate(STREAMS.len());
+    Ok((request, fds))
+}
+
+/// Runs the compiler for `request`, in the forked process, with the standard
+/// streams `fds` of the client.
+fn run_request(
+    request: Request,
+    fds: &[RawFd],
+    callbacks: &mut (dyn Callbacks + Send),
+    file_loader: Option<Box<dyn FileLoader + Send + Sync>>,
+    emitter: Option<Box<dyn Write + Send>>,
+    make_codegen_backend: MakeCodegenBackend,
+) -> i32 {
+    for (&fd, &stream) in fds.iter().zip(&STREAMS) {
+        unsafe {
+            libc::dup2(fd, stream);
+            libc::close(fd);
+        }
+    }
+    let vars = env::vars_os().map(|(key, _)| key).collect::<Vec<_>>();
+    for key in vars {
+        env::remove_var(key);
+    }
+    for (key, value) in &request.env {
+        env::set_var(key, value);
+    }
+    if let Err(e) = env::set_current_dir(&request.cwd) {
+        let msg = format!("failed to change the working directory to `{}`: {}", request.cwd, e);
+        early_warn(ErrorOutputType::default(), &msg);
+        return 1;
+    }
+
+    catch_with_exit_code(|| {
+        run_compiler(&request.args, callbacks, file_loader, emitter, make_codegen_backend)
+    })
+}
+
+/// Waits for the processes which have exited, sends their wait status to their
+/// client, and loads the crate files they reported into the cache.
+fn reap_children(children: &mut FxHashMap<libc::pid_t, Child>, cache_loader: Option<&CacheLoader>) {
+    loop {
+        let mut status = 0;
+        let pid = unsafe { libc::waitpid(-1, &mut status, libc::WNOHANG) };
+        if pid <= 0 {
+            return;
+        }
+        let mut child = match children.remove(&pid) {
+            Some(child) => child,
+            None => continue,
+        };
+        child.read_report();
+        let _ = (&child.stream).write_all(&status.to_le_bytes());
+
+        if let Some((target, loader)) = cache_loader {
+            for path in child.loaded.split(|&byte| byte == 0).filter(|path| !path.is_empty()) {
+                // The file may be gone already, which only matters to the
+                // compilations which load it.
+                let _ = metadata_cache::load(Path::new(OsStr::from_bytes(path)), target, &**loader);
+            }
+        }
+    }
+}
+
+/// Returns the user of the process at the other end of `stream`.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn peer_uid(stream: &UnixStream) -> io::Result<libc::uid_t> {
+    let mut cred: libc::ucred = unsafe { mem::zeroed() };
+    let mut len = mem::size_of::<libc::ucred>() as libc::socklen_t;
+    let ret = unsafe {
+        libc::getsockopt(
+            stream.as_raw_fd(),
+            libc::SOL_SOCKET,
+            libc::SO_PEERCRED,
+            (&mut cred as *mut libc::ucred).cast(),
+            &mut len,
+        )
+    };
+    if ret < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(cred.uid)
+}
+
+/// Returns the user of the process at the other end of `stream`.
+#[cfg(any(
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "freebsd",
+    target_os = "dragonfly",
+    target_os = "openbsd",
+    target_os = "netbsd"
+))]
+fn peer_uid(stream: &UnixStream) -> io::Result<libc::uid_t> {
+    let (mut uid, mut gid) = (0, 0);
+    if unsafe { libc::getpeereid(stream.as_raw_fd(), &mut uid, &mut gid) } < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(uid)
+}
+
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "freebsd",
+    target_os = "dragonfly",
+    target_os = "openbsd",
+    target_os = "netbsd"
+)))]
+fn peer_uid(_: &UnixStream) -> io::Result<libc::uid_t> {
+    Err(io::Error::new(io::ErrorKind::Other, "not supported on this platform"))
+}
+
+/// Creates a pipe, whose ends are closed by `exec`.
+fn pipe() -> io::Result<(File, File)> {
+    let mut fds = [-1; 2];
+    if unsafe { libc::pipe(fds.as_mut_ptr()) } < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    let pipe = unsafe { (File::from_raw_fd(fds[0]), File::from_raw_fd(fds[1])) };
+    for &fd in &fds {
+        if unsafe { libc::fcntl(fd, libc::F_SETFD, libc::FD_CLOEXEC) } < 0 {
+            return Err(io::Error::last_os_error());
+        }
+    }
+    Ok(pipe)
+}
+
+fn set_nonblocking(file: &File) -> io::Result<()> {
+    unsafe {
+        let flags = libc::fcntl(file.as_raw_fd(), libc::F_GETFL);
+        if flags < 0 || libc::fcntl(file.as_raw_fd(), libc::F_SETFL, flags | libc::O_NONBLOCK) < 0 {
+            return Err(io::Error::last_os_error());
+        }
+    }
+    Ok(())
+}
+
+fn invalid_data(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+/// Sends the file descriptors `fds` on `stream`, along with a single byte.
+fn send_fds(stream: &UnixStream, fds: &[RawFd]) -> io::Result<()> {
+    let data_len = (fds.len() * mem::size_of::<RawFd>()) as u32;
+    // The control message buffer has to be aligned like `cmsghdr`.
+    let space = unsafe { libc::CMSG_SPACE(data_len) } as usize;
+    let mut control = vec![0u64; (space + 7) / 8];
+    let mut byte = [0u8];
+    let mut iov = libc::iovec { iov_base: byte.as_mut_ptr().cast(), iov_len: 1 };
+    unsafe {
+        let mut msg: libc::msghdr = mem::zeroed();
+        msg.msg_iov = &mut iov;
+        msg.msg_iovlen = 1;
+        msg.msg_control = control.as_mut_ptr().cast();
+        msg.msg_controllen = space as _;
+        let cmsg = libc::CMSG_FIRSTHDR(&msg);
+        (*cmsg).cmsg_level = libc::SOL_SOCKET;
+        (*cmsg).cmsg_type = libc::SCM_RIGHTS;
+        (*cmsg).cmsg_len = libc::CMSG_LEN(data_len) as _;
+        ptr::copy_nonoverlapping(fds.as_ptr(), libc::CMSG_DATA(cmsg).cast(), fds.len());
+        if libc::sendmsg(stream.as_raw_fd(), &msg, 0) < 0 {
+            return Err(io::Error::last_os_error());
+        }
+    }
+    Ok(())
+}
+
+/// Receives the file descriptors sent on `stream` by `send_fds`, at most `max`.
+fn receive_fds(stream: &UnixStream, max: usize) -> io::Result<Vec<RawFd>> {
+    let data_len = (max * mem::size_of::<RawFd>()) as u32;
+    let space = unsafe { libc::CMSG_SPACE(data_len) } as usize;
+    let mut control = vec![0u64; (space + 7) / 8];
+    let mut byte = [0u8];
+    let mut iov = libc::iovec { iov_base: byte.as_mut_ptr().cast(), iov_len: 1 };
+    let mut fds = vec![-1; max];
+    unsafe {
+        let mut msg: libc::msghdr = mem::zeroed();
+        msg.msg_iov = &mut iov;
+        msg.msg_iovlen = 1;
+        msg.msg_control = control.as_mut_ptr().cast();
+        msg.msg_controllen = space as _;
+        if libc::recvmsg(stream.as_raw_fd(), &mut msg, 0) < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let cmsg = libc::CMSG_FIRSTHDR(&msg);
+        if cmsg.is_null()
+            || (*cmsg).cmsg_level != libc::SOL_SOCKET
+            || (*cmsg).cmsg_type != libc::SCM_RIGHTS
+            || msg.msg_flags & libc::MSG_CTRUNC != 0
+        {
+            return Err(invalid_data("expected the standard streams of the client"));
+        }
+        let count =
+            ((*cmsg).cmsg_len as usize - libc::CMSG_LEN(0) as usize) / mem::size_of::<RawFd>();
+        ptr::copy_nonoverlapping(libc::CMSG_DATA(cmsg).cast(), fds.as_mut_ptr(), count);
+        fds.truncate(count);
+    }
+    Ok(fds)
+}
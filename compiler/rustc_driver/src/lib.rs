@@ -16,10 +16,13 @@
 
 use rustc_ast as ast;
 use rustc_codegen_ssa::{traits::CodegenBackend, CodegenResults};
-use rustc_data_structures::profiling::{get_resident_set_size, print_time_passes_entry};
+use rustc_data_structures::profiling::{
+    get_resident_set_size, print_time_passes_entry, TimePassesFormat,
+};
 use rustc_data_structures::sync::SeqCst;
+use rustc_errors::markdown;
 use rustc_errors::registry::{InvalidErrorCode, Registry};
-use rustc_errors::{ErrorReported, PResult};
+use rustc_errors::{ColorConfig, ErrorReported, PResult};
 use rustc_feature::find_gated_cfg;
 use rustc_hir::def_id::LOCAL_CRATE;
 use rustc_interface::util::{self, collect_crate_types, get_builtin_codegen_backend};
@@ -43,18 +46,20 @@
 use std::cmp::max;
 use std::default::Default;
 use std::env;
-use std::ffi::OsString;
+use std::ffi::{OsStr, OsString};
 use std::fs;
 use std::io::{self, Read, Write};
 use std::lazy::SyncLazy;
 use std::mem;
 use std::panic::{self, catch_unwind};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::{self, Command, Stdio};
 use std::str;
 use std::time::Instant;
 
 pub mod args;
+#[cfg(unix)]
+mod daemon;
 pub mod pretty;
 
 /// Exit status code used for successful compilation and help output.
@@ -116,15 +121,16 @@ fn after_analysis<'tcx>(
 
 #[derive(Default)]
 pub struct TimePassesCallbacks {
-    time_passes: bool,
+    time_passes: Option<TimePassesFormat>,
 }
 
 impl Callbacks for TimePassesCallbacks {
     fn config(&mut self, config: &mut interface::Config) {
         // If a --prints=... option has been given, we don't print the "total"
         // time because it will mess up the --prints output. See #64339.
-        self.time_passes = config.opts.prints.is_empty()
-            && (config.opts.debugging_opts.time_passes || config.opts.debugging_opts.time);
+        self.time_passes = (config.opts.prints.is_empty()
+            && (config.opts.debugging_opts.time_passes || config.opts.debugging_opts.time))
+            .then(|| config.opts.debugging_opts.time_passes_format);
         config.opts.trimmed_def_paths = TrimmedDefPaths::GoodPath;
     }
 }
@@ -202,7 +208,6 @@ fn run_compiler(
 ) -> interface::Result<()> {
     let args = args::arg_expand_all(at_args);
 
-    let diagnostic_output = emitter.map_or(DiagnosticOutput::Default, DiagnosticOutput::Raw);
     let matches = match handle_options(&args) {
         Some(matches) => matches,
         None => return Ok(()),
@@ -211,10 +216,24 @@ fn run_compiler(
     let sopts = config::build_session_options(&matches);
 
     if let Some(ref code) = matches.opt_str("explain") {
-        handle_explain(diagnostics_registry(), code, sopts.error_format);
+        let color = config::parse_color(&matches);
+        let terminal_width = sopts.debugging_opts.terminal_width;
+        handle_explain(diagnostics_registry(), code, sopts.error_format, color, terminal_width);
         return Ok(());
     }
 
+    if matches.opt_present("daemon") || matches.opt_present("daemon-connect") {
+        #[cfg(unix)]
+        daemon::run(&matches, &args, &sopts, callbacks, file_loader, emitter, make_codegen_backend);
+        #[cfg(not(unix))]
+        early_error(
+            sopts.error_format,
+            "`--daemon` and `--daemon-connect` are only supported on Unix",
+        );
+    }
+
+    let diagnostic_output = emitter.map_or(DiagnosticOutput::Default, DiagnosticOutput::Raw);
+
     let cfg = interface::parse_cfgspecs(matches.opt_strs("cfg"));
     let (odir, ofile) = make_output(&matches);
     let mut config = interface::Config {
@@ -528,11 +547,35 @@ fn stderr_isatty() -> bool {
     atty::is(atty::Stream::Stderr)
 }
 
-fn handle_explain(registry: Registry, code: &str, output: ErrorOutputType) {
+fn handle_explain(
+    registry: Registry,
+    code: &str,
+    output: ErrorOutputType,
+    color: ColorConfig,
+    terminal_width: Option<usize>,
+) {
     let normalised =
         if code.starts_with('E') { code.to_string() } else { format!("E{0:0>4}", code) };
     match registry.try_find_description(&normalised) {
         Ok(Some(description)) => {
+            let colored = match color {
+                ColorConfig::Always => true,
+                ColorConfig::Never => false,
+                // The pager of Windows doesn't understand ANSI escape codes.
+                ColorConfig::Auto => stdout_isatty() && !cfg!(windows),
+            };
+            // The Markdown is rendered for terminals, and printed as it is otherwise, unless colors
+            // were requested.
+            if stdout_isatty() || colored {
+                let text = markdown::render_to_string(description, terminal_width, colored);
+                if stdout_isatty() {
+                    show_content_with_pager(&text, colored);
+                } else {
+                    print!("{}", text);
+                }
+                return;
+            }
+
             let mut is_in_code_block = false;
             let mut text = String::new();
             // Slice off the leading newline and print.
@@ -550,11 +593,7 @@ fn handle_explain(registry: Registry, code: &str, output: ErrorOutputType) {
                 }
                 text.push('\n');
             }
-            if stdout_isatty() {
-                show_content_with_pager(&text);
-            } else {
-                print!("{}", text);
-            }
+            print!("{}", text);
         }
         Ok(None) => {
             early_error(output, &format!("no extended information for {}", code));
@@ -565,14 +604,19 @@ fn handle_explain(registry: Registry, code: &str, output: ErrorOutputType) {
     }
 }
 
-fn show_content_with_pager(content: &str) {
+fn show_content_with_pager(content: &str, colored: bool) {
     let pager_name = env::var_os("PAGER").unwrap_or_else(|| {
         if cfg!(windows) { OsString::from("more.com") } else { OsString::from("less") }
     });
 
     let mut fallback_to_println = false;
 
-    match Command::new(pager_name).stdin(Stdio::piped()).spawn() {
+    let mut pager = Command::new(&pager_name);
+    // `less` only shows colors with `-R`.
+    if colored && Path::new(&pager_name).file_stem() == Some(OsStr::new("less")) {
+        pager.arg("-R");
+    }
+    match pager.stdin(Stdio::piped()).spawn() {
         Ok(mut pager) => {
             if let Some(pipe) = pager.stdin.as_mut() {
                 if pipe.write_all(content.as_bytes()).is_err() {
@@ -1306,9 +1350,9 @@ pub fn main() -> ! {
         RunCompiler::new(&args, &mut callbacks).run()
     });
 
-    if callbacks.time_passes {
+    if let Some(format) = callbacks.time_passes {
         let end_rss = get_resident_set_size();
-        print_time_passes_entry("total", start_time.elapsed(), start_rss, end_rss);
+        print_time_passes_entry("total", start_time.elapsed(), start_rss, end_rss, format);
     }
 
     process::exit(exit_code)
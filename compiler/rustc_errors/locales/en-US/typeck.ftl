@@ -0,0 +1,84 @@
+# Messages of `rustc_typeck`.
+
+typeck-missing-cast-for-variadic-arg = can't pass `{$ty}` to variadic function
+    .suggestion = cast the value to `{$cast_ty}`
+    .help = cast the value to `{$cast_ty}`
+    .note = certain types, like `{$ty}`, must be casted before passing them to a variadic function, because of arcane ABI rules dictated by the C standard
+
+typeck-sized-unsized-cast = cannot cast thin pointer `{$expr_ty}` to fat pointer `{$cast_ty}`
+    .help =
+        Thin pointers are "simple" pointers: they are purely a reference to a
+        memory address.
+
+        Fat pointers are pointers referencing "Dynamically Sized Types" (also
+        called DST). DST don't have a statically known size, therefore they can
+        only exist behind some kind of pointers that contain additional
+        information. Slices and trait objects are DSTs. In the case of slices,
+        the additional information the fat pointer holds is their size.
+
+        To fix this error, don't try to cast directly between thin and fat
+        pointers.
+
+        For more information about casts, take a look at The Book:
+        https://doc.rust-lang.org/reference/expressions/operator-expr.html#type-cast-expressions
+
+# The arguments of the `wrong number of generic arguments` messages are:
+# - `$kind`: `lifetime` or `generic`, the kind of the arguments;
+# - `$quantifier`: `exactly`, `at-least` or `at-most`, and `$expected`: the
+#   number of parameters of that kind;
+# - `$provided`: the number of arguments of that kind.
+typeck-wrong-number-of-generic-args =
+    this {$def_kind} takes {typeck-quantifier}{$expected} {typeck-generic-arg-kind} {$expected ->
+        [one] argument
+       *[other] arguments
+    } but {$provided} {typeck-generic-arg-kind} {$provided ->
+        [one] argument was
+       *[other] arguments were
+    } supplied
+    .missing-generics = missing generics for {$def_kind} `{$def_path}`
+    .label = expected {typeck-quantifier}{$expected} {typeck-generic-arg-kind} {$expected ->
+        [one] argument
+       *[other] arguments
+    }
+    .supplied-label = supplied {$provided} {typeck-generic-arg-kind} {$provided ->
+        [one] argument
+       *[other] arguments
+    }
+    .add-args = add missing {typeck-generic-arg-kind} {$missing ->
+        [one] argument
+       *[other] arguments
+    }
+    .remove-lifetime-args = remove {$redundant ->
+        [one] this
+       *[other] these
+    } lifetime {$redundant_lifetimes ->
+        [one] argument
+       *[other] arguments
+    }
+    .remove-generic-args = remove {$redundant ->
+        [one] this
+       *[other] these
+    } generic {$redundant_generics ->
+        [one] argument
+       *[other] arguments
+    }
+    .remove-generics = remove these generics
+    .remove-parenthetical-generics = remove these parenthetical generics
+    .definition = {$def_kind} defined here, with {typeck-quantifier}{$expected} {typeck-generic-arg-kind} {$expected ->
+        [one] parameter
+       *[other] parameters
+    }{$expected ->
+        [0] {""}
+       *[other] : {$params}
+    }
+
+typeck-quantifier = {$quantifier ->
+        [at-least] at least{" "}
+        [at-most] at most{" "}
+       *[exactly] {""}
+    }
+
+typeck-generic-arg-kind = {$kind ->
+        [lifetime] lifetime
+       *[generic] generic
+    }
@@ -1,24 +1,30 @@
 use crate::snippet::Style;
+use crate::translation::{DiagnosticArg, IntoDiagnosticArg};
 use crate::CodeSuggestion;
 use crate::Level;
 use crate::Substitution;
 use crate::SubstitutionPart;
 use crate::SuggestionStyle;
 use crate::ToolMetadata;
-use rustc_lint_defs::Applicability;
+use rustc_lint_defs::{Applicability, LintLevelOrigin};
 use rustc_serialize::json::Json;
-use rustc_span::{MultiSpan, Span, DUMMY_SP};
+use rustc_span::{DiagnosticMessage, MultiSpan, Span, DUMMY_SP};
 use std::fmt;
 
 #[must_use]
 #[derive(Clone, Debug, PartialEq, Hash, Encodable, Decodable)]
 pub struct Diagnostic {
     pub level: Level,
-    pub message: Vec<(String, Style)>,
+    pub message: Vec<(DiagnosticMessage, Style)>,
     pub code: Option<DiagnosticId>,
     pub span: MultiSpan,
     pub children: Vec<SubDiagnostic>,
     pub suggestions: Vec<CodeSuggestion>,
+    /// The arguments of the translated messages of this diagnostic and of its
+    /// children, see [`Diagnostic::set_arg()`].
+    pub args: Vec<DiagnosticArg>,
+    /// For lints, where their level was set.
+    pub lint_level_origin: Option<LintLevelOrigin>,
 
     /// This is not used for highlighting or rendering any error message.  Rather, it can be used
     /// as a sort key to sort a buffer of diagnostics.  By default, it is the primary span of
@@ -37,7 +43,7 @@ pub enum DiagnosticId {
 #[derive(Clone, Debug, PartialEq, Hash, Encodable, Decodable)]
 pub struct SubDiagnostic {
     pub level: Level,
-    pub message: Vec<(String, Style)>,
+    pub message: Vec<(DiagnosticMessage, Style)>,
     pub span: MultiSpan,
     pub render_span: Option<MultiSpan>,
 }
@@ -78,18 +84,24 @@ pub enum StringPart {
 }
 
 impl Diagnostic {
-    pub fn new(level: Level, message: &str) -> Self {
+    pub fn new(level: Level, message: impl Into<DiagnosticMessage>) -> Self {
         Diagnostic::new_with_code(level, None, message)
     }
 
-    pub fn new_with_code(level: Level, code: Option<DiagnosticId>, message: &str) -> Self {
+    pub fn new_with_code(
+        level: Level,
+        code: Option<DiagnosticId>,
+        message: impl Into<DiagnosticMessage>,
+    ) -> Self {
         Diagnostic {
             level,
-            message: vec![(message.to_owned(), Style::NoStyle)],
+            message: vec![(message.into(), Style::NoStyle)],
             code,
             span: MultiSpan::new(),
             children: vec![],
             suggestions: vec![],
+            args: vec![],
+            lint_level_origin: None,
             sort_span: DUMMY_SP,
         }
     }
@@ -128,7 +140,7 @@ pub fn cancelled(&self) -> bool {
     ///
     /// This span is *not* considered a ["primary span"][`MultiSpan`]; only
     /// the `Span` supplied when creating the diagnostic is primary.
-    pub fn span_label<T: Into<String>>(&mut self, span: Span, label: T) -> &mut Self {
+    pub fn span_label<T: Into<DiagnosticMessage>>(&mut self, span: Span, label: T) -> &mut Self {
         self.span.push_span_label(span, label.into());
         self
     }
@@ -136,10 +148,8 @@ pub fn span_label<T: Into<String>>(&mut self, span: Span, label: T) -> &mut Self
     pub fn replace_span_with(&mut self, after: Span) -> &mut Self {
         let before = self.span.clone();
         self.set_span(after);
-        for span_label in before.span_labels() {
-            if let Some(label) = span_label.label {
-                self.span_label(after, label);
-            }
+        for (_, label) in before.span_label_messages() {
+            self.span_label(after, label.clone());
         }
         self
     }
@@ -232,7 +242,7 @@ pub fn note_trait_signature(&mut self, name: String, signature: String) -> &mut
     }
 
     /// Add a note attached to this diagnostic.
-    pub fn note(&mut self, msg: &str) -> &mut Self {
+    pub fn note(&mut self, msg: impl Into<DiagnosticMessage>) -> &mut Self {
         self.sub(Level::Note, msg, MultiSpan::new(), None);
         self
     }
@@ -244,42 +254,57 @@ pub fn highlighted_note(&mut self, msg: Vec<(String, Style)>) -> &mut Self {
 
     /// Prints the span with a note above it.
     /// This is like [`Diagnostic::note()`], but it gets its own span.
-    crate fn span_note<S: Into<MultiSpan>>(&mut self, sp: S, msg: &str) -> &mut Self {
+    crate fn span_note<S: Into<MultiSpan>>(
+        &mut self,
+        sp: S,
+        msg: impl Into<DiagnosticMessage>,
+    ) -> &mut Self {
         self.sub(Level::Note, msg, sp.into(), None);
         self
     }
 
     /// Add a warning attached to this diagnostic.
-    crate fn warn(&mut self, msg: &str) -> &mut Self {
+    crate fn warn(&mut self, msg: impl Into<DiagnosticMessage>) -> &mut Self {
         self.sub(Level::Warning, msg, MultiSpan::new(), None);
         self
     }
 
     /// Prints the span with a warning above it.
     /// This is like [`Diagnostic::warn()`], but it gets its own span.
-    crate fn span_warn<S: Into<MultiSpan>>(&mut self, sp: S, msg: &str) -> &mut Self {
+    crate fn span_warn<S: Into<MultiSpan>>(
+        &mut self,
+        sp: S,
+        msg: impl Into<DiagnosticMessage>,
+    ) -> &mut Self {
         self.sub(Level::Warning, msg, sp.into(), None);
         self
     }
 
     /// Add a help message attached to this diagnostic.
-    crate fn help(&mut self, msg: &str) -> &mut Self {
+    crate fn help(&mut self, msg: impl Into<DiagnosticMessage>) -> &mut Self {
         self.sub(Level::Help, msg, MultiSpan::new(), None);
         self
     }
 
     /// Prints the span with some help above it.
     /// This is like [`Diagnostic::help()`], but it gets its own span.
-    crate fn span_help<S: Into<MultiSpan>>(&mut self, sp: S, msg: &str) -> &mut Self {
+    crate fn span_help<S: Into<MultiSpan>>(
+        &mut self,
+        sp: S,
+        msg: impl Into<DiagnosticMessage>,
+    ) -> &mut Self {
         self.sub(Level::Help, msg, sp.into(), None);
         self
     }
 
     /// Show a suggestion that has multiple parts to it.
     /// In other words, multiple changes need to be applied as part of this suggestion.
+    ///
+    /// The parts may be in different files, e.g. to add an import to the crate root and use it
+    /// at the location of the error.
     pub fn multipart_suggestion(
         &mut self,
-        msg: &str,
+        msg: impl Into<DiagnosticMessage>,
         suggestion: Vec<(Span, String)>,
         applicability: Applicability,
     ) -> &mut Self {
@@ -294,7 +319,7 @@ pub fn multipart_suggestion(
     /// [`Diagnostic::multipart_suggestion()`] but you can set the [`SuggestionStyle`].
     pub fn multipart_suggestion_with_style(
         &mut self,
-        msg: &str,
+        msg: impl Into<DiagnosticMessage>,
         suggestion: Vec<(Span, String)>,
         applicability: Applicability,
         style: SuggestionStyle,
@@ -307,7 +332,7 @@ pub fn multipart_suggestion_with_style(
                     .map(|(span, snippet)| SubstitutionPart { snippet, span })
                     .collect(),
             }],
-            msg: msg.to_owned(),
+            msg: msg.into(),
             style,
             applicability,
             tool_metadata: Default::default(),
@@ -323,7 +348,7 @@ pub fn multipart_suggestion_with_style(
     /// improve understandability.
     pub fn tool_only_multipart_suggestion(
         &mut self,
-        msg: &str,
+        msg: impl Into<DiagnosticMessage>,
         suggestion: Vec<(Span, String)>,
         applicability: Applicability,
     ) -> &mut Self {
@@ -335,7 +360,7 @@ pub fn tool_only_multipart_suggestion(
                     .map(|(span, snippet)| SubstitutionPart { snippet, span })
                     .collect(),
             }],
-            msg: msg.to_owned(),
+            msg: msg.into(),
             style: SuggestionStyle::CompletelyHidden,
             applicability,
             tool_metadata: Default::default(),
@@ -363,7 +388,7 @@ pub fn tool_only_multipart_suggestion(
     pub fn span_suggestion(
         &mut self,
         sp: Span,
-        msg: &str,
+        msg: impl Into<DiagnosticMessage>,
         suggestion: String,
         applicability: Applicability,
     ) -> &mut Self {
@@ -381,7 +406,7 @@ pub fn span_suggestion(
     pub fn span_suggestion_with_style(
         &mut self,
         sp: Span,
-        msg: &str,
+        msg: impl Into<DiagnosticMessage>,
         suggestion: String,
         applicability: Applicability,
         style: SuggestionStyle,
@@ -390,7 +415,7 @@ pub fn span_suggestion_with_style(
             substitutions: vec![Substitution {
                 parts: vec![SubstitutionPart { snippet: suggestion, span: sp }],
             }],
-            msg: msg.to_owned(),
+            msg: msg.into(),
             style,
             applicability,
             tool_metadata: Default::default(),
@@ -402,7 +427,7 @@ pub fn span_suggestion_with_style(
     pub fn span_suggestion_verbose(
         &mut self,
         sp: Span,
-        msg: &str,
+        msg: impl Into<DiagnosticMessage>,
         suggestion: String,
         applicability: Applicability,
     ) -> &mut Self {
@@ -421,7 +446,7 @@ pub fn span_suggestion_verbose(
     pub fn span_suggestions(
         &mut self,
         sp: Span,
-        msg: &str,
+        msg: impl Into<DiagnosticMessage>,
         suggestions: impl Iterator<Item = String>,
         applicability: Applicability,
     ) -> &mut Self {
@@ -429,7 +454,32 @@ pub fn span_suggestions(
             substitutions: suggestions
                 .map(|snippet| Substitution { parts: vec![SubstitutionPart { snippet, span: sp }] })
                 .collect(),
-            msg: msg.to_owned(),
+            msg: msg.into(),
+            style: SuggestionStyle::ShowCode,
+            applicability,
+            tool_metadata: Default::default(),
+        });
+        self
+    }
+
+    /// Prints out a message with multiple alternative suggestions, each of which has multiple
+    /// parts. See also [`Diagnostic::multipart_suggestion()`].
+    pub fn multipart_suggestions(
+        &mut self,
+        msg: impl Into<DiagnosticMessage>,
+        suggestions: impl Iterator<Item = Vec<(Span, String)>>,
+        applicability: Applicability,
+    ) -> &mut Self {
+        self.suggestions.push(CodeSuggestion {
+            substitutions: suggestions
+                .map(|sugg| Substitution {
+                    parts: sugg
+                        .into_iter()
+                        .map(|(span, snippet)| SubstitutionPart { snippet, span })
+                        .collect(),
+                })
+                .collect(),
+            msg: msg.into(),
             style: SuggestionStyle::ShowCode,
             applicability,
             tool_metadata: Default::default(),
@@ -444,7 +494,7 @@ pub fn span_suggestions(
     pub fn span_suggestion_short(
         &mut self,
         sp: Span,
-        msg: &str,
+        msg: impl Into<DiagnosticMessage>,
         suggestion: String,
         applicability: Applicability,
     ) -> &mut Self {
@@ -467,7 +517,7 @@ pub fn span_suggestion_short(
     pub fn span_suggestion_hidden(
         &mut self,
         sp: Span,
-        msg: &str,
+        msg: impl Into<DiagnosticMessage>,
         suggestion: String,
         applicability: Applicability,
     ) -> &mut Self {
@@ -488,7 +538,7 @@ pub fn span_suggestion_hidden(
     pub fn tool_only_span_suggestion(
         &mut self,
         sp: Span,
-        msg: &str,
+        msg: impl Into<DiagnosticMessage>,
         suggestion: String,
         applicability: Applicability,
     ) -> &mut Self {
@@ -506,13 +556,13 @@ pub fn tool_only_span_suggestion(
     /// the suggestion in a tool-specific way, as it may not even directly involve Rust code.
     pub fn tool_only_suggestion_with_metadata(
         &mut self,
-        msg: &str,
+        msg: impl Into<DiagnosticMessage>,
         applicability: Applicability,
         tool_metadata: Json,
     ) {
         self.suggestions.push(CodeSuggestion {
             substitutions: vec![],
-            msg: msg.to_owned(),
+            msg: msg.into(),
             style: SuggestionStyle::CompletelyHidden,
             applicability,
             tool_metadata: ToolMetadata::new(tool_metadata),
@@ -541,17 +591,33 @@ pub fn get_code(&self) -> Option<DiagnosticId> {
         self.code.clone()
     }
 
-    crate fn set_primary_message<M: Into<String>>(&mut self, msg: M) -> &mut Self {
+    pub fn set_lint_level_origin(&mut self, origin: LintLevelOrigin) -> &mut Self {
+        self.lint_level_origin = Some(origin);
+        self
+    }
+
+    crate fn set_primary_message<M: Into<DiagnosticMessage>>(&mut self, msg: M) -> &mut Self {
         self.message[0] = (msg.into(), Style::NoStyle);
         self
     }
 
+    /// Sets the argument `name` of the translated messages of this diagnostic.
+    ///
+    /// The arguments are shared by the messages of the diagnostic, of its
+    /// labels, of its children and of its suggestions.
+    pub fn set_arg(&mut self, name: &str, arg: impl IntoDiagnosticArg) -> &mut Self {
+        self.args.push((name.to_owned(), arg.into_diagnostic_arg()));
+        self
+    }
+
+    /// Returns the message of the diagnostic. Messages that have not been
+    /// translated yet are returned as their identifiers.
     pub fn message(&self) -> String {
-        self.message.iter().map(|i| i.0.as_str()).collect::<String>()
+        self.message.iter().map(|i| i.0.as_str_or_identifier()).collect::<String>()
     }
 
-    pub fn styled_message(&self) -> &Vec<(String, Style)> {
-        &self.message
+    pub fn styled_message(&self) -> Vec<(String, Style)> {
+        styled_message(&self.message)
     }
 
     /// Convenience function for internal use, clients should use one of the
@@ -561,13 +627,13 @@ pub fn styled_message(&self) -> &Vec<(String, Style)> {
     pub fn sub(
         &mut self,
         level: Level,
-        message: &str,
+        message: impl Into<DiagnosticMessage>,
         span: MultiSpan,
         render_span: Option<MultiSpan>,
     ) {
         let sub = SubDiagnostic {
             level,
-            message: vec![(message.to_owned(), Style::NoStyle)],
+            message: vec![(message.into(), Style::NoStyle)],
             span,
             render_span,
         };
@@ -583,17 +649,24 @@ fn sub_with_highlights(
         span: MultiSpan,
         render_span: Option<MultiSpan>,
     ) {
+        let message = message.into_iter().map(|(m, style)| (m.into(), style)).collect();
         let sub = SubDiagnostic { level, message, span, render_span };
         self.children.push(sub);
     }
 }
 
 impl SubDiagnostic {
+    /// Returns the message of the sub-diagnostic. Messages that have not been
+    /// translated yet are returned as their identifiers.
     pub fn message(&self) -> String {
-        self.message.iter().map(|i| i.0.as_str()).collect::<String>()
+        self.message.iter().map(|i| i.0.as_str_or_identifier()).collect::<String>()
     }
 
-    pub fn styled_message(&self) -> &Vec<(String, Style)> {
-        &self.message
+    pub fn styled_message(&self) -> Vec<(String, Style)> {
+        styled_message(&self.message)
     }
 }
+
+fn styled_message(message: &[(DiagnosticMessage, Style)]) -> Vec<(String, Style)> {
+    message.iter().map(|(m, style)| (m.as_str_or_identifier().to_owned(), *style)).collect()
+}
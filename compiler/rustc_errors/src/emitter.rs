@@ -11,16 +11,18 @@
 
 use rustc_lint_defs::FutureBreakage;
 use rustc_span::source_map::SourceMap;
-use rustc_span::{MultiSpan, SourceFile, Span};
+use rustc_span::{FileName, MultiSpan, RealFileName, SourceFile, Span};
 
 use crate::snippet::{Annotation, AnnotationType, Line, MultilineAnnotation, Style, StyledString};
 use crate::styled_buffer::StyledBuffer;
-use crate::{CodeSuggestion, Diagnostic, DiagnosticId, Level, SubDiagnostic, SuggestionStyle};
+use crate::{
+    CodeSuggestion, Diagnostic, DiagnosticId, Level, SplicedLines, SubDiagnostic, SuggestionStyle,
+};
 
 use rustc_lint_defs::pluralize;
 
 use rustc_data_structures::fx::FxHashMap;
-use rustc_data_structures::sync::Lrc;
+use rustc_data_structures::sync::{self, Lrc};
 use rustc_span::hygiene::{ExpnKind, MacroKind};
 use std::borrow::Cow;
 use std::cmp::{max, min, Reverse};
@@ -233,7 +235,7 @@ fn primary_span_formatted<'a>(
                // don't display multipart suggestions as labels
                sugg.substitutions[0].parts.len() == 1 &&
                // don't display long messages as labels
-               sugg.msg.split_whitespace().count() < 10 &&
+               sugg.msg.as_str_or_identifier().split_whitespace().count() < 10 &&
                // don't display multiline suggestions as labels
                !sugg.substitutions[0].parts[0].snippet.contains('\n') &&
                ![
@@ -249,12 +251,12 @@ fn primary_span_formatted<'a>(
                 let msg = if substitution.is_empty() || sugg.style.hide_inline() {
                     // This substitution is only removal OR we explicitly don't want to show the
                     // code inline (`hide_inline`). Therefore, we don't show the substitution.
-                    format!("help: {}", sugg.msg)
+                    format!("help: {}", sugg.msg.as_str_or_identifier())
                 } else {
                     // Show the default suggestion text with the substitution
                     format!(
                         "help: {}{}: `{}`",
-                        sugg.msg,
+                        sugg.msg.as_str_or_identifier(),
                         if self
                             .source_map()
                             .map(|sm| is_case_difference(
@@ -332,7 +334,7 @@ fn fix_multispans_in_extern_macros_and_render_macro_backtrace(
 
                 children.push(SubDiagnostic {
                     level: Level::Note,
-                    message: vec![(msg, Style::NoStyle)],
+                    message: vec![(msg.into(), Style::NoStyle)],
                     span: MultiSpan::new(),
                     render_span: None,
                 });
@@ -539,6 +541,62 @@ fn source_map(&self) -> Option<&Lrc<SourceMap>> {
     fn emit_diagnostic(&mut self, _: &Diagnostic) {}
 }
 
+/// An emitter that emits everything to two emitters, e.g. human readable
+/// diagnostics to the terminal and JSON diagnostics to a file.
+///
+/// The `Handler` deduplicates diagnostics and flushes delayed bugs before
+/// they reach its emitter, and serializes calls to it when there are several
+/// threads, so both emitters see the same diagnostics in the same order.
+pub struct TeeEmitter {
+    primary: Box<dyn Emitter + sync::Send>,
+    secondary: Box<dyn Emitter + sync::Send>,
+}
+
+impl TeeEmitter {
+    /// Creates an emitter for `primary` and `secondary`. Questions about the
+    /// output, like whether it supports colors, are answered by `primary`.
+    pub fn new(
+        primary: Box<dyn Emitter + sync::Send>,
+        secondary: Box<dyn Emitter + sync::Send>,
+    ) -> TeeEmitter {
+        TeeEmitter { primary, secondary }
+    }
+}
+
+impl Emitter for TeeEmitter {
+    fn emit_diagnostic(&mut self, diag: &Diagnostic) {
+        self.primary.emit_diagnostic(diag);
+        self.secondary.emit_diagnostic(diag);
+    }
+
+    fn emit_artifact_notification(&mut self, path: &Path, artifact_type: &str) {
+        self.primary.emit_artifact_notification(path, artifact_type);
+        self.secondary.emit_artifact_notification(path, artifact_type);
+    }
+
+    fn emit_future_breakage_report(&mut self, diags: Vec<(FutureBreakage, Diagnostic)>) {
+        self.primary.emit_future_breakage_report(diags.clone());
+        self.secondary.emit_future_breakage_report(diags);
+    }
+
+    fn emit_unused_externs(&mut self, lint_level: &str, unused_externs: &[&str]) {
+        self.primary.emit_unused_externs(lint_level, unused_externs);
+        self.secondary.emit_unused_externs(lint_level, unused_externs);
+    }
+
+    fn should_show_explain(&self) -> bool {
+        self.primary.should_show_explain()
+    }
+
+    fn supports_color(&self) -> bool {
+        self.primary.supports_color()
+    }
+
+    fn source_map(&self) -> Option<&Lrc<SourceMap>> {
+        self.primary.source_map()
+    }
+}
+
 /// Maximum number of lines we will print for a multiline suggestion; arbitrary.
 ///
 /// This should be replaced with a more involved mechanism to output multiline suggestions that
@@ -549,6 +607,78 @@ fn emit_diagnostic(&mut self, _: &Diagnostic) {}
 /// Arbitrary, but taken from trait import suggestion limit
 pub const MAX_SUGGESTIONS: usize = 4;
 
+/// Whether to write file locations and error codes as hyperlinks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TerminalUrl {
+    No,
+    Yes,
+    /// Only if the output is a terminal that is known to support them.
+    Auto,
+}
+
+/// Returns `true` if the terminal rustc is running in is known to support OSC 8 hyperlinks.
+/// Terminals that don't support them may print the escape sequences, so this errs on the
+/// side of caution.
+fn terminal_supports_hyperlinks() -> bool {
+    let var = |name| std::env::var(name).ok();
+    if var("WT_SESSION").is_some() || var("DOMTERM").is_some() {
+        return true;
+    }
+    // GNOME Terminal and other VTE-based terminals, since VTE 0.50.
+    if var("VTE_VERSION").and_then(|v| v.parse::<u32>().ok()).map_or(false, |v| v >= 5000) {
+        return true;
+    }
+    if let Some(program) = var("TERM_PROGRAM") {
+        if ["iTerm.app", "WezTerm", "vscode", "Hyper"].contains(&&*program) {
+            return true;
+        }
+    }
+    var("TERM").map_or(false, |term| ["xterm-kitty", "alacritty", "foot"].contains(&&*term))
+}
+
+/// Returns the `file://` URL of the file `name`, if it has an absolute path. The path is
+/// remapped like the paths embedded in build artifacts, so that `--remap-path-prefix` can
+/// be used to link to the sources from somewhere else, e.g. outside of a container.
+fn file_url(sm: &SourceMap, name: &FileName) -> Option<String> {
+    let path = match name {
+        FileName::Real(RealFileName::LocalPath(path)) => {
+            let path = if path.is_relative() {
+                std::env::current_dir().ok()?.join(path)
+            } else {
+                path.clone()
+            };
+            sm.path_mapping().map_prefix(path).0
+        }
+        FileName::Real(RealFileName::Remapped { virtual_name, .. }) => virtual_name.clone(),
+        _ => return None,
+    };
+    if !path.is_absolute() {
+        return None;
+    }
+
+    let path = path.to_string_lossy();
+    let mut url = String::from("file://");
+    // Windows paths like `C:\foo` become `file:///C:/foo`.
+    if !path.starts_with('/') {
+        url.push('/');
+    }
+    for byte in path.bytes() {
+        match byte {
+            b'\\' if cfg!(windows) => url.push('/'),
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' | b':' => {
+                url.push(byte as char)
+            }
+            _ => url.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    Some(url)
+}
+
+/// Returns the URL of the explanation of the error `code` in the error index.
+fn error_code_url(code: &str) -> String {
+    format!("https://doc.rust-lang.org/error-index.html#{}", code)
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ColorConfig {
     Auto,
@@ -589,6 +719,8 @@ pub struct EmitterWriter {
     terminal_width: Option<usize>,
 
     macro_backtrace: bool,
+    /// Whether file locations and error codes are written as hyperlinks.
+    terminal_urls: bool,
 }
 
 #[derive(Debug)]
@@ -616,6 +748,7 @@ pub fn stderr(
             ui_testing: false,
             terminal_width,
             macro_backtrace,
+            terminal_urls: false,
         }
     }
 
@@ -636,6 +769,7 @@ pub fn new(
             ui_testing: false,
             terminal_width,
             macro_backtrace,
+            terminal_urls: false,
         }
     }
 
@@ -644,6 +778,21 @@ pub fn ui_testing(mut self, ui_testing: bool) -> Self {
         self
     }
 
+    /// Sets whether file locations and error codes are written as OSC 8 hyperlinks, to the
+    /// source files and to the error index.
+    pub fn terminal_urls(mut self, terminal_urls: TerminalUrl) -> Self {
+        self.terminal_urls = match terminal_urls {
+            TerminalUrl::No => false,
+            TerminalUrl::Yes => true,
+            TerminalUrl::Auto => self.dst.supports_color() && terminal_supports_hyperlinks(),
+        };
+        self
+    }
+
+    fn file_url(&self, sm: &SourceMap, name: &FileName) -> Option<String> {
+        if self.terminal_urls { file_url(sm, name) } else { None }
+    }
+
     fn maybe_anonymized(&self, line_num: usize) -> String {
         if self.ui_testing { ANONYMIZED_LINE_NUM.to_string() } else { line_num.to_string() }
     }
@@ -1267,7 +1416,11 @@ fn emit_message_default(
             // only render error codes, not lint codes
             if let Some(DiagnosticId::Error(ref code)) = *code {
                 buffer.append(0, "[", Style::Level(*level));
-                buffer.append(0, &code, Style::Level(*level));
+                if self.terminal_urls {
+                    buffer.append_link(0, &code, Style::Level(*level), &error_code_url(code));
+                } else {
+                    buffer.append(0, &code, Style::Level(*level));
+                }
                 buffer.append(0, "]", Style::Level(*level));
             }
             let header_style = if is_secondary { Style::HeaderMsg } else { Style::MainHeaderMsg };
@@ -1319,30 +1472,39 @@ fn emit_message_default(
                     let buffer_msg_line_offset = buffer.num_lines();
 
                     buffer.prepend(buffer_msg_line_offset, "--> ", Style::LineNumber);
-                    buffer.append(
-                        buffer_msg_line_offset,
-                        &format!(
-                            "{}:{}:{}",
-                            loc.file.name.prefer_local(),
-                            sm.doctest_offset_line(&loc.file.name, loc.line),
-                            loc.col.0 + 1,
-                        ),
-                        Style::LineAndColumn,
+                    let loc_str = format!(
+                        "{}:{}:{}",
+                        loc.file.name.prefer_local(),
+                        sm.doctest_offset_line(&loc.file.name, loc.line),
+                        loc.col.0 + 1,
                     );
+                    if let Some(url) = self.file_url(sm, &loc.file.name) {
+                        buffer.append_link(
+                            buffer_msg_line_offset,
+                            &loc_str,
+                            Style::LineAndColumn,
+                            &url,
+                        );
+                    } else {
+                        buffer.append(buffer_msg_line_offset, &loc_str, Style::LineAndColumn);
+                    }
                     for _ in 0..max_line_num_len {
                         buffer.prepend(buffer_msg_line_offset, " ", Style::NoStyle);
                     }
                 } else {
-                    buffer.prepend(
-                        0,
-                        &format!(
-                            "{}:{}:{}: ",
-                            loc.file.name.prefer_local(),
-                            sm.doctest_offset_line(&loc.file.name, loc.line),
-                            loc.col.0 + 1,
-                        ),
-                        Style::LineAndColumn,
+                    // The trailing `: ` is not part of the link.
+                    buffer.prepend(0, ": ", Style::LineAndColumn);
+                    let loc_str = format!(
+                        "{}:{}:{}",
+                        loc.file.name.prefer_local(),
+                        sm.doctest_offset_line(&loc.file.name, loc.line),
+                        loc.col.0 + 1,
                     );
+                    if let Some(url) = self.file_url(sm, &loc.file.name) {
+                        buffer.prepend_link(0, &loc_str, Style::LineAndColumn, &url);
+                    } else {
+                        buffer.prepend(0, &loc_str, Style::LineAndColumn);
+                    }
                 }
             } else if !self.short_message {
                 // remember where we are in the output buffer for easy reference
@@ -1368,7 +1530,16 @@ fn emit_message_default(
                 } else {
                     format!("{}", annotated_file.file.name.prefer_local())
                 };
-                buffer.append(buffer_msg_line_offset + 1, &loc, Style::LineAndColumn);
+                if let Some(url) = self.file_url(sm, &annotated_file.file.name) {
+                    buffer.append_link(
+                        buffer_msg_line_offset + 1,
+                        &loc,
+                        Style::LineAndColumn,
+                        &url,
+                    );
+                } else {
+                    buffer.append(buffer_msg_line_offset + 1, &loc, Style::LineAndColumn);
+                }
                 for _ in 0..max_line_num_len {
                     buffer.prepend(buffer_msg_line_offset + 1, " ", Style::NoStyle);
                 }
@@ -1585,7 +1756,7 @@ fn emit_suggestion_default(
 
         self.msg_to_buffer(
             &mut buffer,
-            &[(suggestion.msg.to_owned(), Style::NoStyle)],
+            &[(suggestion.msg.as_str_or_identifier().to_owned(), Style::NoStyle)],
             max_line_num_len,
             "suggestion",
             Some(Style::HeaderMsg),
@@ -1593,112 +1764,169 @@ fn emit_suggestion_default(
 
         let mut row_num = 2;
         let mut notice_capitalization = false;
-        for (complete, parts, only_capitalization) in suggestions.iter().take(MAX_SUGGESTIONS) {
-            notice_capitalization |= only_capitalization;
-            // Only show underline if the suggestion spans a single line and doesn't cover the
-            // entirety of the code output. If you have multiple replacements in the same line
-            // of code, show the underline.
-            let show_underline = !(parts.len() == 1 && parts[0].snippet.trim() == complete.trim())
-                && complete.lines().count() == 1;
-
-            let lines = sm
-                .span_to_lines(parts[0].span)
-                .expect("span_to_lines failed when emitting suggestion");
-
-            assert!(!lines.lines.is_empty() || parts[0].span.is_dummy());
-
-            let line_start = sm.lookup_char_pos(parts[0].span.lo()).line;
-            draw_col_separator_no_space(&mut buffer, 1, max_line_num_len + 1);
-            let mut lines = complete.lines();
-            for (line_pos, line) in lines.by_ref().take(MAX_SUGGESTION_HIGHLIGHT_LINES).enumerate()
-            {
-                // Print the span column to avoid confusion
-                buffer.puts(
-                    row_num,
-                    0,
-                    &self.maybe_anonymized(line_start + line_pos),
-                    Style::LineNumber,
-                );
-                // print the suggestion
-                draw_col_separator(&mut buffer, row_num, max_line_num_len + 1);
-                buffer.append(row_num, &replace_tabs(line), Style::NoStyle);
-                row_num += 1;
-            }
-
-            // This offset and the ones below need to be signed to account for replacement code
-            // that is shorter than the original code.
-            let mut offsets: Vec<(usize, isize)> = Vec::new();
-            // Only show an underline in the suggestions if the suggestion is not the
-            // entirety of the code being shown and the displayed code is not multiline.
-            if show_underline {
-                draw_col_separator(&mut buffer, row_num, max_line_num_len + 1);
-                for part in parts {
-                    let span_start_pos = sm.lookup_char_pos(part.span.lo()).col_display;
-                    let span_end_pos = sm.lookup_char_pos(part.span.hi()).col_display;
-
-                    // Do not underline the leading...
-                    let start = part.snippet.len().saturating_sub(part.snippet.trim_start().len());
-                    // ...or trailing spaces. Account for substitutions containing unicode
-                    // characters.
-                    let sub_len: usize = part
-                        .snippet
-                        .trim()
-                        .chars()
-                        .map(|ch| unicode_width::UnicodeWidthChar::width(ch).unwrap_or(1))
-                        .sum();
-
-                    let offset: isize = offsets
-                        .iter()
-                        .filter_map(
-                            |(start, v)| if span_start_pos <= *start { None } else { Some(v) },
-                        )
-                        .sum();
-                    let underline_start = (span_start_pos + start) as isize + offset;
-                    let underline_end = (span_start_pos + start + sub_len) as isize + offset;
-                    assert!(underline_start >= 0 && underline_end >= 0);
-                    for p in underline_start..underline_end {
-                        buffer.putc(
-                            row_num,
-                            ((max_line_num_len + 3) as isize + p) as usize,
-                            '^',
-                            Style::UnderlinePrimary,
-                        );
+        for hunks in suggestions.iter().take(MAX_SUGGESTIONS) {
+            let file_of = |hunk: &SplicedLines| sm.lookup_source_file(hunk.parts[0].span.lo());
+            let multiple_files =
+                hunks.iter().any(|hunk| file_of(hunk).start_pos != file_of(&hunks[0]).start_pos);
+            let mut prev_file: Option<Lrc<SourceFile>> = None;
+            for SplicedLines { text: complete, parts, only_capitalization } in hunks {
+                notice_capitalization |= only_capitalization;
+                // A hunk which only removes code from a line, like the prefix of a path next to
+                // an added import, shows the line as it is, with the removed code underlined.
+                let first = sm.lookup_char_pos(parts[0].span.lo());
+                let removed_line = if hunks.len() > 1
+                    && parts.iter().all(|part| {
+                        part.snippet.is_empty()
+                            && !sm.is_multiline(part.span)
+                            && sm.lookup_char_pos(part.span.lo()).line == first.line
+                    }) {
+                    first.file.get_line(first.line - 1).map(|line| line.into_owned())
+                } else {
+                    None
+                };
+                let complete = removed_line.as_deref().unwrap_or(&complete[..]);
+                // Only show underline if the suggestion spans a single line and doesn't cover the
+                // entirety of the code output. If you have multiple replacements in the same line
+                // of code, show the underline.
+                let show_underline = !(parts.len() == 1
+                    && parts[0].snippet.trim() == complete.trim())
+                    && complete.lines().count() == 1;
+
+                let lines = sm
+                    .span_to_lines(parts[0].span)
+                    .expect("span_to_lines failed when emitting suggestion");
+
+                assert!(!lines.lines.is_empty() || parts[0].span.is_dummy());
+
+                let line_start = sm.lookup_char_pos(parts[0].span.lo()).line;
+                draw_col_separator_no_space(&mut buffer, 1, max_line_num_len + 1);
+                if prev_file.map_or(false, |file| file.start_pos == lines.file.start_pos) {
+                    // Elide the lines between hunks of the same file.
+                    buffer.puts(row_num, 0, "...", Style::LineNumber);
+                    row_num += 1;
+                } else if multiple_files {
+                    // Say which file this part of the suggestion is in.
+                    let file = &lines.file;
+                    buffer.puts(row_num, max_line_num_len, "::: ", Style::LineNumber);
+                    let loc = format!(
+                        "{}:{}",
+                        file.name.prefer_local(),
+                        sm.doctest_offset_line(&file.name, line_start)
+                    );
+                    if let Some(url) = self.file_url(sm, &file.name) {
+                        buffer.append_link(row_num, &loc, Style::LineAndColumn, &url);
+                    } else {
+                        buffer.append(row_num, &loc, Style::LineAndColumn);
                     }
-                    // underline removals too
-                    if underline_start == underline_end {
-                        for p in underline_start - 1..underline_start + 1 {
+                    draw_col_separator_no_space(&mut buffer, row_num + 1, max_line_num_len + 1);
+                    row_num += 2;
+                }
+                prev_file = Some(lines.file.clone());
+                let mut lines = complete.lines();
+                for (line_pos, line) in
+                    lines.by_ref().take(MAX_SUGGESTION_HIGHLIGHT_LINES).enumerate()
+                {
+                    // Print the span column to avoid confusion
+                    buffer.puts(
+                        row_num,
+                        0,
+                        &self.maybe_anonymized(line_start + line_pos),
+                        Style::LineNumber,
+                    );
+                    // print the suggestion
+                    draw_col_separator(&mut buffer, row_num, max_line_num_len + 1);
+                    buffer.append(row_num, &replace_tabs(line), Style::NoStyle);
+                    row_num += 1;
+                }
+
+                // This offset and the ones below need to be signed to account for replacement code
+                // that is shorter than the original code.
+                let mut offsets: Vec<(usize, isize)> = Vec::new();
+                // Only show an underline in the suggestions if the suggestion is not the
+                // entirety of the code being shown and the displayed code is not multiline.
+                if show_underline {
+                    draw_col_separator(&mut buffer, row_num, max_line_num_len + 1);
+                    for part in parts {
+                        let span_start_pos = sm.lookup_char_pos(part.span.lo()).col_display;
+                        let span_end_pos = sm.lookup_char_pos(part.span.hi()).col_display;
+
+                        if removed_line.is_some() {
+                            for p in span_start_pos..span_end_pos {
+                                buffer.putc(
+                                    row_num,
+                                    max_line_num_len + 3 + p,
+                                    '-',
+                                    Style::UnderlineSecondary,
+                                );
+                            }
+                            continue;
+                        }
+
+                        // Do not underline the leading...
+                        let start =
+                            part.snippet.len().saturating_sub(part.snippet.trim_start().len());
+                        // ...or trailing spaces. Account for substitutions containing unicode
+                        // characters.
+                        let sub_len: usize = part
+                            .snippet
+                            .trim()
+                            .chars()
+                            .map(|ch| unicode_width::UnicodeWidthChar::width(ch).unwrap_or(1))
+                            .sum();
+
+                        let offset: isize = offsets
+                            .iter()
+                            .filter_map(
+                                |(start, v)| if span_start_pos <= *start { None } else { Some(v) },
+                            )
+                            .sum();
+                        let underline_start = (span_start_pos + start) as isize + offset;
+                        let underline_end = (span_start_pos + start + sub_len) as isize + offset;
+                        assert!(underline_start >= 0 && underline_end >= 0);
+                        for p in underline_start..underline_end {
                             buffer.putc(
                                 row_num,
                                 ((max_line_num_len + 3) as isize + p) as usize,
-                                '-',
-                                Style::UnderlineSecondary,
+                                '^',
+                                Style::UnderlinePrimary,
                             );
                         }
-                    }
+                        // underline removals too
+                        if underline_start == underline_end {
+                            for p in underline_start - 1..underline_start + 1 {
+                                buffer.putc(
+                                    row_num,
+                                    ((max_line_num_len + 3) as isize + p) as usize,
+                                    '-',
+                                    Style::UnderlineSecondary,
+                                );
+                            }
+                        }
 
-                    // length of the code after substitution
-                    let full_sub_len = part
-                        .snippet
-                        .chars()
-                        .map(|ch| unicode_width::UnicodeWidthChar::width(ch).unwrap_or(1))
-                        .sum::<usize>() as isize;
-
-                    // length of the code to be substituted
-                    let snippet_len = span_end_pos as isize - span_start_pos as isize;
-                    // For multiple substitutions, use the position *after* the previous
-                    // substitutions have happened, only when further substitutions are
-                    // located strictly after.
-                    offsets.push((span_end_pos, full_sub_len - snippet_len));
+                        // length of the code after substitution
+                        let full_sub_len = part
+                            .snippet
+                            .chars()
+                            .map(|ch| unicode_width::UnicodeWidthChar::width(ch).unwrap_or(1))
+                            .sum::<usize>() as isize;
+
+                        // length of the code to be substituted
+                        let snippet_len = span_end_pos as isize - span_start_pos as isize;
+                        // For multiple substitutions, use the position *after* the previous
+                        // substitutions have happened, only when further substitutions are
+                        // located strictly after.
+                        offsets.push((span_end_pos, full_sub_len - snippet_len));
+                    }
+                    row_num += 1;
                 }
-                row_num += 1;
-            }
 
-            // if we elided some lines, add an ellipsis
-            if lines.next().is_some() {
-                buffer.puts(row_num, max_line_num_len - 1, "...", Style::LineNumber);
-            } else if !show_underline {
-                draw_col_separator_no_space(&mut buffer, row_num, max_line_num_len + 1);
-                row_num += 1;
+                // if we elided some lines, add an ellipsis
+                if lines.next().is_some() {
+                    buffer.puts(row_num, max_line_num_len - 1, "...", Style::LineNumber);
+                } else if !show_underline {
+                    draw_col_separator_no_space(&mut buffer, row_num, max_line_num_len + 1);
+                    row_num += 1;
+                }
             }
         }
         if suggestions.len() > MAX_SUGGESTIONS {
@@ -1767,7 +1995,7 @@ fn emit_messages_default(
                         } else if sugg.style == SuggestionStyle::HideCodeAlways {
                             if let Err(e) = self.emit_message_default(
                                 &MultiSpan::new(),
-                                &[(sugg.msg.to_owned(), Style::HeaderMsg)],
+                                &[(sugg.msg.as_str_or_identifier().to_owned(), Style::HeaderMsg)],
                                 &None,
                                 &Level::Help,
                                 max_line_num_len,
@@ -2064,9 +2292,17 @@ fn emit_to_destination(
     let _buffer_lock = lock::acquire_global_lock("rustc_errors");
     for (pos, line) in rendered_buffer.iter().enumerate() {
         for part in line {
+            // OSC 8 hyperlinks, see
+            // https://gist.github.com/egmontkob/eb114294efbcd5adb1944c9f3cb5feaf.
+            if let Some(url) = &part.link {
+                write!(dst, "\x1b]8;;{}\x1b\\", url)?;
+            }
             dst.apply_style(*lvl, part.style)?;
             write!(dst, "{}", part.text)?;
             dst.reset()?;
+            if part.link.is_some() {
+                write!(dst, "\x1b]8;;\x1b\\")?;
+            }
         }
         if !short_message && (!lvl.is_failure_note() || pos != rendered_buffer.len() - 1) {
             writeln!(dst)?;
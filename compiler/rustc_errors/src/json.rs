@@ -15,8 +15,10 @@
 use crate::registry::Registry;
 use crate::DiagnosticId;
 use crate::ToolMetadata;
-use crate::{CodeSuggestion, SubDiagnostic};
-use rustc_lint_defs::{Applicability, FutureBreakage};
+use crate::{CodeSuggestion, SubDiagnostic, SubstitutionPart};
+use rustc_lint_defs::{
+    Applicability, FutureBreakage, LintCap, LintLevelOrigin, LintLevelOriginKind,
+};
 
 use rustc_data_structures::sync::Lrc;
 use rustc_span::hygiene::ExpnData;
@@ -196,6 +198,11 @@ struct Diagnostic {
     children: Vec<Diagnostic>,
     /// The message as rustc would render it.
     rendered: Option<String>,
+    /// For suggestions with parts in several files, the indices into `spans` of the parts of each
+    /// alternative.
+    substitutions: Option<Vec<Vec<usize>>>,
+    /// For lints, where their level was set.
+    lint_level: Option<DiagnosticLintLevel>,
     /// Extra tool metadata
     tool_metadata: ToolMetadata,
 }
@@ -226,10 +233,10 @@ macro_rules! encode_fields {
     };
 }
 
-// Special-case encoder to skip tool_metadata if not set
+// Special-case encoder to skip substitutions, lint_level and tool_metadata if not set
 impl<E: Encoder> Encodable<E> for Diagnostic {
     fn encode(&self, s: &mut E) -> Result<(), E::Error> {
-        s.emit_struct("diagnostic", 7, |s| {
+        s.emit_struct("diagnostic", 9, |s| {
             let mut idx = 0;
 
             idx = encode_fields!(
@@ -238,8 +245,28 @@ fn encode(&self, s: &mut E) -> Result<(), E::Error> {
                 self,
                 Self,
                 [message, code, level, spans, children, rendered],
-                [tool_metadata]
+                [substitutions, lint_level, tool_metadata]
             );
+            if self.substitutions.is_some() {
+                idx = encode_fields!(
+                    s,
+                    idx,
+                    self,
+                    Self,
+                    [substitutions],
+                    [message, code, level, spans, children, rendered, lint_level, tool_metadata]
+                );
+            }
+            if self.lint_level.is_some() {
+                idx = encode_fields!(
+                    s,
+                    idx,
+                    self,
+                    Self,
+                    [lint_level],
+                    [message, code, level, spans, children, rendered, substitutions, tool_metadata]
+                );
+            }
             if self.tool_metadata.is_set() {
                 idx = encode_fields!(
                     s,
@@ -247,7 +274,7 @@ fn encode(&self, s: &mut E) -> Result<(), E::Error> {
                     self,
                     Self,
                     [tool_metadata],
-                    [message, code, level, spans, children, rendered]
+                    [message, code, level, spans, children, rendered, substitutions, lint_level]
                 );
             }
 
@@ -308,6 +335,24 @@ struct DiagnosticSpanMacroExpansion {
     def_site_span: DiagnosticSpan,
 }
 
+#[derive(Encodable)]
+struct DiagnosticLintLevel {
+    /// "allow", "warn", "deny" or "forbid".
+    level: &'static str,
+    /// The level set by the source, which is higher than `level` if the lint was capped.
+    set_level: &'static str,
+    /// The lint, or the lint group, whose level was set.
+    name: String,
+    /// "default", "command_line", "attribute" or "lint_config".
+    source: &'static str,
+    /// The lint flag which set the level, like `-D warnings`.
+    flag: Option<String>,
+    /// The lint name in the attribute, or the entry of the lint config, which set the level.
+    span: Option<DiagnosticSpan>,
+    /// "cap_lints" or "driver", if the level was lowered by `--cap-lints` or by the driver.
+    capped_by: Option<&'static str>,
+}
+
 #[derive(Encodable)]
 struct DiagnosticCode {
     /// The code itself.
@@ -350,12 +395,14 @@ struct UnusedExterns<'a, 'b, 'c> {
 impl Diagnostic {
     fn from_errors_diagnostic(diag: &crate::Diagnostic, je: &JsonEmitter) -> Diagnostic {
         let sugg = diag.suggestions.iter().map(|sugg| Diagnostic {
-            message: sugg.msg.clone(),
+            message: sugg.msg.as_str_or_identifier().to_owned(),
             code: None,
             level: "help",
             spans: DiagnosticSpan::from_suggestion(sugg, je),
             children: vec![],
             rendered: None,
+            substitutions: Diagnostic::substitutions(sugg, je),
+            lint_level: None,
             tool_metadata: sugg.tool_metadata.clone(),
         });
 
@@ -400,6 +447,11 @@ fn flush(&mut self) -> io::Result<()> {
                 .chain(sugg)
                 .collect(),
             rendered: Some(output),
+            substitutions: None,
+            lint_level: diag
+                .lint_level_origin
+                .as_ref()
+                .map(|origin| DiagnosticLintLevel::from_origin(origin, je)),
             tool_metadata: ToolMetadata::default(),
         }
     }
@@ -416,9 +468,38 @@ fn from_sub_diagnostic(diag: &SubDiagnostic, je: &JsonEmitter) -> Diagnostic {
                 .unwrap_or_else(|| DiagnosticSpan::from_multispan(&diag.span, je)),
             children: vec![],
             rendered: None,
+            substitutions: None,
+            lint_level: None,
             tool_metadata: ToolMetadata::default(),
         }
     }
+
+    /// If any alternative of `suggestion` has parts in more than one file, returns the indices
+    /// of the spans (as produced by `DiagnosticSpan::from_suggestion`) making up each of them,
+    /// so that tools know which edits have to be applied together.
+    fn substitutions(suggestion: &CodeSuggestion, je: &JsonEmitter) -> Option<Vec<Vec<usize>>> {
+        let file = |part: &SubstitutionPart| je.sm.lookup_source_file(part.span.lo()).start_pos;
+        let multiple_files = suggestion
+            .substitutions
+            .iter()
+            .any(|subst| subst.parts.iter().any(|part| file(part) != file(&subst.parts[0])));
+        if !multiple_files {
+            return None;
+        }
+
+        let mut idx = 0;
+        Some(
+            suggestion
+                .substitutions
+                .iter()
+                .map(|subst| {
+                    let indices = (idx..idx + subst.parts.len()).collect();
+                    idx += subst.parts.len();
+                    indices
+                })
+                .collect(),
+        )
+    }
 }
 
 impl DiagnosticSpan {
@@ -554,6 +635,32 @@ fn from_span(span: Span, je: &JsonEmitter) -> Vec<DiagnosticSpanLine> {
     }
 }
 
+impl DiagnosticLintLevel {
+    fn from_origin(origin: &LintLevelOrigin, je: &JsonEmitter) -> DiagnosticLintLevel {
+        let span = |span| DiagnosticSpan::from_span_etc(span, false, None, None, je);
+        let (source, flag, span) = match origin.kind {
+            LintLevelOriginKind::Default => ("default", None, None),
+            LintLevelOriginKind::CommandLine { ref flag } => {
+                ("command_line", Some(flag.clone()), None)
+            }
+            LintLevelOriginKind::Attribute(sp) => ("attribute", None, Some(span(sp))),
+            LintLevelOriginKind::LintConfig(sp) => ("lint_config", None, Some(span(sp))),
+        };
+        DiagnosticLintLevel {
+            level: origin.level.as_str(),
+            set_level: origin.set_level.as_str(),
+            name: origin.name.clone(),
+            source,
+            flag,
+            span,
+            capped_by: origin.cap.map(|cap| match cap {
+                LintCap::CapLints => "cap_lints",
+                LintCap::Driver => "driver",
+            }),
+        }
+    }
+}
+
 impl DiagnosticCode {
     fn map_opt_string(s: Option<DiagnosticId>, je: &JsonEmitter) -> Option<DiagnosticCode> {
         s.map(|s| {
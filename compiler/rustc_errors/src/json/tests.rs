@@ -4,9 +4,9 @@
 use rustc_span::source_map::{FilePathMapping, SourceMap};
 
 use crate::emitter::{ColorConfig, HumanReadableErrorType};
-use crate::Handler;
+use crate::{Applicability, Handler};
 use rustc_serialize::json::decode;
-use rustc_span::{BytePos, Span};
+use rustc_span::{BytePos, SourceFile, Span};
 
 use std::str;
 
@@ -25,6 +25,23 @@ struct SpanTestData {
     pub column_end: u32,
 }
 
+#[derive(Decodable, Debug)]
+struct SuggestionsTestData {
+    children: Vec<ChildTestData>,
+    rendered: String,
+}
+
+#[derive(Decodable, Debug)]
+struct ChildTestData {
+    spans: Vec<FileNameTestData>,
+    substitutions: Option<Vec<Vec<usize>>>,
+}
+
+#[derive(Decodable, Debug)]
+struct FileNameTestData {
+    file_name: String,
+}
+
 struct Shared<T> {
     data: Arc<Mutex<T>>,
 }
@@ -202,3 +219,66 @@ fn span_after_crlf() {
         },
     )
 }
+
+#[test]
+fn multi_file_suggestions() {
+    with_default_session_globals(|| {
+        let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+        let main = sm.new_source_file(
+            Path::new("main.rs").to_owned().into(),
+            "mod foo;\nfn main() { Foo; }\n".to_owned(),
+        );
+        let lib =
+            sm.new_source_file(Path::new("foo.rs").to_owned().into(), "fn bar() {}\n".to_owned());
+        let span = |file: &SourceFile, lo, hi| {
+            Span::with_root_ctxt(file.start_pos + BytePos(lo), file.start_pos + BytePos(hi))
+        };
+
+        let output = Arc::new(Mutex::new(Vec::new()));
+        let je = JsonEmitter::new(
+            Box::new(Shared { data: output.clone() }),
+            None,
+            sm.clone(),
+            false,
+            HumanReadableErrorType::Default(ColorConfig::Never),
+            None,
+            false,
+        );
+        let handler = Handler::with_emitter(true, None, Box::new(je));
+        handler
+            .struct_span_err(span(&main, 21, 24), "foo")
+            .multipart_suggestions(
+                "add a struct to the module",
+                vec![
+                    vec![
+                        (span(&lib, 0, 0), "pub struct Foo;\n".to_owned()),
+                        (span(&main, 21, 21), "foo::".to_owned()),
+                    ],
+                    vec![(span(&main, 21, 24), "()".to_owned())],
+                ]
+                .into_iter(),
+                Applicability::MaybeIncorrect,
+            )
+            .span_suggestion(
+                span(&main, 21, 24),
+                "use a unit value",
+                "()".to_owned(),
+                Applicability::MaybeIncorrect,
+            )
+            .emit();
+
+        let bytes = output.lock().unwrap();
+        let output: SuggestionsTestData = decode(str::from_utf8(&bytes).unwrap()).unwrap();
+
+        let multi_file = &output.children[0];
+        let files = multi_file.spans.iter().map(|span| &span.file_name[..]).collect::<Vec<_>>();
+        assert_eq!(files, ["foo.rs", "main.rs", "main.rs"]);
+        assert_eq!(multi_file.substitutions, Some(vec![vec![0, 1], vec![2]]));
+        assert_eq!(output.children[1].substitutions, None);
+
+        assert!(output.rendered.contains("::: foo.rs:1"), "{}", output.rendered);
+        assert!(output.rendered.contains("::: main.rs:2"), "{}", output.rendered);
+        assert!(output.rendered.contains("pub struct Foo;"), "{}", output.rendered);
+        assert!(output.rendered.contains("fn main() { foo::Foo; }"), "{}", output.rendered);
+    })
+}
@@ -29,14 +29,16 @@
 use rustc_serialize::json::Json;
 use rustc_serialize::{Decodable, Decoder, Encodable, Encoder};
 use rustc_span::source_map::SourceMap;
-use rustc_span::{Loc, MultiSpan, Span};
+pub use rustc_span::DiagnosticMessage;
+use rustc_span::{Loc, MultiSpan, SourceFile, Span};
+use translation::Translator;
 
 use std::borrow::Cow;
 use std::hash::{Hash, Hasher};
 use std::num::NonZeroUsize;
 use std::panic;
 use std::path::Path;
-use std::{error, fmt};
+use std::{error, fmt, mem};
 
 use termcolor::{Color, ColorSpec};
 
@@ -46,10 +48,14 @@
 pub mod emitter;
 pub mod json;
 mod lock;
+pub mod markdown;
 pub mod registry;
+pub mod sarif;
 mod snippet;
 mod styled_buffer;
+pub mod translation;
 pub use snippet::Style;
+pub use translation::{DiagnosticArgValue, IntoDiagnosticArg};
 
 pub type PResult<'a, T> = Result<T, DiagnosticBuilder<'a>>;
 
@@ -137,7 +143,7 @@ pub struct CodeSuggestion {
     /// ]
     /// ```
     pub substitutions: Vec<Substitution>,
-    pub msg: String,
+    pub msg: DiagnosticMessage,
     /// Visual representation of this suggestion.
     pub style: SuggestionStyle,
     /// Whether or not the suggestion is approximate
@@ -152,6 +158,9 @@ pub struct CodeSuggestion {
 
 #[derive(Clone, Debug, PartialEq, Hash, Encodable, Decodable)]
 /// See the docs on `CodeSuggestion::substitutions`
+///
+/// The parts of a substitution may be in different files, e.g. to add an import at the top of
+/// one module while changing a path in another.
 pub struct Substitution {
     pub parts: Vec<SubstitutionPart>,
 }
@@ -162,10 +171,22 @@ pub struct SubstitutionPart {
     pub snippet: String,
 }
 
+/// Parts of a substitution which are shown together, spliced into the lines they modify.
+#[derive(Debug)]
+pub struct SplicedLines {
+    /// The modified lines.
+    pub text: String,
+    /// The parts which were spliced into `text`, in ascending order.
+    pub parts: Vec<SubstitutionPart>,
+    /// Whether the parts only change the capitalization of the code.
+    pub only_capitalization: bool,
+}
+
 impl CodeSuggestion {
-    /// Returns the assembled code suggestions, whether they should be shown with an underline
-    /// and whether the substitution only differs in capitalization.
-    pub fn splice_lines(&self, sm: &SourceMap) -> Vec<(String, Vec<SubstitutionPart>, bool)> {
+    /// Returns the assembled code suggestions, one per substitution. Each substitution is split
+    /// into the lines it modifies in each file, in the order in which the files first appear in
+    /// its parts. Parts which insert lines, like imports, are split from the other ones.
+    pub fn splice_lines(&self, sm: &SourceMap) -> Vec<Vec<SplicedLines>> {
         use rustc_span::{CharPos, Pos};
 
         fn push_trailing(
@@ -190,6 +211,73 @@ fn push_trailing(
             }
         }
 
+        /// Splices sorted parts which are all in the same file.
+        fn splice_hunk(sm: &SourceMap, parts: Vec<SubstitutionPart>) -> Option<SplicedLines> {
+            // Find the bounding span.
+            let lo = parts.iter().map(|part| part.span.lo()).min()?;
+            let hi = parts.iter().map(|part| part.span.hi()).max()?;
+            let bounding_span = Span::with_root_ctxt(lo, hi);
+            // The different spans might belong to different contexts, if so ignore suggestion.
+            let lines = sm.span_to_lines(bounding_span).ok()?;
+            assert!(!lines.lines.is_empty() || bounding_span.is_dummy());
+
+            // We can't splice anything if the source is unavailable.
+            if !sm.ensure_source_file_source_present(lines.file.clone()) {
+                return None;
+            }
+
+            // To build up the result, we do this for each span:
+            // - push the line segment trailing the previous span
+            //   (at the beginning a "phantom" span pointing at the start of the line)
+            // - push lines between the previous and current span (if any)
+            // - if the previous and current span are not on the same line
+            //   push the line segment leading up to the current span
+            // - splice in the span substitution
+            //
+            // Finally push the trailing line segment of the last span
+            let sf = &lines.file;
+            let mut prev_hi = sm.lookup_char_pos(bounding_span.lo());
+            prev_hi.col = CharPos::from_usize(0);
+            let mut prev_line = lines.lines.get(0).and_then(|line0| sf.get_line(line0.line_index));
+            let mut buf = String::new();
+
+            for part in &parts {
+                let cur_lo = sm.lookup_char_pos(part.span.lo());
+                if prev_hi.line == cur_lo.line {
+                    push_trailing(&mut buf, prev_line.as_ref(), &prev_hi, Some(&cur_lo));
+                } else {
+                    push_trailing(&mut buf, prev_line.as_ref(), &prev_hi, None);
+                    // push lines between the previous and current span (if any)
+                    for idx in prev_hi.line..(cur_lo.line - 1) {
+                        if let Some(line) = sf.get_line(idx) {
+                            buf.push_str(line.as_ref());
+                            buf.push('\n');
+                        }
+                    }
+                    if let Some(cur_line) = sf.get_line(cur_lo.line - 1) {
+                        let end = match cur_line.char_indices().nth(cur_lo.col.to_usize()) {
+                            Some((i, _)) => i,
+                            None => cur_line.len(),
+                        };
+                        buf.push_str(&cur_line[..end]);
+                    }
+                }
+                buf.push_str(&part.snippet);
+                prev_hi = sm.lookup_char_pos(part.span.hi());
+                prev_line = sf.get_line(prev_hi.line - 1);
+            }
+            let only_capitalization = is_case_difference(sm, &buf, bounding_span);
+            // if the replacement already ends with a newline, don't print the next line
+            if !buf.ends_with('\n') {
+                push_trailing(&mut buf, prev_line.as_ref(), &prev_hi, None);
+            }
+            // remove trailing newlines
+            while buf.ends_with('\n') {
+                buf.pop();
+            }
+            Some(SplicedLines { text: buf, parts, only_capitalization })
+        }
+
         assert!(!self.substitutions.is_empty());
 
         self.substitutions
@@ -203,76 +291,45 @@ fn push_trailing(
                 }
                 !invalid
             })
-            .cloned()
-            .filter_map(|mut substitution| {
-                // Assumption: all spans are in the same file, and all spans
-                // are disjoint. Sort in ascending order.
-                substitution.parts.sort_by_key(|part| part.span.lo());
-
-                // Find the bounding span.
-                let lo = substitution.parts.iter().map(|part| part.span.lo()).min()?;
-                let hi = substitution.parts.iter().map(|part| part.span.hi()).max()?;
-                let bounding_span = Span::with_root_ctxt(lo, hi);
-                // The different spans might belong to different contexts, if so ignore suggestion.
-                let lines = sm.span_to_lines(bounding_span).ok()?;
-                assert!(!lines.lines.is_empty() || bounding_span.is_dummy());
-
-                // We can't splice anything if the source is unavailable.
-                if !sm.ensure_source_file_source_present(lines.file.clone()) {
+            .filter_map(|substitution| {
+                let mut files: Vec<(Lrc<SourceFile>, Vec<SubstitutionPart>)> = vec![];
+                for part in &substitution.parts {
+                    let file = sm.lookup_source_file(part.span.lo());
+                    match files.iter_mut().find(|(f, _)| f.start_pos == file.start_pos) {
+                        Some((_, parts)) => parts.push(part.clone()),
+                        None => files.push((file, vec![part.clone()])),
+                    }
+                }
+                if files.is_empty() {
                     return None;
                 }
 
-                // To build up the result, we do this for each span:
-                // - push the line segment trailing the previous span
-                //   (at the beginning a "phantom" span pointing at the start of the line)
-                // - push lines between the previous and current span (if any)
-                // - if the previous and current span are not on the same line
-                //   push the line segment leading up to the current span
-                // - splice in the span substitution
-                //
-                // Finally push the trailing line segment of the last span
-                let sf = &lines.file;
-                let mut prev_hi = sm.lookup_char_pos(bounding_span.lo());
-                prev_hi.col = CharPos::from_usize(0);
-                let mut prev_line =
-                    lines.lines.get(0).and_then(|line0| sf.get_line(line0.line_index));
-                let mut buf = String::new();
-
-                for part in &substitution.parts {
-                    let cur_lo = sm.lookup_char_pos(part.span.lo());
-                    if prev_hi.line == cur_lo.line {
-                        push_trailing(&mut buf, prev_line.as_ref(), &prev_hi, Some(&cur_lo));
-                    } else {
-                        push_trailing(&mut buf, prev_line.as_ref(), &prev_hi, None);
-                        // push lines between the previous and current span (if any)
-                        for idx in prev_hi.line..(cur_lo.line - 1) {
-                            if let Some(line) = sf.get_line(idx) {
-                                buf.push_str(line.as_ref());
-                                buf.push('\n');
-                            }
-                        }
-                        if let Some(cur_line) = sf.get_line(cur_lo.line - 1) {
-                            let end = match cur_line.char_indices().nth(cur_lo.col.to_usize()) {
-                                Some((i, _)) => i,
-                                None => cur_line.len(),
-                            };
-                            buf.push_str(&cur_line[..end]);
+                // If any of the hunks can't be spliced, showing the rest of the substitution
+                // would be misleading, so ignore it entirely.
+                let mut hunks = vec![];
+                for (_, mut parts) in files {
+                    // Assumption: all spans are disjoint. Sort in ascending order.
+                    parts.sort_by_key(|part| part.span.lo());
+
+                    // Lines inserted before some code, like imports, are shown apart from the
+                    // other parts, which are usually far from them. Everything else is shown
+                    // together.
+                    let inserts_lines = |part: &SubstitutionPart| {
+                        part.span.is_empty() && part.snippet.ends_with('\n')
+                    };
+                    let mut hunk: Vec<SubstitutionPart> = vec![];
+                    for part in parts {
+                        let new_hunk = hunk
+                            .last()
+                            .map_or(false, |prev| inserts_lines(prev) != inserts_lines(&part));
+                        if new_hunk {
+                            hunks.push(splice_hunk(sm, mem::take(&mut hunk))?);
                         }
+                        hunk.push(part);
                     }
-                    buf.push_str(&part.snippet);
-                    prev_hi = sm.lookup_char_pos(part.span.hi());
-                    prev_line = sf.get_line(prev_hi.line - 1);
-                }
-                let only_capitalization = is_case_difference(sm, &buf, bounding_span);
-                // if the replacement already ends with a newline, don't print the next line
-                if !buf.ends_with('\n') {
-                    push_trailing(&mut buf, prev_line.as_ref(), &prev_hi, None);
+                    hunks.push(splice_hunk(sm, hunk)?);
                 }
-                // remove trailing newlines
-                while buf.ends_with('\n') {
-                    buf.pop();
-                }
-                Some((buf, substitution.parts, only_capitalization))
+                Some(hunks)
             })
             .collect()
     }
@@ -344,6 +401,11 @@ struct HandlerInner {
     deduplicated_warn_count: usize,
 
     future_breakage_diagnostics: Vec<Diagnostic>,
+
+    /// Translates the messages of the diagnostics before they are emitted.
+    /// This is only created on demand if it wasn't set with
+    /// [`Handler::with_translator()`], as most diagnostics don't need it.
+    translator: Option<Translator>,
 }
 
 /// A key denoting where from a diagnostic was stashed.
@@ -458,10 +520,18 @@ pub fn with_emitter_and_flags(
                 emitted_diagnostics: Default::default(),
                 stashed_diagnostics: Default::default(),
                 future_breakage_diagnostics: Vec::new(),
+                translator: None,
             }),
         }
     }
 
+    /// Sets the translator of the messages of the diagnostics, which
+    /// otherwise uses the built-in English messages.
+    pub fn with_translator(mut self, translator: Translator) -> Self {
+        self.inner.get_mut().translator = Some(translator);
+        self
+    }
+
     // This is here to not allow mutation of flags;
     // as of this writing it's only used in tests in librustc_middle.
     pub fn can_emit_warnings(&self) -> bool {
@@ -566,7 +636,11 @@ pub fn struct_allow(&self, msg: &str) -> DiagnosticBuilder<'_> {
     }
 
     /// Construct a builder at the `Error` level at the given `span` and with the `msg`.
-    pub fn struct_span_err(&self, span: impl Into<MultiSpan>, msg: &str) -> DiagnosticBuilder<'_> {
+    pub fn struct_span_err(
+        &self,
+        span: impl Into<MultiSpan>,
+        msg: impl Into<DiagnosticMessage>,
+    ) -> DiagnosticBuilder<'_> {
         let mut result = self.struct_err(msg);
         result.set_span(span);
         result
@@ -576,7 +650,7 @@ pub fn struct_span_err(&self, span: impl Into<MultiSpan>, msg: &str) -> Diagnost
     pub fn struct_span_err_with_code(
         &self,
         span: impl Into<MultiSpan>,
-        msg: &str,
+        msg: impl Into<DiagnosticMessage>,
         code: DiagnosticId,
     ) -> DiagnosticBuilder<'_> {
         let mut result = self.struct_span_err(span, msg);
@@ -586,7 +660,7 @@ pub fn struct_span_err_with_code(
 
     /// Construct a builder at the `Error` level with the `msg`.
     // FIXME: This method should be removed (every error should have an associated error code).
-    pub fn struct_err(&self, msg: &str) -> DiagnosticBuilder<'_> {
+    pub fn struct_err(&self, msg: impl Into<DiagnosticMessage>) -> DiagnosticBuilder<'_> {
         DiagnosticBuilder::new(self, Level::Error, msg)
     }
 
@@ -601,7 +675,7 @@ pub fn struct_err_with_code(&self, msg: &str, code: DiagnosticId) -> DiagnosticB
     pub fn struct_span_fatal(
         &self,
         span: impl Into<MultiSpan>,
-        msg: &str,
+        msg: impl Into<DiagnosticMessage>,
     ) -> DiagnosticBuilder<'_> {
         let mut result = self.struct_fatal(msg);
         result.set_span(span);
@@ -612,7 +686,7 @@ pub fn struct_span_fatal(
     pub fn struct_span_fatal_with_code(
         &self,
         span: impl Into<MultiSpan>,
-        msg: &str,
+        msg: impl Into<DiagnosticMessage>,
         code: DiagnosticId,
     ) -> DiagnosticBuilder<'_> {
         let mut result = self.struct_span_fatal(span, msg);
@@ -621,7 +695,7 @@ pub fn struct_span_fatal_with_code(
     }
 
     /// Construct a builder at the `Error` level with the `msg`.
-    pub fn struct_fatal(&self, msg: &str) -> DiagnosticBuilder<'_> {
+    pub fn struct_fatal(&self, msg: impl Into<DiagnosticMessage>) -> DiagnosticBuilder<'_> {
         DiagnosticBuilder::new(self, Level::Fatal, msg)
     }
 
@@ -783,9 +857,16 @@ fn must_teach(&mut self, code: &DiagnosticId) -> bool {
     }
 
     fn force_print_diagnostic(&mut self, db: Diagnostic) {
+        let db = self.translate(&db);
         self.emitter.emit_diagnostic(&db);
     }
 
+    /// Translates the messages of `diagnostic` that are referred to by
+    /// identifier, so that emitters only have to deal with strings.
+    fn translate<'a>(&mut self, diagnostic: &'a Diagnostic) -> Cow<'a, Diagnostic> {
+        self.translator.get_or_insert_with(Translator::fallback).translate_diagnostic(diagnostic)
+    }
+
     /// Emit all stashed diagnostics.
     fn emit_stashed_diagnostics(&mut self) {
         let diags = self.stashed_diagnostics.drain(..).map(|x| x.1).collect::<Vec<_>>();
@@ -797,6 +878,9 @@ fn emit_diagnostic(&mut self, diagnostic: &Diagnostic) {
             return;
         }
 
+        let diagnostic = self.translate(diagnostic);
+        let diagnostic = &*diagnostic;
+
         if diagnostic.has_future_breakage() {
             self.future_breakage_diagnostics.push(diagnostic.clone());
         }
@@ -0,0 +1,481 @@
+//! Rendering of the Markdown of error code explanations in the terminal, for
+//! `rustc --explain`.
+//!
+//! Only the subset of Markdown used by the explanations is understood:
+//! headings, paragraphs, lists, code blocks, link definitions, and inline
+//! code, emphasis and links. Paragraphs and list items are wrapped to the
+//! width of the terminal, and Rust code blocks are highlighted. As rustdoc
+//! does, the lines of Rust code blocks starting with `#` are hidden, and the
+//! annotations of code blocks, like `compile_fail,E0308`, are not shown.
+
+use rustc_lexer::{LiteralKind, TokenKind};
+use termcolor::{Buffer, Color, ColorSpec, WriteColor};
+use unicode_width::UnicodeWidthStr;
+
+use std::io;
+use std::iter::Peekable;
+use std::str::Lines;
+
+#[cfg(test)]
+mod tests;
+
+/// Paragraphs are not wrapped to more columns than this, even in wide
+/// terminals, since long lines are hard to read.
+const MAX_WIDTH: usize = 100;
+
+/// The width used when the width of the terminal is unknown.
+const DEFAULT_WIDTH: usize = 80;
+
+/// The indentation of code blocks.
+const CODE_INDENT: usize = 4;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Style {
+    Plain,
+    Heading,
+    Strong,
+    Emphasis,
+    Code,
+    Link,
+    Keyword,
+    Literal,
+    Number,
+    Lifetime,
+    Comment,
+}
+
+impl Style {
+    fn color_spec(self) -> ColorSpec {
+        let mut spec = ColorSpec::new();
+        match self {
+            Style::Plain => {}
+            Style::Heading => {
+                spec.set_bold(true).set_underline(true);
+            }
+            Style::Strong => {
+                spec.set_bold(true);
+            }
+            Style::Emphasis | Style::Link => {
+                spec.set_underline(true);
+            }
+            Style::Code => {
+                spec.set_fg(Some(Color::Cyan));
+            }
+            Style::Keyword => {
+                spec.set_fg(Some(Color::Magenta)).set_bold(true);
+            }
+            Style::Literal => {
+                spec.set_fg(Some(Color::Green));
+            }
+            Style::Number => {
+                spec.set_fg(Some(Color::Yellow));
+            }
+            Style::Lifetime => {
+                spec.set_fg(Some(Color::Cyan)).set_bold(true);
+            }
+            Style::Comment => {
+                spec.set_fg(Some(Color::Black)).set_intense(true);
+            }
+        }
+        spec
+    }
+}
+
+/// Renders the Markdown `src` for the terminal, with ANSI colors if `colored`.
+/// Paragraphs are wrapped to `width` columns, or to the width of the terminal
+/// if it is `None`.
+pub fn render_to_string(src: &str, width: Option<usize>, colored: bool) -> String {
+    let width = width
+        .or_else(|| termize::dimensions().map(|(columns, _)| columns))
+        .unwrap_or(DEFAULT_WIDTH);
+    let mut buffer = if colored { Buffer::ansi() } else { Buffer::no_color() };
+    render(src, width, &mut buffer).expect("writing to a buffer cannot fail");
+    String::from_utf8(buffer.into_inner()).expect("the rendered Markdown is valid UTF-8")
+}
+
+/// Renders the Markdown `src` to `dst`, wrapping paragraphs to `width` columns.
+pub fn render(src: &str, width: usize, dst: &mut dyn WriteColor) -> io::Result<()> {
+    let mut renderer = Renderer { dst, width: width.min(MAX_WIDTH), blocks: 0 };
+    let mut lines = src.lines().peekable();
+    // Whether the last block was a list item or a link definition directly
+    // followed by the current line, in which case no blank line is put between
+    // them if the current line is also one.
+    let mut tight = false;
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim_start();
+        if trimmed.is_empty() {
+            tight = false;
+            continue;
+        }
+        let indent = " ".repeat(line.len() - trimmed.len());
+        if let Some(info) = trimmed.strip_prefix("```") {
+            let code = code_block(&mut lines, indent.len(), is_rust(info));
+            renderer.separate(false)?;
+            renderer.code_block(&code, is_rust(info))?;
+            tight = false;
+        } else if let Some(text) = heading(trimmed) {
+            renderer.separate(false)?;
+            renderer.paragraph(&indent, indent.len(), text, Style::Heading)?;
+            tight = false;
+        } else if is_link_definition(trimmed) {
+            // Link definitions are kept as they are, since reference links
+            // only show their text.
+            renderer.separate(tight)?;
+            renderer.write(&format!("{}{}\n", indent, trimmed.trim_end()), Style::Plain)?;
+            tight = true;
+        } else if let Some(marker) = list_marker(trimmed) {
+            let text = paragraph_text(&mut lines, &trimmed[marker.len()..]);
+            let prefix = format!("{}{}", indent, marker);
+            renderer.separate(tight)?;
+            renderer.paragraph(&prefix, prefix.len(), &text, Style::Plain)?;
+            tight = true;
+        } else {
+            let text = paragraph_text(&mut lines, trimmed);
+            renderer.separate(false)?;
+            renderer.paragraph(&indent, indent.len(), &text, Style::Plain)?;
+            tight = false;
+        }
+    }
+    Ok(())
+}
+
+/// Returns the lines of the code block whose opening fence was just read,
+/// without its hidden lines, and consumes its closing fence.
+fn code_block(lines: &mut Peekable<Lines<'_>>, indent: usize, rust: bool) -> String {
+    let mut code = String::new();
+    for line in lines {
+        let dedented = line.trim_start();
+        if dedented.starts_with("```") {
+            break;
+        }
+        if rust && (dedented == "#" || dedented.starts_with("# ")) {
+            continue;
+        }
+        // Remove at most the indentation of the opening fence.
+        let removed = (line.len() - dedented.len()).min(indent);
+        code.push_str(&line[removed..]);
+        code.push('\n');
+    }
+    code
+}
+
+/// Returns the text of the paragraph starting with `first`, whose other lines
+/// are the following lines up to a blank line or the start of another block.
+fn paragraph_text(lines: &mut Peekable<Lines<'_>>, first: &str) -> String {
+    let mut text = first.trim().to_string();
+    while let Some(line) = lines.peek() {
+        let trimmed = line.trim();
+        if trimmed.is_empty()
+            || trimmed.starts_with("```")
+            || heading(trimmed).is_some()
+            || list_marker(trimmed).is_some()
+            || is_link_definition(trimmed)
+        {
+            break;
+        }
+        text.push(' ');
+        text.push_str(trimmed);
+        lines.next();
+    }
+    text
+}
+
+/// Whether the code block with the info string `info` is Rust code. As in
+/// rustdoc, it is unless it has an annotation other than those of doctests.
+fn is_rust(info: &str) -> bool {
+    let mut in_comment = false;
+    let mut seen_rust = false;
+    let mut seen_other = false;
+    for token in info.split(|c: char| c == ',' || c.is_whitespace()).filter(|t| !t.is_empty()) {
+        // Parenthesized text, like in `ignore (needs an extern crate)`, is a
+        // comment.
+        if in_comment || token.starts_with('(') {
+            in_comment = !token.ends_with(')');
+            continue;
+        }
+        let is_error_code = token.len() == 5
+            && token.starts_with('E')
+            && token[1..].chars().all(|c| c.is_ascii_digit());
+        match token {
+            "rust" | "ignore" | "should_panic" | "no_run" | "compile_fail" | "test_harness"
+            | "allow_fail" => seen_rust = true,
+            _ if token.starts_with("ignore-") || token.starts_with("edition") => seen_rust = true,
+            _ if is_error_code => seen_rust = true,
+            _ => seen_other = true,
+        }
+    }
+    !seen_other || seen_rust
+}
+
+/// Returns the text of `line` if it is a heading.
+fn heading(line: &str) -> Option<&str> {
+    let level = line.chars().take_while(|&c| c == '#').count();
+    if level == 0 || level > 6 {
+        return None;
+    }
+    match &line[level..] {
+        "" => Some(""),
+        rest if rest.starts_with(' ') => Some(rest.trim().trim_end_matches('#').trim_end()),
+        _ => None,
+    }
+}
+
+/// Returns the marker of `line`, including the space after it, if it is a list
+/// item.
+fn list_marker(line: &str) -> Option<&str> {
+    if line.starts_with("- ") || line.starts_with("* ") || line.starts_with("+ ") {
+        return Some(&line[..2]);
+    }
+    let digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+    let rest = &line[digits..];
+    if digits > 0 && (rest.starts_with(". ") || rest.starts_with(") ")) {
+        return Some(&line[..digits + 2]);
+    }
+    None
+}
+
+/// Whether `line` defines the URL of reference links, like `[RFC 401]: https://...`.
+fn is_link_definition(line: &str) -> bool {
+    line.starts_with('[')
+        && line.find("]:").map_or(false, |end| !line[1..end].contains(|c| c == '[' || c == ']'))
+}
+
+/// Splits the inline Markdown `text` into runs of text with the same style.
+fn inline(text: &str, plain: Style) -> Vec<(String, Style)> {
+    let mut runs = Vec::new();
+    let mut plain_text = String::new();
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        if let Some((run, style, len)) = inline_span(rest, plain_text.chars().next_back()) {
+            if !plain_text.is_empty() {
+                runs.push((std::mem::take(&mut plain_text), plain));
+            }
+            runs.push((run, style));
+            rest = &rest[len..];
+        } else {
+            plain_text.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    if !plain_text.is_empty() {
+        runs.push((plain_text, plain));
+    }
+    runs
+}
+
+/// Parses the inline code, emphasis or link at the start of `text`, returning
+/// its text, its style and its length in `text`. `prev` is the character before
+/// `text`, since `_` only starts emphasis at the start of a word.
+fn inline_span(text: &str, prev: Option<char>) -> Option<(String, Style, usize)> {
+    if text.starts_with('`') {
+        let ticks = text.len() - text.trim_start_matches('`').len();
+        let fence = &text[..ticks];
+        let end = text[ticks..].find(fence)? + ticks;
+        let code = text[ticks..end].trim();
+        return Some((code.to_string(), Style::Code, end + ticks));
+    }
+    for &(delim, style) in &[("**", Style::Strong), ("__", Style::Strong)] {
+        if let Some(inner) = text.strip_prefix(delim) {
+            let end = inner.find(delim).filter(|&end| end > 0)?;
+            return Some((inner[..end].to_string(), style, end + 2 * delim.len()));
+        }
+    }
+    if text.starts_with('*') || text.starts_with('_') {
+        let delim = &text[..1];
+        if delim == "_" && prev.map_or(false, char::is_alphanumeric) {
+            return None;
+        }
+        let inner = &text[1..];
+        if inner.starts_with(' ') {
+            return None;
+        }
+        let end = inner
+            .match_indices(delim)
+            .map(|(end, _)| end)
+            .filter(|&end| end > 0 && !inner[..end].ends_with(' '))
+            .find(|&end| delim == "*" || !inner[end + 1..].starts_with(char::is_alphanumeric))?;
+        return Some((inner[..end].to_string(), Style::Emphasis, end + 2));
+    }
+    if text.starts_with('[') {
+        let close = text.find(']')?;
+        let link_text = &text[1..close];
+        if link_text.contains('[') {
+            return None;
+        }
+        let after = &text[close + 1..];
+        if after.starts_with('(') {
+            // An inline link, whose URL is shown after its text.
+            let url_end = after.find(')')?;
+            let link = format!("{} <{}>", link_text, &after[1..url_end]);
+            return Some((link, Style::Link, close + url_end + 2));
+        } else if after.starts_with('[') {
+            // A reference link, whose definition is shown where it is.
+            let len = close + after.find(']')? + 2;
+            return Some((link_text.to_string(), Style::Link, len));
+        }
+    }
+    None
+}
+
+/// Splits the Rust `code` into its tokens, with their styles.
+fn highlight(code: &str) -> Vec<(&str, Style)> {
+    let mut runs = Vec::new();
+    let mut pos = 0;
+    for token in rustc_lexer::tokenize(code) {
+        let text = &code[pos..pos + token.len];
+        pos += token.len;
+        let style = match token.kind {
+            TokenKind::LineComment { .. } | TokenKind::BlockComment { .. } => Style::Comment,
+            TokenKind::Ident if is_keyword(text) => Style::Keyword,
+            TokenKind::Literal { kind: LiteralKind::Int { .. }, .. }
+            | TokenKind::Literal { kind: LiteralKind::Float { .. }, .. } => Style::Number,
+            TokenKind::Literal { .. } => Style::Literal,
+            TokenKind::Lifetime { .. } => Style::Lifetime,
+            _ => Style::Plain,
+        };
+        runs.push((text, style));
+    }
+    runs
+}
+
+fn is_keyword(ident: &str) -> bool {
+    matches!(
+        ident,
+        "as" | "async"
+            | "await"
+            | "break"
+            | "const"
+            | "continue"
+            | "crate"
+            | "dyn"
+            | "else"
+            | "enum"
+            | "extern"
+            | "false"
+            | "fn"
+            | "for"
+            | "if"
+            | "impl"
+            | "in"
+            | "let"
+            | "loop"
+            | "match"
+            | "mod"
+            | "move"
+            | "mut"
+            | "pub"
+            | "ref"
+            | "return"
+            | "self"
+            | "Self"
+            | "static"
+            | "struct"
+            | "super"
+            | "trait"
+            | "true"
+            | "type"
+            | "union"
+            | "unsafe"
+            | "use"
+            | "where"
+            | "while"
+    )
+}
+
+struct Renderer<'a> {
+    dst: &'a mut dyn WriteColor,
+    width: usize,
+    /// The number of blocks rendered so far.
+    blocks: usize,
+}
+
+impl Renderer<'_> {
+    fn write(&mut self, text: &str, style: Style) -> io::Result<()> {
+        if style == Style::Plain {
+            return self.dst.write_all(text.as_bytes());
+        }
+        self.dst.set_color(&style.color_spec())?;
+        self.dst.write_all(text.as_bytes())?;
+        self.dst.reset()
+    }
+
+    /// Starts a new block, separated from the previous one by a blank line
+    /// unless `tight` is true.
+    fn separate(&mut self, tight: bool) -> io::Result<()> {
+        if self.blocks > 0 && !tight {
+            self.write("\n", Style::Plain)?;
+        }
+        self.blocks += 1;
+        Ok(())
+    }
+
+    /// Writes the inline Markdown `text`, wrapped to the width of the renderer.
+    /// The first line starts with `prefix`, which is `indent` columns wide, and
+    /// the others with `indent` spaces. The text outside of code, emphasis and
+    /// links has the style `plain`.
+    fn paragraph(
+        &mut self,
+        prefix: &str,
+        indent: usize,
+        text: &str,
+        plain: Style,
+    ) -> io::Result<()> {
+        // Split the runs into words, each of which may have several styles.
+        let mut words: Vec<Vec<(String, Style)>> = vec![vec![]];
+        for (run, style) in inline(text, plain) {
+            for (i, part) in run.split(' ').enumerate() {
+                if i > 0 && !words.last().unwrap().is_empty() {
+                    words.push(vec![]);
+                }
+                if !part.is_empty() {
+                    words.last_mut().unwrap().push((part.to_string(), style));
+                }
+            }
+        }
+
+        let available = self.width.saturating_sub(indent).max(1);
+        let mut column = 0;
+        self.write(prefix, Style::Plain)?;
+        for word in words.iter().filter(|word| !word.is_empty()) {
+            let len: usize = word.iter().map(|(part, _)| part.width()).sum();
+            if column > 0 && column + 1 + len > available {
+                self.write("\n", Style::Plain)?;
+                self.write(&" ".repeat(indent), Style::Plain)?;
+                column = 0;
+            } else if column > 0 {
+                self.write(" ", Style::Plain)?;
+                column += 1;
+            }
+            for (part, style) in word {
+                self.write(part, *style)?;
+            }
+            column += len;
+        }
+        self.write("\n", Style::Plain)
+    }
+
+    fn code_block(&mut self, code: &str, rust: bool) -> io::Result<()> {
+        let runs = if rust { highlight(code) } else { vec![(code, Style::Plain)] };
+        let indent = " ".repeat(CODE_INDENT);
+        let mut at_line_start = true;
+        for (run, style) in runs {
+            // Tokens like block comments may span several lines, each of which
+            // is indented.
+            for (i, line) in run.split('\n').enumerate() {
+                if i > 0 {
+                    self.write("\n", Style::Plain)?;
+                    at_line_start = true;
+                }
+                if line.is_empty() {
+                    continue;
+                }
+                if at_line_start {
+                    self.write(&indent, Style::Plain)?;
+                    at_line_start = false;
+                }
+                self.write(line, style)?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,113 @@
+use super::*;
+
+/// Removes the ANSI escape sequences of `text`.
+fn strip_colors(text: &str) -> String {
+    let mut stripped = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('\x1b') {
+        stripped.push_str(&rest[..start]);
+        rest = &rest[start..];
+        rest = &rest[rest.find('m').unwrap() + 1..];
+    }
+    stripped.push_str(rest);
+    stripped
+}
+
+#[test]
+fn blocks() {
+    let src = r#"# Title
+
+Some *text* with `code`, wrapped
+to the width.
+
+```compile_fail,E0308
+# fn hidden() {}
+let x: u32 = "a";
+```
+
+- a list item
+- another item which is long enough to wrap
+
+[RFC 401]: https://example.com
+"#;
+    let expected = r#"Title
+
+Some text with code,
+wrapped to the
+width.
+
+    let x: u32 = "a";
+
+- a list item
+- another item which
+  is long enough to
+  wrap
+
+[RFC 401]: https://example.com
+"#;
+    assert_eq!(render_to_string(src, Some(20), false), expected);
+}
+
+#[test]
+fn code_blocks() {
+    let src = "```text\n# not hidden\n```\n\n  ```\n  fn f() {\n      /* a\n  b */\n  }\n  ```\n";
+    let expected = "    # not hidden\n\n    fn f() {\n        /* a\n    b */\n    }\n";
+    assert_eq!(render_to_string(src, Some(80), false), expected);
+}
+
+#[test]
+fn colors() {
+    let src = "Some **text**.\n\n```\nfn f() -> &'static str { \"a\" } // 1\n```\n";
+    let colored = render_to_string(src, Some(80), true);
+    assert!(colored.contains('\x1b'));
+    assert_eq!(strip_colors(&colored), render_to_string(src, Some(80), false));
+}
+
+#[test]
+fn inline_styles() {
+    let runs = inline("a `b c` **d** _e_ snake_case_name *f* [g](h) [i][j] [k]", Style::Plain);
+    let runs = runs.iter().map(|(text, style)| (&**text, *style)).collect::<Vec<_>>();
+    assert_eq!(
+        runs,
+        [
+            ("a ", Style::Plain),
+            ("b c", Style::Code),
+            (" ", Style::Plain),
+            ("d", Style::Strong),
+            (" ", Style::Plain),
+            ("e", Style::Emphasis),
+            (" snake_case_name ", Style::Plain),
+            ("f", Style::Emphasis),
+            (" ", Style::Plain),
+            ("g <h>", Style::Link),
+            (" ", Style::Plain),
+            ("i", Style::Link),
+            (" [k]", Style::Plain),
+        ]
+    );
+}
+
+#[test]
+fn highlighting() {
+    let runs = highlight("let s = 'a' as u8; // c");
+    let styled = runs.into_iter().filter(|&(_, style)| style != Style::Plain).collect::<Vec<_>>();
+    assert_eq!(
+        styled,
+        [
+            ("let", Style::Keyword),
+            ("'a'", Style::Literal),
+            ("as", Style::Keyword),
+            ("// c", Style::Comment)
+        ]
+    );
+}
+
+#[test]
+fn rust_code_blocks() {
+    for info in &["", "rust", "compile_fail,E0308", "edition2018", "ignore (needs a crate)"] {
+        assert!(is_rust(info), "`{}` should be Rust", info);
+    }
+    for info in &["text", "console", "sh (shell)"] {
+        assert!(!is_rust(info), "`{}` should not be Rust", info);
+    }
+}
@@ -0,0 +1,345 @@
+//! A SARIF emitter for errors.
+//!
+//! [SARIF] is a JSON format for the output of static analysis tools, which is
+//! understood by code-scanning platforms. Unlike the JSON emitter, which
+//! writes one object per diagnostic, a SARIF log is a single document: the
+//! results are collected as diagnostics are emitted, and the log is written
+//! when the emitter is dropped.
+//!
+//! Each diagnostic becomes a result. Its error code or lint name is the rule
+//! of the result, its primary spans are the locations, and its secondary spans
+//! and the spans of its children are the related locations. Children without
+//! spans are appended to the message, and every substitution of a suggestion
+//! becomes a fix.
+//!
+//! [SARIF]: https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html
+
+use crate::emitter::Emitter;
+use crate::registry::Registry;
+use crate::{CodeSuggestion, DiagnosticId, Level, SubDiagnostic};
+
+use rustc_data_structures::fx::FxHashMap;
+use rustc_data_structures::sync::Lrc;
+use rustc_serialize::json::{Json, Object};
+use rustc_span::source_map::{FilePathMapping, SourceMap};
+use rustc_span::{MultiSpan, SourceFile, Span};
+
+use std::io::{self, Write};
+use std::path::Path;
+
+#[cfg(test)]
+mod tests;
+
+const SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+pub struct SarifEmitter {
+    dst: Box<dyn Write + Send>,
+    registry: Option<Registry>,
+    sm: Lrc<SourceMap>,
+    results: Vec<Json>,
+    rules: Vec<Json>,
+    /// Index of each rule in `rules`, by error code or lint name.
+    rule_indices: FxHashMap<String, usize>,
+}
+
+impl SarifEmitter {
+    pub fn stderr(registry: Option<Registry>, source_map: Lrc<SourceMap>) -> SarifEmitter {
+        SarifEmitter::new(Box::new(io::BufWriter::new(io::stderr())), registry, source_map)
+    }
+
+    pub fn basic() -> SarifEmitter {
+        let file_path_mapping = FilePathMapping::empty();
+        SarifEmitter::stderr(None, Lrc::new(SourceMap::new(file_path_mapping)))
+    }
+
+    pub fn new(
+        dst: Box<dyn Write + Send>,
+        registry: Option<Registry>,
+        source_map: Lrc<SourceMap>,
+    ) -> SarifEmitter {
+        SarifEmitter {
+            dst,
+            registry,
+            sm: source_map,
+            results: vec![],
+            rules: vec![],
+            rule_indices: Default::default(),
+        }
+    }
+
+    /// Returns the index of the rule for `code`, adding the rule if it is new.
+    fn rule_index(&mut self, code: &DiagnosticId) -> (String, usize) {
+        let (id, is_lint) = match code {
+            DiagnosticId::Error(code) => (code, false),
+            DiagnosticId::Lint { name, .. } => (name, true),
+        };
+        if let Some(&index) = self.rule_indices.get(id) {
+            return (id.clone(), index);
+        }
+
+        let mut rule = Object::new();
+        rule.insert("id".to_owned(), Json::String(id.clone()));
+        if is_lint {
+            rule.insert("name".to_owned(), Json::String(id.clone()));
+        } else {
+            rule.insert(
+                "helpUri".to_owned(),
+                Json::String(format!("https://doc.rust-lang.org/error-index.html#{}", id)),
+            );
+            let explanation = self.registry.as_ref().and_then(|r| r.try_find_description(id).ok());
+            if let Some(Some(explanation)) = explanation {
+                let mut help = Object::new();
+                help.insert("text".to_owned(), Json::String(explanation.to_owned()));
+                help.insert("markdown".to_owned(), Json::String(explanation.to_owned()));
+                rule.insert("help".to_owned(), Json::Object(help));
+            }
+        }
+
+        let index = self.rules.len();
+        self.rules.push(Json::Object(rule));
+        self.rule_indices.insert(id.clone(), index);
+        (id.clone(), index)
+    }
+
+    fn artifact_location(&self, file: &SourceFile) -> Option<Json> {
+        if !file.name.is_real() {
+            return None;
+        }
+        let path = file.name.prefer_local().to_string();
+        let mut location = Object::new();
+        if Path::new(&path).is_absolute() {
+            let uri = path_to_uri(&path);
+            let uri = if uri.starts_with('/') { uri } else { format!("/{}", uri) };
+            location.insert("uri".to_owned(), Json::String(format!("file://{}", uri)));
+        } else {
+            location.insert("uri".to_owned(), Json::String(path_to_uri(&path)));
+            location.insert("uriBaseId".to_owned(), Json::String("%SRCROOT%".to_owned()));
+        }
+        Some(Json::Object(location))
+    }
+
+    /// Returns the artifact location and the region of `span`, or `None` if
+    /// it doesn't point into a real file.
+    fn physical_location(&self, span: Span) -> Option<(Json, Json)> {
+        if span.is_dummy() {
+            return None;
+        }
+        let start = self.sm.lookup_char_pos(span.lo());
+        let end = self.sm.lookup_char_pos(span.hi());
+        let artifact = self.artifact_location(&start.file)?;
+
+        let byte_start = start.file.original_relative_byte_pos(span.lo()).0;
+        let byte_end = start.file.original_relative_byte_pos(span.hi()).0;
+        let mut region = Object::new();
+        region.insert("startLine".to_owned(), Json::U64(start.line as u64));
+        region.insert("startColumn".to_owned(), Json::U64(start.col.0 as u64 + 1));
+        region.insert("endLine".to_owned(), Json::U64(end.line as u64));
+        region.insert("endColumn".to_owned(), Json::U64(end.col.0 as u64 + 1));
+        region.insert("byteOffset".to_owned(), Json::U64(byte_start as u64));
+        let byte_length = byte_end.saturating_sub(byte_start);
+        region.insert("byteLength".to_owned(), Json::U64(byte_length as u64));
+        Some((artifact, Json::Object(region)))
+    }
+
+    fn location(&self, span: Span, message: Option<String>) -> Option<Json> {
+        let (artifact, region) = self.physical_location(span)?;
+        let mut physical = Object::new();
+        physical.insert("artifactLocation".to_owned(), artifact);
+        physical.insert("region".to_owned(), region);
+
+        let mut location = Object::new();
+        location.insert("physicalLocation".to_owned(), Json::Object(physical));
+        if let Some(message) = message {
+            location.insert("message".to_owned(), message_json(message));
+        }
+        Some(Json::Object(location))
+    }
+
+    /// Splits the labels of `span` into primary and secondary locations.
+    fn locations(&self, span: &MultiSpan, prefix: Option<&str>) -> (Vec<Json>, Vec<Json>) {
+        let mut primary = vec![];
+        let mut secondary = vec![];
+        for label in span.span_labels() {
+            let message = match (prefix, label.label) {
+                (Some(prefix), Some(label)) => Some(format!("{}: {}", prefix, label)),
+                (Some(prefix), None) => Some(prefix.to_owned()),
+                (None, label) => label,
+            };
+            if let Some(location) = self.location(label.span, message) {
+                if label.is_primary { primary.push(location) } else { secondary.push(location) }
+            }
+        }
+        (primary, secondary)
+    }
+
+    fn fixes(&self, suggestion: &CodeSuggestion) -> Vec<Json> {
+        suggestion
+            .substitutions
+            .iter()
+            .filter_map(|substitution| {
+                // One change per file, in the order the files first appear.
+                let mut changes: Vec<(Json, Vec<Json>)> = vec![];
+                for part in &substitution.parts {
+                    let (artifact, region) = self.physical_location(part.span)?;
+                    let mut replacement = Object::new();
+                    replacement.insert("deletedRegion".to_owned(), region);
+                    replacement.insert("insertedContent".to_owned(), message_json(&part.snippet));
+                    let replacement = Json::Object(replacement);
+                    match changes.iter_mut().find(|(a, _)| *a == artifact) {
+                        Some((_, replacements)) => replacements.push(replacement),
+                        None => changes.push((artifact, vec![replacement])),
+                    }
+                }
+
+                let changes = changes
+                    .into_iter()
+                    .map(|(artifact, replacements)| {
+                        let mut change = Object::new();
+                        change.insert("artifactLocation".to_owned(), artifact);
+                        change.insert("replacements".to_owned(), Json::Array(replacements));
+                        Json::Object(change)
+                    })
+                    .collect();
+
+                let mut properties = Object::new();
+                properties.insert(
+                    "applicability".to_owned(),
+                    Json::String(format!("{:?}", suggestion.applicability)),
+                );
+
+                let mut fix = Object::new();
+                let description = suggestion.msg.as_str_or_identifier();
+                fix.insert("description".to_owned(), message_json(description));
+                fix.insert("artifactChanges".to_owned(), Json::Array(changes));
+                fix.insert("properties".to_owned(), Json::Object(properties));
+                Some(Json::Object(fix))
+            })
+            .collect()
+    }
+
+    /// Adds the span-less `child` to `message`, or its spans to `related`.
+    fn add_child(&self, child: &SubDiagnostic, message: &mut String, related: &mut Vec<Json>) {
+        let span = child.render_span.as_ref().unwrap_or(&child.span);
+        let child_message = format!("{}: {}", child.level.to_str(), child.message());
+        if span.primary_spans().is_empty() {
+            message.push('\n');
+            message.push_str(&child_message);
+        } else {
+            let (primary, secondary) = self.locations(span, Some(&child_message));
+            related.extend(primary);
+            related.extend(secondary);
+        }
+    }
+
+    fn log(&mut self) -> Json {
+        let mut driver = Object::new();
+        driver.insert("name".to_owned(), Json::String("rustc".to_owned()));
+        driver.insert(
+            "informationUri".to_owned(),
+            Json::String("https://www.rust-lang.org/".to_owned()),
+        );
+        driver.insert("rules".to_owned(), Json::Array(std::mem::take(&mut self.rules)));
+        let mut tool = Object::new();
+        tool.insert("driver".to_owned(), Json::Object(driver));
+
+        let mut run = Object::new();
+        run.insert("tool".to_owned(), Json::Object(tool));
+        // rustc counts columns in characters, not in UTF-16 code units.
+        run.insert("columnKind".to_owned(), Json::String("unicodeCodePoints".to_owned()));
+        run.insert("results".to_owned(), Json::Array(std::mem::take(&mut self.results)));
+
+        let mut log = Object::new();
+        log.insert("$schema".to_owned(), Json::String(SCHEMA.to_owned()));
+        log.insert("version".to_owned(), Json::String("2.1.0".to_owned()));
+        log.insert("runs".to_owned(), Json::Array(vec![Json::Object(run)]));
+        Json::Object(log)
+    }
+}
+
+impl Emitter for SarifEmitter {
+    fn emit_diagnostic(&mut self, diag: &crate::Diagnostic) {
+        let level = match diag.level {
+            Level::Bug | Level::Fatal | Level::Error => "error",
+            Level::Warning => "warning",
+            Level::Note | Level::Help => "note",
+            Level::Allow => "none",
+            // Failure notes like "aborting due to previous error" are not
+            // results of their own.
+            Level::FailureNote | Level::Cancelled => return,
+        };
+
+        let mut result = Object::new();
+        if let Some(code) = &diag.code {
+            let (id, index) = self.rule_index(code);
+            result.insert("ruleId".to_owned(), Json::String(id));
+            result.insert("ruleIndex".to_owned(), Json::U64(index as u64));
+        }
+        result.insert("level".to_owned(), Json::String(level.to_owned()));
+
+        let mut message = diag.message();
+        let (locations, mut related) = self.locations(&diag.span, None);
+        for child in &diag.children {
+            self.add_child(child, &mut message, &mut related);
+        }
+        result.insert("message".to_owned(), message_json(message));
+        if !locations.is_empty() {
+            result.insert("locations".to_owned(), Json::Array(locations));
+        }
+        if !related.is_empty() {
+            result.insert("relatedLocations".to_owned(), Json::Array(related));
+        }
+
+        let fixes: Vec<_> = diag.suggestions.iter().flat_map(|s| self.fixes(s)).collect();
+        if !fixes.is_empty() {
+            result.insert("fixes".to_owned(), Json::Array(fixes));
+        }
+
+        self.results.push(Json::Object(result));
+    }
+
+    fn source_map(&self) -> Option<&Lrc<SourceMap>> {
+        Some(&self.sm)
+    }
+
+    fn should_show_explain(&self) -> bool {
+        false
+    }
+}
+
+impl Drop for SarifEmitter {
+    fn drop(&mut self) {
+        let log = self.log();
+        let result = writeln!(&mut self.dst, "{}", log.pretty()).and_then(|_| self.dst.flush());
+        // Panicking while already unwinding, e.g. because of a fatal error,
+        // would abort the process.
+        if let Err(e) = result {
+            if !std::thread::panicking() {
+                panic!("failed to print diagnostics: {:?}", e);
+            }
+        }
+    }
+}
+
+fn message_json(text: impl Into<String>) -> Json {
+    let mut message = Object::new();
+    message.insert("text".to_owned(), Json::String(text.into()));
+    Json::Object(message)
+}
+
+/// Turns a file path into a URI reference, percent-encoding everything but
+/// the unreserved characters and path separators.
+fn path_to_uri(path: &str) -> String {
+    let mut uri = String::with_capacity(path.len());
+    for byte in path.bytes() {
+        match byte {
+            b'\\' => uri.push('/'),
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' => {
+                uri.push(byte as char)
+            }
+            // Keep Windows drive letters like `C:` readable.
+            b':' if uri.len() == 1 => uri.push(':'),
+            _ => uri.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    uri
+}
@@ -0,0 +1,187 @@
+use super::*;
+
+use crate::{Applicability, Handler};
+use rustc_serialize::json;
+use rustc_span::{BytePos, Span};
+
+use std::str;
+use std::sync::{Arc, Mutex};
+
+struct Shared<T> {
+    data: Arc<Mutex<T>>,
+}
+
+impl<T: Write> Write for Shared<T> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.data.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.data.lock().unwrap().flush()
+    }
+}
+
+fn with_default_session_globals(f: impl FnOnce()) {
+    let session_globals = rustc_span::SessionGlobals::new(rustc_span::edition::DEFAULT_EDITION);
+    rustc_span::SESSION_GLOBALS.set(&session_globals, f);
+}
+
+/// Emits the diagnostics of `emit` for `code` in `test.rs`, and returns the
+/// SARIF log.
+fn sarif_log(code: &str, emit: impl FnOnce(&Handler)) -> Json {
+    let mut log = None;
+    with_default_session_globals(|| {
+        let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+        sm.new_source_file(Path::new("test.rs").to_owned().into(), code.to_owned());
+
+        let output = Arc::new(Mutex::new(Vec::new()));
+        let registry = Registry::new(&[("E0001", Some("An explanation."))]);
+        let emitter =
+            SarifEmitter::new(Box::new(Shared { data: output.clone() }), Some(registry), sm);
+        let handler = Handler::with_emitter(true, None, Box::new(emitter));
+        emit(&handler);
+        // The log is written when the emitter is dropped.
+        drop(handler);
+
+        let bytes = output.lock().unwrap();
+        log = Some(json::from_str(str::from_utf8(&bytes).unwrap()).unwrap());
+    });
+    log.unwrap()
+}
+
+fn span(lo: u32, hi: u32) -> Span {
+    Span::with_root_ctxt(BytePos(lo), BytePos(hi))
+}
+
+fn results(log: &Json) -> &[Json] {
+    log.find_path(&["runs"]).unwrap().as_array().unwrap()[0]
+        .find("results")
+        .unwrap()
+        .as_array()
+        .unwrap()
+}
+
+fn rules(log: &Json) -> &[Json] {
+    log.find_path(&["runs"]).unwrap().as_array().unwrap()[0]
+        .find_path(&["tool", "driver", "rules"])
+        .unwrap()
+        .as_array()
+        .unwrap()
+}
+
+fn string<'a>(json: &'a Json, path: &[&str]) -> &'a str {
+    json.find_path(path).unwrap().as_string().unwrap()
+}
+
+fn number(json: &Json, path: &[&str]) -> u64 {
+    json.find_path(path).unwrap().as_u64().unwrap()
+}
+
+#[test]
+fn empty_log() {
+    let log = sarif_log("", |_| {});
+    assert_eq!(string(&log, &["version"]), "2.1.0");
+    assert!(results(&log).is_empty());
+    assert!(rules(&log).is_empty());
+}
+
+#[test]
+fn error_with_code() {
+    let log = sarif_log("fn main() {}\n", |handler| {
+        handler
+            .struct_span_err_with_code(span(3, 7), "bad name", DiagnosticId::Error("E0001".into()))
+            .span_label(span(3, 7), "here")
+            .span_label(span(10, 12), "body")
+            .note("a note")
+            .emit();
+    });
+
+    let results = results(&log);
+    assert_eq!(results.len(), 1);
+    let result = &results[0];
+    assert_eq!(string(result, &["ruleId"]), "E0001");
+    assert_eq!(number(result, &["ruleIndex"]), 0);
+    assert_eq!(string(result, &["level"]), "error");
+    assert_eq!(string(result, &["message", "text"]), "bad name\nnote: a note");
+
+    let location = &result.find("locations").unwrap().as_array().unwrap()[0];
+    assert_eq!(string(location, &["message", "text"]), "here");
+    let physical = location.find("physicalLocation").unwrap();
+    assert_eq!(string(physical, &["artifactLocation", "uri"]), "test.rs");
+    assert_eq!(string(physical, &["artifactLocation", "uriBaseId"]), "%SRCROOT%");
+    assert_eq!(number(physical, &["region", "startLine"]), 1);
+    assert_eq!(number(physical, &["region", "startColumn"]), 4);
+    assert_eq!(number(physical, &["region", "endColumn"]), 8);
+    assert_eq!(number(physical, &["region", "byteOffset"]), 3);
+    assert_eq!(number(physical, &["region", "byteLength"]), 4);
+
+    let related = result.find("relatedLocations").unwrap().as_array().unwrap();
+    assert_eq!(related.len(), 1);
+    assert_eq!(string(&related[0], &["message", "text"]), "body");
+
+    let rules = rules(&log);
+    assert_eq!(rules.len(), 1);
+    assert_eq!(string(&rules[0], &["id"]), "E0001");
+    assert_eq!(string(&rules[0], &["helpUri"]), "https://doc.rust-lang.org/error-index.html#E0001");
+    assert_eq!(string(&rules[0], &["help", "markdown"]), "An explanation.");
+}
+
+#[test]
+fn lints_share_rules() {
+    let log = sarif_log("fn main() {}\n", |handler| {
+        for &(lo, hi) in &[(0, 2), (3, 7)] {
+            let lint = DiagnosticId::Lint { name: "some_lint".into(), has_future_breakage: false };
+            let mut diag = handler.struct_span_warn(span(lo, hi), "lint fired");
+            diag.code(lint);
+            diag.emit();
+        }
+    });
+
+    let results = results(&log);
+    assert_eq!(results.len(), 2);
+    for result in results {
+        assert_eq!(string(result, &["level"]), "warning");
+        assert_eq!(string(result, &["ruleId"]), "some_lint");
+        assert_eq!(number(result, &["ruleIndex"]), 0);
+    }
+
+    let rules = rules(&log);
+    assert_eq!(rules.len(), 1);
+    assert_eq!(string(&rules[0], &["name"]), "some_lint");
+    assert!(rules[0].find("helpUri").is_none());
+}
+
+#[test]
+fn suggestions_become_fixes() {
+    let log = sarif_log("fn mian() {}\n", |handler| {
+        handler
+            .struct_span_err(span(3, 7), "typo")
+            .span_suggestion(
+                span(3, 7),
+                "use the right name",
+                "main".to_owned(),
+                Applicability::MachineApplicable,
+            )
+            .emit();
+    });
+
+    let result = &results(&log)[0];
+    assert!(result.find("ruleId").is_none());
+    let fix = &result.find("fixes").unwrap().as_array().unwrap()[0];
+    assert_eq!(string(fix, &["description", "text"]), "use the right name");
+    assert_eq!(string(fix, &["properties", "applicability"]), "MachineApplicable");
+    let change = &fix.find("artifactChanges").unwrap().as_array().unwrap()[0];
+    assert_eq!(string(change, &["artifactLocation", "uri"]), "test.rs");
+    let replacement = &change.find("replacements").unwrap().as_array().unwrap()[0];
+    assert_eq!(string(replacement, &["insertedContent", "text"]), "main");
+    assert_eq!(number(replacement, &["deletedRegion", "byteOffset"]), 3);
+    assert_eq!(number(replacement, &["deletedRegion", "byteLength"]), 4);
+}
+
+#[test]
+fn uris() {
+    assert_eq!(path_to_uri("src/main.rs"), "src/main.rs");
+    assert_eq!(path_to_uri("src\\my file.rs"), "src/my%20file.rs");
+    assert_eq!(path_to_uri("C:\\src\\lib.rs"), "C:/src/lib.rs");
+    assert_eq!(path_to_uri("src/caf\u{e9}.rs"), "src/caf%C3%A9.rs");
+}
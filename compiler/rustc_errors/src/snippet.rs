@@ -161,6 +161,8 @@ pub fn takes_space(&self) -> bool {
 pub struct StyledString {
     pub text: String,
     pub style: Style,
+    /// The URL the text links to, if it is a hyperlink.
+    pub link: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Hash, Encodable, Decodable)]
@@ -5,25 +5,29 @@
 #[derive(Debug)]
 pub struct StyledBuffer {
     lines: Vec<Vec<StyledChar>>,
+    /// The URLs of the hyperlinks in the buffer.
+    links: Vec<String>,
 }
 
 #[derive(Debug, Clone)]
 struct StyledChar {
     chr: char,
     style: Style,
+    /// The index in `links` of the URL this character links to, if any.
+    link: Option<usize>,
 }
 
 impl StyledChar {
     const SPACE: Self = StyledChar::new(' ', Style::NoStyle);
 
     const fn new(chr: char, style: Style) -> Self {
-        StyledChar { chr, style }
+        StyledChar { chr, style, link: None }
     }
 }
 
 impl StyledBuffer {
     pub fn new() -> StyledBuffer {
-        StyledBuffer { lines: vec![] }
+        StyledBuffer { lines: vec![], links: vec![] }
     }
 
     /// Returns content of `StyledBuffer` splitted by lines and line styles
@@ -34,22 +38,30 @@ pub fn render(&self) -> Vec<Vec<StyledString>> {
         let mut output: Vec<Vec<StyledString>> = vec![];
         let mut styled_vec: Vec<StyledString> = vec![];
 
+        let styled_string = |text, style, link: Option<usize>| StyledString {
+            text,
+            style,
+            link: link.map(|link| self.links[link].clone()),
+        };
+
         for styled_line in &self.lines {
             let mut current_style = Style::NoStyle;
+            let mut current_link = None;
             let mut current_text = String::new();
 
             for sc in styled_line {
-                if sc.style != current_style {
+                if sc.style != current_style || sc.link != current_link {
                     if !current_text.is_empty() {
-                        styled_vec.push(StyledString { text: current_text, style: current_style });
+                        styled_vec.push(styled_string(current_text, current_style, current_link));
                     }
                     current_style = sc.style;
+                    current_link = sc.link;
                     current_text = String::new();
                 }
                 current_text.push(sc.chr);
             }
             if !current_text.is_empty() {
-                styled_vec.push(StyledString { text: current_text, style: current_style });
+                styled_vec.push(styled_string(current_text, current_style, current_link));
             }
 
             // We're done with the row, push and keep going
@@ -116,6 +128,38 @@ pub fn append(&mut self, line: usize, string: &str, style: Style) {
         }
     }
 
+    /// For given `line` inserts `string` with `style` before old content of that line, as a
+    /// hyperlink to `url`
+    pub fn prepend_link(&mut self, line: usize, string: &str, style: Style, url: &str) {
+        self.prepend(line, string, style);
+        self.set_link_range(line, 0, string.chars().count(), url);
+    }
+
+    /// For given `line` inserts `string` with `style` after old content of that line, as a
+    /// hyperlink to `url`
+    pub fn append_link(&mut self, line: usize, string: &str, style: Style, url: &str) {
+        let col_start = self.lines.get(line).map_or(0, |line| line.len());
+        self.append(line, string, style);
+        self.set_link_range(line, col_start, col_start + string.chars().count(), url);
+    }
+
+    /// Makes the characters of `line` in the `col_start..col_end` range a hyperlink to `url`.
+    /// The link moves with the characters when content is later prepended to the line.
+    pub fn set_link_range(&mut self, line: usize, col_start: usize, col_end: usize, url: &str) {
+        let link = match self.links.iter().position(|link| link == url) {
+            Some(link) => link,
+            None => {
+                self.links.push(url.to_owned());
+                self.links.len() - 1
+            }
+        };
+        if let Some(line) = self.lines.get_mut(line) {
+            for sc in line.iter_mut().take(col_end).skip(col_start) {
+                sc.link = Some(link);
+            }
+        }
+    }
+
     pub fn num_lines(&self) -> usize {
         self.lines.len()
     }
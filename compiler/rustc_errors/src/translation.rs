@@ -0,0 +1,919 @@
+//! Translation of diagnostic messages.
+//!
+//! A [`DiagnosticMessage`] is either a string, or the identifier of a message
+//! in a catalogue. Catalogues are written in a subset of the [Fluent] syntax:
+//!
+//! ```text
+//! # Comments start with `#`.
+//! typeck-missing-cast = can't pass `{$ty}` to variadic function
+//!     .suggestion = cast the value to `{$cast_ty}`
+//! typeck-supplied-args = supplied {$count} {$count ->
+//!         [one] argument
+//!        *[other] arguments
+//!     }
+//! ```
+//!
+//! Messages can have a value and attributes, which may span several indented
+//! lines. Placeables can refer to the arguments of the diagnostic (`{$name}`),
+//! to other messages (`{other-message}` or `{other-message.attr}`), be string
+//! or number literals, or select a variant based on the value of an argument:
+//! numbers match variants with the same number, or the plural category of the
+//! number in the language of the catalogue; strings match variants with the
+//! same name; everything else selects the default variant, marked with `*`.
+//!
+//! Messages are translated by the [`Translator`] of the `Handler` when the
+//! diagnostic is emitted, so that the emitters only ever see strings. The
+//! translator looks messages up in the catalogue given with
+//! `-Ztranslate-additional-ftl`, then in the catalogue of the language given
+//! with `-Ztranslate-lang`, and finally in the English catalogue built into the
+//! compiler, which defines every message.
+//!
+//! [Fluent]: https://projectfluent.org/
+
+use crate::{CodeSuggestion, Diagnostic, SubDiagnostic};
+
+use rustc_data_structures::fx::FxHashMap;
+use rustc_span::symbol::{Ident, Symbol};
+use rustc_span::{DiagnosticMessage, MultiSpan};
+
+use std::borrow::Cow;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use tracing::debug;
+
+#[cfg(test)]
+mod tests;
+
+/// The catalogues of the built-in English messages.
+pub static DEFAULT_LOCALE_RESOURCES: &[&str] = &[include_str!("../locales/en-US/typeck.ftl")];
+
+/// The language of the built-in messages.
+pub const FALLBACK_LOCALE: &str = "en-US";
+
+/// Nested message references are not followed past this depth, to protect
+/// against cycles.
+const MAX_REFERENCE_DEPTH: usize = 16;
+
+/// The value of an argument of a diagnostic, which can be referred to by its
+/// name in translated messages.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Encodable, Decodable)]
+pub enum DiagnosticArgValue {
+    Str(String),
+    Number(usize),
+}
+
+/// The name and value of an argument of a diagnostic.
+pub type DiagnosticArg = (String, DiagnosticArgValue);
+
+/// Converts a value into an argument of a diagnostic.
+pub trait IntoDiagnosticArg {
+    fn into_diagnostic_arg(self) -> DiagnosticArgValue;
+}
+
+impl IntoDiagnosticArg for String {
+    fn into_diagnostic_arg(self) -> DiagnosticArgValue {
+        DiagnosticArgValue::Str(self)
+    }
+}
+
+impl<'a> IntoDiagnosticArg for &'a str {
+    fn into_diagnostic_arg(self) -> DiagnosticArgValue {
+        DiagnosticArgValue::Str(self.to_owned())
+    }
+}
+
+impl<'a> IntoDiagnosticArg for Cow<'a, str> {
+    fn into_diagnostic_arg(self) -> DiagnosticArgValue {
+        DiagnosticArgValue::Str(self.into_owned())
+    }
+}
+
+impl IntoDiagnosticArg for usize {
+    fn into_diagnostic_arg(self) -> DiagnosticArgValue {
+        DiagnosticArgValue::Number(self)
+    }
+}
+
+impl IntoDiagnosticArg for Symbol {
+    fn into_diagnostic_arg(self) -> DiagnosticArgValue {
+        DiagnosticArgValue::Str(self.to_string())
+    }
+}
+
+impl IntoDiagnosticArg for Ident {
+    fn into_diagnostic_arg(self) -> DiagnosticArgValue {
+        DiagnosticArgValue::Str(self.to_string())
+    }
+}
+
+/// An error in the syntax of a catalogue.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// The line of the error, starting at 1.
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+/// An error while loading the catalogues of a [`Translator`].
+#[derive(Debug)]
+pub enum TranslationError {
+    /// There are no catalogues for the requested language in the sysroot.
+    MissingLocale {
+        locale: String,
+        dir: PathBuf,
+    },
+    Io {
+        path: PathBuf,
+        error: io::Error,
+    },
+    /// The catalogue `path` has syntax errors, which aren't part of the
+    /// message of the error.
+    Parse {
+        path: PathBuf,
+        errors: Vec<ParseError>,
+    },
+}
+
+impl fmt::Display for TranslationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TranslationError::MissingLocale { locale, dir } => write!(
+                f,
+                "no translation catalogues for language `{}` (looked in `{}`)",
+                locale,
+                dir.display()
+            ),
+            TranslationError::Io { path, error } => {
+                write!(f, "failed to read translation catalogue `{}`: {}", path.display(), error)
+            }
+            TranslationError::Parse { path, .. } => {
+                write!(f, "failed to parse translation catalogue `{}`", path.display())
+            }
+        }
+    }
+}
+
+/// The messages of one language.
+#[derive(Debug)]
+pub struct Catalogue {
+    locale: String,
+    messages: FxHashMap<String, Message>,
+}
+
+impl Catalogue {
+    pub fn new(locale: &str) -> Catalogue {
+        Catalogue { locale: locale.to_owned(), messages: Default::default() }
+    }
+
+    /// Returns the language of the messages in the catalogue.
+    pub fn locale(&self) -> &str {
+        &self.locale
+    }
+
+    /// Parses `source` and adds its messages to the catalogue.
+    ///
+    /// If there are any errors, none of the messages are added. It is an
+    /// error to define a message that is already in the catalogue.
+    pub fn add_resource(&mut self, source: &str) -> Result<(), Vec<ParseError>> {
+        let mut errors = vec![];
+        let messages = Parser::parse_resource(source, &mut errors);
+        for (line, id, _) in &messages {
+            if self.messages.contains_key(id)
+                || messages.iter().filter(|(_, other, _)| other == id).count() > 1
+            {
+                errors.push(ParseError {
+                    line: *line,
+                    message: format!("message `{}` is defined more than once", id),
+                });
+            }
+        }
+        if !errors.is_empty() {
+            errors.sort_by_key(|e| e.line);
+            errors.dedup();
+            return Err(errors);
+        }
+        self.messages.extend(messages.into_iter().map(|(_, id, message)| (id, message)));
+        Ok(())
+    }
+
+    /// Returns `true` if the catalogue has the message `id`, or its attribute
+    /// `attr` if one is given.
+    pub fn has_message(&self, id: &str, attr: Option<&str>) -> bool {
+        self.pattern(id, attr).is_some()
+    }
+
+    fn pattern(&self, id: &str, attr: Option<&str>) -> Option<&Pattern> {
+        let message = self.messages.get(id)?;
+        match attr {
+            Some(attr) => message.attributes.get(attr),
+            None => message.value.as_ref(),
+        }
+    }
+}
+
+/// Translates diagnostic messages using a list of catalogues.
+#[derive(Debug)]
+pub struct Translator {
+    /// The catalogues in which messages are looked up, in order; the last one
+    /// is always the built-in English catalogue.
+    catalogues: Vec<Catalogue>,
+}
+
+impl Translator {
+    /// Returns a translator for the built-in English messages.
+    pub fn fallback() -> Translator {
+        Translator { catalogues: vec![fallback_catalogue()] }
+    }
+
+    /// Returns a translator for the language `locale`, whose catalogues are
+    /// the `.ftl` files in `$sysroot/share/locale/$locale`, preferring the
+    /// messages of the catalogue `additional_ftl`.
+    pub fn new(
+        sysroot: &Path,
+        locale: Option<&str>,
+        additional_ftl: Option<&Path>,
+    ) -> Result<Translator, TranslationError> {
+        let mut catalogues = vec![];
+        let locale_name = locale.unwrap_or(FALLBACK_LOCALE);
+
+        if let Some(path) = additional_ftl {
+            let mut catalogue = Catalogue::new(locale_name);
+            add_resource_file(&mut catalogue, path)?;
+            catalogues.push(catalogue);
+        }
+
+        if let Some(locale) = locale.filter(|&locale| locale != FALLBACK_LOCALE) {
+            let dir = sysroot.join("share").join("locale").join(locale);
+            let missing =
+                || TranslationError::MissingLocale { locale: locale.to_owned(), dir: dir.clone() };
+            let entries = fs::read_dir(&dir).map_err(|_| missing())?;
+            let mut paths = entries
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| path.extension().map_or(false, |ext| ext == "ftl"))
+                .collect::<Vec<_>>();
+            if paths.is_empty() {
+                return Err(missing());
+            }
+            // Load the files in a deterministic order, so that errors are too.
+            paths.sort();
+
+            let mut catalogue = Catalogue::new(locale);
+            for path in paths {
+                add_resource_file(&mut catalogue, &path)?;
+            }
+            catalogues.push(catalogue);
+        }
+
+        catalogues.push(fallback_catalogue());
+        Ok(Translator { catalogues })
+    }
+
+    /// Translates `message` with the arguments `args`.
+    pub fn translate_message<'a>(
+        &self,
+        message: &'a DiagnosticMessage,
+        args: &[DiagnosticArg],
+    ) -> Cow<'a, str> {
+        match message {
+            DiagnosticMessage::Str(s) => Cow::Borrowed(s),
+            DiagnosticMessage::FluentIdentifier(id, attr) => {
+                let mut out = String::new();
+                self.format_message(id, attr.as_deref(), args, 0, &mut out);
+                Cow::Owned(out)
+            }
+        }
+    }
+
+    /// Returns `diagnostic` with all of its messages translated.
+    pub fn translate_diagnostic<'a>(&self, diagnostic: &'a Diagnostic) -> Cow<'a, Diagnostic> {
+        if !needs_translation(diagnostic) {
+            return Cow::Borrowed(diagnostic);
+        }
+
+        let mut diagnostic = diagnostic.clone();
+        let args = std::mem::take(&mut diagnostic.args);
+        let translate = |message: &mut DiagnosticMessage| {
+            if let DiagnosticMessage::FluentIdentifier(..) = message {
+                *message = DiagnosticMessage::Str(self.translate_message(message, &args).into());
+            }
+        };
+        let translate_span =
+            |span: &mut MultiSpan| span.span_label_messages_mut().for_each(translate);
+
+        diagnostic.message.iter_mut().for_each(|(message, _)| translate(message));
+        translate_span(&mut diagnostic.span);
+        for child in &mut diagnostic.children {
+            child.message.iter_mut().for_each(|(message, _)| translate(message));
+            translate_span(&mut child.span);
+            if let Some(render_span) = &mut child.render_span {
+                translate_span(render_span);
+            }
+        }
+        for suggestion in &mut diagnostic.suggestions {
+            translate(&mut suggestion.msg);
+        }
+        diagnostic.args = args;
+        Cow::Owned(diagnostic)
+    }
+
+    /// Formats the message `id` into `out`, using the first catalogue that
+    /// has it. Messages that can't be found are written as `{id}`.
+    fn format_message(
+        &self,
+        id: &str,
+        attr: Option<&str>,
+        args: &[DiagnosticArg],
+        depth: usize,
+        out: &mut String,
+    ) {
+        let found = self
+            .catalogues
+            .iter()
+            .find_map(|catalogue| Some((catalogue, catalogue.pattern(id, attr)?)));
+        match found {
+            Some((catalogue, pattern)) if depth < MAX_REFERENCE_DEPTH => {
+                self.format_pattern(catalogue, pattern, args, depth, out)
+            }
+            _ => {
+                debug!("failed to translate message `{}` (attribute {:?})", id, attr);
+                out.push('{');
+                out.push_str(id);
+                if let Some(attr) = attr {
+                    out.push('.');
+                    out.push_str(attr);
+                }
+                out.push('}');
+            }
+        }
+    }
+
+    fn format_pattern(
+        &self,
+        catalogue: &Catalogue,
+        pattern: &Pattern,
+        args: &[DiagnosticArg],
+        depth: usize,
+        out: &mut String,
+    ) {
+        for element in pattern {
+            match element {
+                PatternElement::Text(text) => out.push_str(text),
+                PatternElement::Placeable(Expression::Inline(expr)) => {
+                    self.format_inline(expr, args, depth, out)
+                }
+                PatternElement::Placeable(Expression::Select { selector, variants }) => {
+                    let variant = select_variant(catalogue, selector, variants, args);
+                    self.format_pattern(catalogue, &variant.value, args, depth, out)
+                }
+            }
+        }
+    }
+
+    fn format_inline(
+        &self,
+        expr: &InlineExpression,
+        args: &[DiagnosticArg],
+        depth: usize,
+        out: &mut String,
+    ) {
+        match expr {
+            InlineExpression::Variable(name) => match find_arg(args, name) {
+                Some(DiagnosticArgValue::Str(s)) => out.push_str(s),
+                Some(DiagnosticArgValue::Number(n)) => out.push_str(&n.to_string()),
+                None => {
+                    debug!("missing argument `{}` of diagnostic message", name);
+                    out.push_str("{$");
+                    out.push_str(name);
+                    out.push('}');
+                }
+            },
+            InlineExpression::StringLiteral(s) | InlineExpression::NumberLiteral(s) => {
+                out.push_str(s)
+            }
+            InlineExpression::MessageReference(id, attr) => {
+                self.format_message(id, attr.as_deref(), args, depth + 1, out)
+            }
+        }
+    }
+}
+
+fn fallback_catalogue() -> Catalogue {
+    let mut catalogue = Catalogue::new(FALLBACK_LOCALE);
+    for resource in DEFAULT_LOCALE_RESOURCES {
+        if let Err(errors) = catalogue.add_resource(resource) {
+            panic!("invalid built-in translation catalogue: {:?}", errors);
+        }
+    }
+    catalogue
+}
+
+fn add_resource_file(catalogue: &mut Catalogue, path: &Path) -> Result<(), TranslationError> {
+    let source = fs::read_to_string(path)
+        .map_err(|error| TranslationError::Io { path: path.to_owned(), error })?;
+    catalogue
+        .add_resource(&source)
+        .map_err(|errors| TranslationError::Parse { path: path.to_owned(), errors })
+}
+
+fn needs_translation(diagnostic: &Diagnostic) -> bool {
+    let is_identifier = |message: &DiagnosticMessage| message.as_str().is_none();
+    let span_needs_translation =
+        |span: &MultiSpan| span.span_label_messages().any(|(_, label)| is_identifier(label));
+    let child_needs_translation = |child: &SubDiagnostic| {
+        child.message.iter().any(|(message, _)| is_identifier(message))
+            || span_needs_translation(&child.span)
+            || child.render_span.as_ref().map_or(false, span_needs_translation)
+    };
+    let suggestion_needs_translation = |suggestion: &CodeSuggestion| is_identifier(&suggestion.msg);
+
+    diagnostic.message.iter().any(|(message, _)| is_identifier(message))
+        || span_needs_translation(&diagnostic.span)
+        || diagnostic.children.iter().any(child_needs_translation)
+        || diagnostic.suggestions.iter().any(suggestion_needs_translation)
+}
+
+fn find_arg<'a>(args: &'a [DiagnosticArg], name: &str) -> Option<&'a DiagnosticArgValue> {
+    args.iter().rev().find(|(arg, _)| arg == name).map(|(_, value)| value)
+}
+
+fn select_variant<'a>(
+    catalogue: &Catalogue,
+    selector: &InlineExpression,
+    variants: &'a [Variant],
+    args: &[DiagnosticArg],
+) -> &'a Variant {
+    let number = |s: &str| s.parse::<f64>().ok();
+    let with_number = |n: Option<f64>| {
+        variants.iter().find(|v| matches!(&v.key, VariantKey::Number(k) if number(k) == n))
+    };
+    let with_identifier = |name: &str| {
+        variants.iter().find(|v| matches!(&v.key, VariantKey::Identifier(k) if k == name))
+    };
+    let selected = match selector {
+        InlineExpression::Variable(name) => match find_arg(args, name) {
+            Some(&DiagnosticArgValue::Number(n)) => with_number(Some(n as f64))
+                .or_else(|| with_identifier(plural_category(&catalogue.locale, n))),
+            Some(DiagnosticArgValue::Str(s)) => with_identifier(s),
+            None => None,
+        },
+        InlineExpression::StringLiteral(s) => with_identifier(s),
+        InlineExpression::NumberLiteral(s) => with_number(number(s)),
+        InlineExpression::MessageReference(..) => None,
+    };
+    // The parser ensures that there is exactly one default variant.
+    selected.unwrap_or_else(|| variants.iter().find(|v| v.default).unwrap())
+}
+
+/// Returns the CLDR plural category of the integer `n` in the language
+/// `locale`.
+fn plural_category(locale: &str, n: usize) -> &'static str {
+    let language = locale.split(|c| c == '-' || c == '_').next().unwrap_or("");
+    let (n10, n100) = (n % 10, n % 100);
+    match language {
+        "ja" | "ko" | "zh" | "th" | "vi" | "id" | "ms" => "other",
+        "fr" | "pt" | "hi" | "bn" if n <= 1 => "one",
+        "ru" | "uk" | "be" | "sr" | "hr" | "bs" => {
+            if n10 == 1 && n100 != 11 {
+                "one"
+            } else if (2..=4).contains(&n10) && !(12..=14).contains(&n100) {
+                "few"
+            } else {
+                "many"
+            }
+        }
+        "pl" => {
+            if n == 1 {
+                "one"
+            } else if (2..=4).contains(&n10) && !(12..=14).contains(&n100) {
+                "few"
+            } else {
+                "many"
+            }
+        }
+        "cs" | "sk" => match n {
+            1 => "one",
+            2..=4 => "few",
+            _ => "other",
+        },
+        "fr" | "pt" | "hi" | "bn" => "other",
+        _ if n == 1 => "one",
+        _ => "other",
+    }
+}
+
+#[derive(Debug)]
+struct Message {
+    value: Option<Pattern>,
+    attributes: FxHashMap<String, Pattern>,
+}
+
+type Pattern = Vec<PatternElement>;
+
+#[derive(Debug, PartialEq)]
+enum PatternElement {
+    Text(String),
+    Placeable(Expression),
+}
+
+#[derive(Debug, PartialEq)]
+enum Expression {
+    Inline(InlineExpression),
+    Select { selector: InlineExpression, variants: Vec<Variant> },
+}
+
+#[derive(Debug, PartialEq)]
+enum InlineExpression {
+    Variable(String),
+    StringLiteral(String),
+    NumberLiteral(String),
+    MessageReference(String, Option<String>),
+}
+
+#[derive(Debug, PartialEq)]
+struct Variant {
+    key: VariantKey,
+    value: Pattern,
+    default: bool,
+}
+
+#[derive(Debug, PartialEq)]
+enum VariantKey {
+    Identifier(String),
+    Number(String),
+}
+
+/// Parses the patterns of a catalogue.
+///
+/// A catalogue is first split into entries line by line, and the lines of the
+/// value and of each attribute of a message are joined, after removing their
+/// common indentation. The patterns are then parsed character by character.
+struct Parser<'a> {
+    src: &'a str,
+    pos: usize,
+    /// The line of the catalogue on which `src` starts.
+    first_line: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn parse_resource(source: &str, errors: &mut Vec<ParseError>) -> Vec<(usize, String, Message)> {
+        let lines = source.lines().collect::<Vec<_>>();
+        let mut messages = vec![];
+        let mut i = 0;
+        while i < lines.len() {
+            let line = lines[i];
+            let line_number = i + 1;
+            i += 1;
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            // The indented lines after the first one belong to the same entry.
+            let mut end = i;
+            while end < lines.len() && (lines[end].trim().is_empty() || is_indented(lines[end])) {
+                end += 1;
+            }
+            let continuation = &lines[i..end];
+            i = end;
+
+            let (id, value) = match split_definition(line) {
+                Some((id, value)) if !is_indented(line) => (id, value),
+                _ => {
+                    errors.push(ParseError {
+                        line: line_number,
+                        message: "expected a message definition like `message-id = ...`".to_owned(),
+                    });
+                    continue;
+                }
+            };
+
+            match Parser::parse_message(line_number, value, continuation) {
+                Ok(message) => messages.push((line_number, id.to_owned(), message)),
+                Err(error) => errors.push(error),
+            }
+        }
+        messages
+    }
+
+    fn parse_message(
+        line_number: usize,
+        value: &str,
+        continuation: &[&str],
+    ) -> Result<Message, ParseError> {
+        // Split the lines into the value and the attributes.
+        let mut blocks = vec![(line_number, None, value, vec![])];
+        for (offset, &line) in continuation.iter().enumerate() {
+            match line.trim_start().strip_prefix('.').and_then(split_definition) {
+                Some((name, value)) => {
+                    blocks.push((line_number + offset + 1, Some(name), value, vec![]))
+                }
+                None => blocks.last_mut().unwrap().3.push(line),
+            }
+        }
+
+        let mut message = Message { value: None, attributes: Default::default() };
+        for (line, name, first, rest) in blocks {
+            let pattern = if first.trim().is_empty() && rest.iter().all(|l| l.trim().is_empty()) {
+                None
+            } else {
+                Some(Parser::parse_block(line, first, &rest)?)
+            };
+            match (name, pattern) {
+                (None, pattern) => message.value = pattern,
+                (Some(name), Some(pattern)) => {
+                    if message.attributes.insert(name.to_owned(), pattern).is_some() {
+                        return Err(ParseError {
+                            line,
+                            message: format!("attribute `{}` is defined more than once", name),
+                        });
+                    }
+                }
+                (Some(name), None) => {
+                    return Err(ParseError {
+                        line,
+                        message: format!("attribute `{}` has no value", name),
+                    });
+                }
+            }
+        }
+        if message.value.is_none() && message.attributes.is_empty() {
+            return Err(ParseError {
+                line: line_number,
+                message: "expected a value or an attribute".to_owned(),
+            });
+        }
+        Ok(message)
+    }
+
+    /// Parses a pattern whose first line is `first`, followed by the lines
+    /// `rest`.
+    fn parse_block(line: usize, first: &str, rest: &[&str]) -> Result<Pattern, ParseError> {
+        let indent = rest
+            .iter()
+            .filter(|l| !l.trim().is_empty())
+            .map(|l| l.len() - l.trim_start().len())
+            .min()
+            .unwrap_or(0);
+        let mut src = first.trim_start().to_owned();
+        let mut first_line = line;
+        for l in rest {
+            src.push('\n');
+            src.push_str(l.get(indent..).unwrap_or(""));
+        }
+        if first.trim().is_empty() {
+            // A pattern that starts on the line after the identifier.
+            src.remove(0);
+            first_line += 1;
+        }
+        let src = src.trim_end();
+
+        let mut parser = Parser { src, pos: 0, first_line };
+        let pattern = parser.parse_pattern(false)?;
+        if parser.pos < src.len() {
+            return Err(parser.error("unexpected `}`"));
+        }
+        Ok(pattern)
+    }
+
+    fn error(&self, message: &str) -> ParseError {
+        let line = self.first_line + self.src[..self.pos].matches('\n').count();
+        ParseError { line, message: message.to_owned() }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.src[self.pos..].chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), ParseError> {
+        if self.eat(c) { Ok(()) } else { Err(self.error(&format!("expected `{}`", c))) }
+    }
+
+    fn skip_blank(&mut self) {
+        while self.peek().map_or(false, char::is_whitespace) {
+            self.bump();
+        }
+    }
+
+    fn skip_blank_inline(&mut self) {
+        while self.peek() == Some(' ') {
+            self.bump();
+        }
+    }
+
+    /// Returns `true` if the next line starts a variant or ends a select
+    /// expression.
+    fn at_variant_end(&self) -> bool {
+        let rest = self.src[self.pos..].trim_start();
+        rest.starts_with('[') || rest.starts_with("*[") || rest.starts_with('}')
+    }
+
+    /// Parses a pattern up to the end of the source or, in the value of a
+    /// variant, up to the next variant.
+    fn parse_pattern(&mut self, in_variant: bool) -> Result<Pattern, ParseError> {
+        let mut pattern = vec![];
+        let mut text = String::new();
+        while let Some(c) = self.peek() {
+            match c {
+                '{' => {
+                    self.bump();
+                    if !text.is_empty() {
+                        pattern.push(PatternElement::Text(std::mem::take(&mut text)));
+                    }
+                    pattern.push(PatternElement::Placeable(self.parse_placeable()?));
+                }
+                '}' => break,
+                '\n' if in_variant => {
+                    if self.at_variant_end() {
+                        break;
+                    }
+                    self.bump();
+                    text.push('\n');
+                    self.skip_blank_inline();
+                }
+                _ => {
+                    self.bump();
+                    text.push(c);
+                }
+            }
+        }
+        if !text.is_empty() {
+            pattern.push(PatternElement::Text(text));
+        }
+        Ok(pattern)
+    }
+
+    /// Parses a placeable, after its opening brace.
+    fn parse_placeable(&mut self) -> Result<Expression, ParseError> {
+        self.skip_blank();
+        let selector = self.parse_inline_expression()?;
+        self.skip_blank();
+        if !self.src[self.pos..].starts_with("->") {
+            self.expect('}')?;
+            return Ok(Expression::Inline(selector));
+        }
+        self.pos += 2;
+
+        let mut variants = vec![];
+        loop {
+            self.skip_blank();
+            if self.eat('}') {
+                break;
+            }
+            let default = self.eat('*');
+            self.expect('[')?;
+            self.skip_blank_inline();
+            let key = match self.peek() {
+                Some(c) if c == '-' || c.is_ascii_digit() => {
+                    VariantKey::Number(self.parse_number()?)
+                }
+                _ => VariantKey::Identifier(self.parse_identifier()?),
+            };
+            self.skip_blank_inline();
+            self.expect(']')?;
+            self.skip_blank_inline();
+            let mut value = self.parse_pattern(true)?;
+            trim_pattern(&mut value);
+            variants.push(Variant { key, value, default });
+            if self.peek().is_none() {
+                return Err(self.error("expected `}` at the end of the select expression"));
+            }
+        }
+
+        match variants.iter().filter(|v| v.default).count() {
+            1 => Ok(Expression::Select { selector, variants }),
+            0 => Err(self.error("expected a default variant, marked with `*`")),
+            _ => Err(self.error("a select expression can only have one default variant")),
+        }
+    }
+
+    fn parse_inline_expression(&mut self) -> Result<InlineExpression, ParseError> {
+        match self.peek() {
+            Some('$') => {
+                self.bump();
+                Ok(InlineExpression::Variable(self.parse_identifier()?))
+            }
+            Some('"') => {
+                self.bump();
+                self.parse_string_literal().map(InlineExpression::StringLiteral)
+            }
+            Some(c) if c == '-' || c.is_ascii_digit() => {
+                self.parse_number().map(InlineExpression::NumberLiteral)
+            }
+            Some(c) if c.is_ascii_alphabetic() => {
+                let id = self.parse_identifier()?;
+                let attr = if self.eat('.') { Some(self.parse_identifier()?) } else { None };
+                Ok(InlineExpression::MessageReference(id, attr))
+            }
+            _ => Err(self.error("expected a variable, a literal or a message reference")),
+        }
+    }
+
+    fn parse_identifier(&mut self) -> Result<String, ParseError> {
+        let rest = &self.src[self.pos..];
+        let len = identifier_len(rest);
+        if len == 0 {
+            return Err(self.error("expected an identifier"));
+        }
+        self.pos += len;
+        Ok(rest[..len].to_owned())
+    }
+
+    fn parse_number(&mut self) -> Result<String, ParseError> {
+        let start = self.pos;
+        self.eat('-');
+        let digits = |this: &mut Self| {
+            let start = this.pos;
+            while this.peek().map_or(false, |c| c.is_ascii_digit()) {
+                this.bump();
+            }
+            this.pos > start
+        };
+        if !digits(self) || (self.eat('.') && !digits(self)) {
+            return Err(self.error("expected a number"));
+        }
+        Ok(self.src[start..self.pos].to_owned())
+    }
+
+    /// Parses a string literal, after its opening quote.
+    fn parse_string_literal(&mut self) -> Result<String, ParseError> {
+        let mut s = String::new();
+        loop {
+            match self.bump() {
+                Some('"') => return Ok(s),
+                Some('\\') => match self.bump() {
+                    Some('\\') => s.push('\\'),
+                    Some('"') => s.push('"'),
+                    Some('u') => {
+                        let hex = self.src[self.pos..].get(..4).unwrap_or("");
+                        let c = u32::from_str_radix(hex, 16).ok().and_then(char::from_u32);
+                        match c {
+                            Some(c) if hex.len() == 4 => {
+                                s.push(c);
+                                self.pos += 4;
+                            }
+                            _ => return Err(self.error("invalid unicode escape")),
+                        }
+                    }
+                    _ => return Err(self.error("unknown escape in string literal")),
+                },
+                Some('\n') | None => return Err(self.error("unterminated string literal")),
+                Some(c) => s.push(c),
+            }
+        }
+    }
+}
+
+fn is_indented(line: &str) -> bool {
+    line.starts_with(' ')
+}
+
+fn identifier_len(s: &str) -> usize {
+    if !s.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        return 0;
+    }
+    s.find(|c: char| !(c.is_ascii_alphanumeric() || c == '-' || c == '_')).unwrap_or(s.len())
+}
+
+/// Splits `name = value` into the name and the value.
+fn split_definition(line: &str) -> Option<(&str, &str)> {
+    let len = identifier_len(line);
+    let rest = line[len..].trim_start_matches(' ');
+    match rest.strip_prefix('=') {
+        Some(value) if len > 0 => Some((&line[..len], value)),
+        _ => None,
+    }
+}
+
+/// Removes the whitespace at the start and end of the value of a variant.
+fn trim_pattern(pattern: &mut Pattern) {
+    if let Some(PatternElement::Text(text)) = pattern.first_mut() {
+        *text = text.trim_start().to_owned();
+    }
+    if let Some(PatternElement::Text(text)) = pattern.last_mut() {
+        *text = text.trim_end().to_owned();
+    }
+    pattern.retain(|element| !matches!(element, PatternElement::Text(text) if text.is_empty()));
+}
@@ -0,0 +1,204 @@
+use super::*;
+
+use crate::{Applicability, Level};
+use rustc_span::DUMMY_SP;
+
+fn translator(locale: &str, source: &str) -> Translator {
+    let mut catalogue = Catalogue::new(locale);
+    catalogue.add_resource(source).unwrap();
+    Translator { catalogues: vec![catalogue, fallback_catalogue()] }
+}
+
+fn args(args: &[(&str, DiagnosticArgValue)]) -> Vec<DiagnosticArg> {
+    args.iter().map(|(name, value)| (name.to_string(), value.clone())).collect()
+}
+
+fn translate(
+    translator: &Translator,
+    message: &DiagnosticMessage,
+    args: &[DiagnosticArg],
+) -> String {
+    translator.translate_message(message, args).into_owned()
+}
+
+fn num(n: usize) -> DiagnosticArgValue {
+    DiagnosticArgValue::Number(n)
+}
+
+fn s(s: &str) -> DiagnosticArgValue {
+    DiagnosticArgValue::Str(s.to_owned())
+}
+
+#[test]
+fn default_resources_parse() {
+    let translator = Translator::fallback();
+    assert!(translator.catalogues[0].has_message("typeck-sized-unsized-cast", None));
+    assert!(translator.catalogues[0].has_message("typeck-sized-unsized-cast", Some("help")));
+    assert!(!translator.catalogues[0].has_message("typeck-sized-unsized-cast", Some("note")));
+}
+
+#[test]
+fn strings_are_not_translated() {
+    let translator = Translator::fallback();
+    let message = DiagnosticMessage::from("a {$plain} string");
+    assert!(matches!(translator.translate_message(&message, &[]), Cow::Borrowed(_)));
+}
+
+#[test]
+fn variables_and_attributes() {
+    let translator = translator(
+        "en-US",
+        r#"greeting = hello, {$name}!
+    .help = say `{$name}` again
+    .note = {"{"}literal{"}"}
+"#,
+    );
+    let args = args(&[("name", s("world"))]);
+    let message = DiagnosticMessage::fluent("greeting");
+    assert_eq!(translate(&translator, &message, &args), "hello, world!");
+    assert_eq!(translate(&translator, &message.attr("help"), &args), "say `world` again");
+    assert_eq!(translate(&translator, &message.attr("note"), &args), "{literal}");
+}
+
+#[test]
+fn multiline_values() {
+    let translator = translator("en-US", "long =\n    first line\n      indented\n    last line\n");
+    assert_eq!(
+        translate(&translator, &DiagnosticMessage::fluent("long"), &[]),
+        "first line\n  indented\nlast line"
+    );
+}
+
+#[test]
+fn plurals() {
+    let source = "\
+apples = {$count ->
+        [0] no apples
+        [one] one apple
+       *[other] {$count} apples
+    }
+";
+    let en = translator("en-US", source);
+    let message = DiagnosticMessage::fluent("apples");
+    assert_eq!(translate(&en, &message, &args(&[("count", num(0))])), "no apples");
+    assert_eq!(translate(&en, &message, &args(&[("count", num(1))])), "one apple");
+    assert_eq!(translate(&en, &message, &args(&[("count", num(2))])), "2 apples");
+
+    // `one` covers 0 and 1 in French.
+    let source = source.replace("[0] no apples\n", "");
+    let fr = translator("fr", &source);
+    assert_eq!(translate(&fr, &message, &args(&[("count", num(0))])), "one apple");
+    assert_eq!(translate(&fr, &message, &args(&[("count", num(2))])), "2 apples");
+}
+
+#[test]
+fn string_selectors() {
+    let translator = translator(
+        "en-US",
+        "\
+kind = {$kind ->
+        [lifetime] lifetime
+       *[generic] generic
+    } argument
+",
+    );
+    let message = DiagnosticMessage::fluent("kind");
+    assert_eq!(
+        translate(&translator, &message, &args(&[("kind", s("lifetime"))])),
+        "lifetime argument"
+    );
+    assert_eq!(
+        translate(&translator, &message, &args(&[("kind", s("const"))])),
+        "generic argument"
+    );
+    assert_eq!(translate(&translator, &message, &[]), "generic argument");
+}
+
+#[test]
+fn message_references() {
+    let translator = translator(
+        "en-US",
+        "outer = <{inner}|{inner.attr}>\ninner = in {$arg}\n    .attr = attr\ncycle = {cycle}\n",
+    );
+    let args = args(&[("arg", num(3))]);
+    assert_eq!(translate(&translator, &DiagnosticMessage::fluent("outer"), &args), "<in 3|attr>");
+    let cycle = translate(&translator, &DiagnosticMessage::fluent("cycle"), &[]);
+    assert!(cycle.ends_with("{cycle}"));
+}
+
+#[test]
+fn missing_messages_and_arguments() {
+    let translator = translator("en-US", "message = value {$missing}\n");
+    let message = DiagnosticMessage::fluent("message");
+    assert_eq!(translate(&translator, &message, &[]), "value {$missing}");
+    assert_eq!(translate(&translator, &message.attr("nope"), &[]), "{message.nope}");
+    assert_eq!(translate(&translator, &DiagnosticMessage::fluent("nope"), &[]), "{nope}");
+}
+
+#[test]
+fn catalogues_are_searched_in_order() {
+    let translator = translator("fr", "typeck-sized-unsized-cast = impossible de convertir\n");
+    let message = DiagnosticMessage::fluent("typeck-sized-unsized-cast");
+    assert_eq!(translate(&translator, &message, &[]), "impossible de convertir");
+    // Messages which are not translated fall back to English.
+    let help = translate(&translator, &message.attr("help"), &[]);
+    assert!(help.starts_with("Thin pointers are \"simple\" pointers"));
+}
+
+#[test]
+fn duplicate_messages() {
+    let mut catalogue = Catalogue::new("en-US");
+    catalogue.add_resource("a = a\n").unwrap();
+    let errors = catalogue.add_resource("b = b\na = a again\nb = b again\n").unwrap_err();
+    let lines = errors.iter().map(|e| e.line).collect::<Vec<_>>();
+    assert_eq!(lines, [1, 2, 3]);
+    assert!(!catalogue.has_message("b", None));
+}
+
+#[test]
+fn parse_errors() {
+    let cases = [
+        "message = {$unterminated\n",
+        "message = {$sel ->\n        [a] a\n    }\n",
+        "message = {$sel ->\n       *[a] a\n       *[b] b\n    }\n",
+        "message = {\"unterminated}\n",
+        "  indented = at the start\n",
+        "not a message\n",
+    ];
+    for source in &cases {
+        let errors = Catalogue::new("en-US").add_resource(source);
+        assert!(errors.is_err(), "`{}` should not parse", source);
+    }
+}
+
+#[test]
+fn translate_diagnostic() {
+    let translator = translator(
+        "en-US",
+        "msg = main {$n}\n    .label = label {$n}\n    .sugg = sugg {$n}\n    .note = note {$n}\n",
+    );
+    let message = DiagnosticMessage::fluent("msg");
+
+    let mut diagnostic = Diagnostic::new(Level::Error, "untranslated");
+    assert!(matches!(translator.translate_diagnostic(&diagnostic), Cow::Borrowed(_)));
+
+    diagnostic.set_primary_message(message.clone());
+    diagnostic.set_arg("n", 1usize);
+    diagnostic.set_span(DUMMY_SP);
+    diagnostic.span_label(DUMMY_SP, message.attr("label"));
+    diagnostic.note(message.attr("note"));
+    diagnostic.span_suggestion(
+        DUMMY_SP,
+        message.attr("sugg"),
+        String::new(),
+        Applicability::Unspecified,
+    );
+
+    let translated = translator.translate_diagnostic(&diagnostic);
+    assert_eq!(translated.message(), "main 1");
+    let labels = translated.span.span_labels();
+    assert_eq!(labels[0].label.as_deref(), Some("label 1"));
+    assert_eq!(translated.children[0].message(), "note 1");
+    assert_eq!(translated.suggestions[0].msg.as_str(), Some("sugg 1"));
+    assert_eq!(translated.args, diagnostic.args);
+}
@@ -77,8 +77,9 @@ fn emit_frag_parse_err(
         e.message[0] = (
             format!(
                 "macro expansion ends with an incomplete expression: {}",
-                msg.0.replace(", found `<eof>`", ""),
-            ),
+                msg.0.as_str_or_identifier().replace(", found `<eof>`", ""),
+            )
+            .into(),
             msg.1,
         );
     }
@@ -668,6 +668,13 @@ pub fn set(&self, features: &mut Features, span: Span) {
     /// Allows specifying the as-needed link modifier
     (active, native_link_modifiers_as_needed, "1.53.0", Some(81490), None),
 
+    // no-tracking-issue-start
+
+    /// Allows `#[ignore(cfg(predicate), reason = "...")]` on tests.
+    (active, test_ignore_cfg, "1.54.0", None, None),
+
+    // no-tracking-issue-end
+
     // -------------------------------------------------------------------------
     // feature-group-end: actual feature gates
     // -------------------------------------------------------------------------
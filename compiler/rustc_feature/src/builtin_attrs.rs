@@ -179,7 +179,10 @@ macro_rules! experimental {
     ungated!(cfg_attr, Normal, template!(List: "predicate, attr1, attr2, ...")),
 
     // Testing:
-    ungated!(ignore, Normal, template!(Word, NameValueStr: "reason")),
+    ungated!(
+        ignore, Normal,
+        template!(Word, List: r#"cfg(predicate), reason = "...""#, NameValueStr: "reason"),
+    ),
     ungated!(
         should_panic, Normal,
         template!(Word, List: r#"expected = "reason"#, NameValueStr: "reason"),
@@ -529,7 +532,8 @@ macro_rules! experimental {
                  for reserving for `for<T> From<!> for T` impl"
     ),
     rustc_attr!(
-        rustc_test_marker, Normal, template!(Word),
+        rustc_test_marker, Normal,
+        template!(Word, List: r#"name = "...", module_path = "...", ignore, should_panic"#),
         "the `#[rustc_test_marker]` attribute is used internally to track tests",
     ),
     rustc_attr!(
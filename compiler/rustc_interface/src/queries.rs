@@ -3,6 +3,7 @@
 
 use rustc_ast as ast;
 use rustc_codegen_ssa::traits::CodegenBackend;
+use rustc_data_structures::profiling::set_time_passes_crate_name;
 use rustc_data_structures::steal::Steal;
 use rustc_data_structures::svh::Svh;
 use rustc_data_structures::sync::{Lrc, OnceCell, WorkerLocal};
@@ -167,7 +168,10 @@ pub fn crate_name(&self) -> Result<&Query<String>> {
                 let parse_result = self.parse()?;
                 let krate = parse_result.peek();
                 // parse `#[crate_name]` even if `--crate-name` was passed, to make sure it matches.
-                find_crate_name(self.session(), &krate.attrs, &self.compiler.input)
+                let crate_name =
+                    find_crate_name(self.session(), &krate.attrs, &self.compiler.input);
+                set_time_passes_crate_name(Some(crate_name.clone()));
+                crate_name
             })
         })
     }
@@ -1,7 +1,9 @@
 use crate::interface::parse_cfgspecs;
 
 use rustc_data_structures::fx::FxHashSet;
-use rustc_errors::{emitter::HumanReadableErrorType, registry, ColorConfig};
+use rustc_data_structures::profiling::TimePassesFormat;
+use rustc_errors::emitter::{HumanReadableErrorType, TerminalUrl};
+use rustc_errors::{registry, ColorConfig};
 use rustc_session::config::InstrumentCoverage;
 use rustc_session::config::Strip;
 use rustc_session::config::{build_configuration, build_session_options, to_crate_config};
@@ -623,6 +625,7 @@ macro_rules! untracked {
     untracked!(dump_mir_graphviz, true);
     untracked!(emit_future_incompat_report, true);
     untracked!(emit_stack_sizes, true);
+    untracked!(error_output_file, Some((PathBuf::from("errors.json"), ErrorOutputType::Sarif)));
     untracked!(hir_stats, true);
     untracked!(identify_regions, true);
     untracked!(incremental_ignore_spans, true);
@@ -657,12 +660,16 @@ macro_rules! untracked {
     untracked!(span_debug, true);
     untracked!(span_free_formats, true);
     untracked!(strip, Strip::Debuginfo);
+    untracked!(terminal_urls, TerminalUrl::Yes);
     untracked!(terminal_width, Some(80));
     untracked!(threads, 99);
     untracked!(time, true);
     untracked!(time_llvm_passes, true);
     untracked!(time_passes, true);
+    untracked!(time_passes_format, TimePassesFormat::Json);
     untracked!(trace_macros, true);
+    untracked!(translate_additional_ftl, Some(PathBuf::from("custom.ftl")));
+    untracked!(translate_lang, Some(String::from("fr")));
     untracked!(trim_diagnostic_paths, false);
     untracked!(ui_testing, true);
     untracked!(unpretty, Some("expanded".to_string()));
@@ -692,6 +699,7 @@ macro_rules! tracked {
     tracked!(debug_macros, true);
     tracked!(dep_info_omit_d_target, true);
     tracked!(dual_proc_macros, true);
+    tracked!(explain_lint_levels, true);
     tracked!(fewer_names, Some(true));
     tracked!(force_overflow_checks, Some(true));
     tracked!(force_unstable_if_unmarked, true);
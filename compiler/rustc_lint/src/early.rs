@@ -85,11 +85,21 @@ fn visit_param(&mut self, param: &'a ast::Param) {
     }
 
     fn visit_item(&mut self, it: &'a ast::Item) {
+        // Out-of-line modules may be in a file with overrides in the lint config.
+        let config_push = match it.kind {
+            ast::ItemKind::Mod(_, ast::ModKind::Loaded(_, ast::Inline::No, inner_span)) => {
+                Some(self.context.builder.push_lint_config(inner_span))
+            }
+            _ => None,
+        };
         self.with_lint_attrs(it.id, &it.attrs, |cx| {
             run_early_pass!(cx, check_item, it);
             ast_visit::walk_item(cx, it);
             run_early_pass!(cx, check_item_post, it);
-        })
+        });
+        if let Some(config_push) = config_push {
+            self.context.builder.pop(config_push);
+        }
     }
 
     fn visit_foreign_item(&mut self, it: &'a ast::ForeignItem) {
@@ -331,6 +341,7 @@ fn early_lint_crate<T: EarlyLintPass>(
     };
 
     // Visit the whole crate.
+    let config_push = cx.context.builder.push_lint_config(krate.span);
     cx.with_lint_attrs(ast::CRATE_NODE_ID, &krate.attrs, |cx| {
         // since the root module isn't visited as an item (because it isn't an
         // item), warn for it here.
@@ -340,6 +351,7 @@ fn early_lint_crate<T: EarlyLintPass>(
 
         run_early_pass!(cx, check_crate_post, krate);
     });
+    cx.context.builder.pop(config_push);
     cx.context.buffered
 }
 
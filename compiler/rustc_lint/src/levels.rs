@@ -1,9 +1,11 @@
 use crate::context::{CheckLintNameResult, LintStore};
 use crate::late::unerased_lint_store;
+use crate::lint_config::{LintConfig, LintConfigEntry};
 use rustc_ast as ast;
 use rustc_ast::unwrap_or;
 use rustc_ast_pretty::pprust;
 use rustc_data_structures::fx::FxHashMap;
+use rustc_data_structures::sync::Lrc;
 use rustc_errors::{struct_span_err, Applicability, DiagnosticBuilder};
 use rustc_hir as hir;
 use rustc_hir::def_id::{CrateNum, DefId, CRATE_DEF_INDEX, LOCAL_CRATE};
@@ -23,7 +25,7 @@
 use rustc_session::parse::feature_err;
 use rustc_session::Session;
 use rustc_span::symbol::{sym, Symbol};
-use rustc_span::{source_map::MultiSpan, Span, DUMMY_SP};
+use rustc_span::{source_map::MultiSpan, FileName, Span, DUMMY_SP};
 use tracing::debug;
 
 use std::cmp;
@@ -38,6 +40,7 @@ fn lint_levels(tcx: TyCtxt<'_>, cnum: CrateNum) -> LintLevelMap {
 
     builder.levels.id_to_set.reserve(krate.exported_macros.len() + 1);
 
+    let config_push = builder.levels.push_lint_config(krate.item.inner);
     let push = builder.levels.push(tcx.hir().attrs(hir::CRATE_HIR_ID), &store, true);
     builder.levels.register_id(hir::CRATE_HIR_ID);
     for macro_def in krate.exported_macros {
@@ -45,6 +48,7 @@ fn lint_levels(tcx: TyCtxt<'_>, cnum: CrateNum) -> LintLevelMap {
     }
     intravisit::walk_crate(&mut builder, krate);
     builder.levels.pop(push);
+    builder.levels.pop(config_push);
 
     builder.levels.build_map()
 }
@@ -57,6 +61,7 @@ pub struct LintLevelsBuilder<'s> {
     warn_about_weird_lints: bool,
     store: &'s LintStore,
     crate_attrs: &'s [ast::Attribute],
+    lint_config: Option<Lrc<LintConfig>>,
 }
 
 pub struct BuilderPush {
@@ -79,9 +84,18 @@ pub fn new(
             warn_about_weird_lints,
             store,
             crate_attrs,
+            lint_config: sess
+                .opts
+                .lint_config
+                .as_ref()
+                .and_then(|path| LintConfig::load(sess, path))
+                .map(Lrc::new),
         };
         builder.process_command_line(sess, store);
         assert_eq!(builder.sets.list.len(), 1);
+        if warn_about_weird_lints {
+            builder.check_lint_config_names();
+        }
         builder
     }
 
@@ -89,6 +103,23 @@ fn process_command_line(&mut self, sess: &Session, store: &LintStore) {
         let mut specs = FxHashMap::default();
         self.sets.lint_cap = sess.opts.lint_cap.unwrap_or(Level::Forbid);
 
+        // The levels from the lint config come first, so that lint flags can
+        // override them.
+        if let Some(lint_config) = &self.lint_config {
+            for entry in &lint_config.lints {
+                let level = cmp::min(entry.level, self.sets.lint_cap);
+                let src = LintLevelSource::LintConfig(
+                    Symbol::intern(&entry.name),
+                    entry.span,
+                    entry.level,
+                );
+                for &id in self.lint_config_ids(entry) {
+                    self.check_gated_lint(id, entry.span);
+                    specs.insert(id, (level, src));
+                }
+            }
+        }
+
         for &(ref lint_name, level) in &sess.opts.lint_opts {
             store.check_lint_name_cmdline(sess, &lint_name, level);
             let orig_level = level;
@@ -142,8 +173,9 @@ fn insert_spec(
                 let id_name = id.lint.name_lower();
                 let fcw_warning = match old_src {
                     LintLevelSource::Default => false,
-                    LintLevelSource::Node(symbol, _, _) => self.store.is_lint_group(symbol),
+                    LintLevelSource::Node(symbol, _, _, _) => self.store.is_lint_group(symbol),
                     LintLevelSource::CommandLine(symbol, _) => self.store.is_lint_group(symbol),
+                    LintLevelSource::LintConfig(symbol, _, _) => self.store.is_lint_group(symbol),
                 };
                 debug!(
                     "fcw_warning={:?}, specs.get(&id) = {:?}, old_src={:?}, id_name={:?}",
@@ -159,7 +191,7 @@ fn insert_spec(
                                 id.to_string()
                             ));
                         }
-                        LintLevelSource::Node(_, forbid_source_span, reason) => {
+                        LintLevelSource::Node(_, forbid_source_span, reason, _) => {
                             diag_builder.span_label(forbid_source_span, "`forbid` level set here");
                             if let Some(rationale) = reason {
                                 diag_builder.note(&rationale.as_str());
@@ -168,6 +200,9 @@ fn insert_spec(
                         LintLevelSource::CommandLine(_, _) => {
                             diag_builder.note("`forbid` lint level was set on command line");
                         }
+                        LintLevelSource::LintConfig(_, forbid_source_span, _) => {
+                            diag_builder.span_label(forbid_source_span, "`forbid` level set here");
+                        }
                     }
                     diag_builder.emit();
                 };
@@ -341,6 +376,7 @@ pub(crate) fn push(
                             meta_item.path.segments.last().expect("empty lint name").ident.name,
                             sp,
                             reason,
+                            level,
                         );
                         for &id in *ids {
                             self.check_gated_lint(id, attr.span);
@@ -356,6 +392,7 @@ pub(crate) fn push(
                                     Symbol::intern(complete_name),
                                     sp,
                                     reason,
+                                    level,
                                 );
                                 for id in ids {
                                     self.insert_spec(&mut specs, *id, (level, src));
@@ -392,6 +429,7 @@ pub(crate) fn push(
                                     Symbol::intern(&new_lint_name),
                                     sp,
                                     reason,
+                                    level,
                                 );
                                 for id in ids {
                                     self.insert_spec(&mut specs, *id, (level, src));
@@ -462,7 +500,8 @@ pub(crate) fn push(
                     // Ignore any errors or warnings that happen because the new name is inaccurate
                     // NOTE: `new_name` already includes the tool name, so we don't have to add it again.
                     if let CheckLintNameResult::Ok(ids) = store.check_lint_name(&new_name, None) {
-                        let src = LintLevelSource::Node(Symbol::intern(&new_name), sp, reason);
+                        let src =
+                            LintLevelSource::Node(Symbol::intern(&new_name), sp, reason, level);
                         for &id in ids {
                             self.check_gated_lint(id, attr.span);
                             self.insert_spec(&mut specs, id, (level, src));
@@ -481,7 +520,7 @@ pub(crate) fn push(
                 }
 
                 let (lint_attr_name, lint_attr_span) = match *src {
-                    LintLevelSource::Node(name, span, _) => (name, span),
+                    LintLevelSource::Node(name, span, _, _) => (name, span),
                     _ => continue,
                 };
 
@@ -508,6 +547,47 @@ pub(crate) fn push(
             }
         }
 
+        self.push_specs(specs)
+    }
+
+    /// Pushes the levels of the overrides in the lint config which apply to
+    /// the file containing `module_span`, like `push` does for attributes.
+    ///
+    /// This should be called when entering the crate root and out-of-line
+    /// modules, before pushing their attributes, so that attributes take
+    /// precedence over the lint config. Don't forget to call `pop`!
+    pub(crate) fn push_lint_config(&mut self, module_span: Span) -> BuilderPush {
+        let mut specs = FxHashMap::default();
+        let lint_config = match self.lint_config.clone() {
+            Some(lint_config) if !lint_config.overrides.is_empty() => lint_config,
+            _ => return self.push_specs(specs),
+        };
+        let file = self.sess.source_map().lookup_source_file(module_span.lo());
+        let path = match &file.name {
+            FileName::Real(name) => name
+                .local_path()
+                .map(|path| self.sess.working_dir.local_path_if_available().join(path)),
+            _ => None,
+        };
+        if let Some(path) = path {
+            for lint_override in lint_config.overrides.iter().filter(|o| o.applies_to(&path)) {
+                for entry in &lint_override.lints {
+                    let src = LintLevelSource::LintConfig(
+                        Symbol::intern(&entry.name),
+                        entry.span,
+                        entry.level,
+                    );
+                    for &id in self.lint_config_ids(entry) {
+                        self.check_gated_lint(id, entry.span);
+                        self.insert_spec(&mut specs, id, (entry.level, src));
+                    }
+                }
+            }
+        }
+        self.push_specs(specs)
+    }
+
+    fn push_specs(&mut self, specs: FxHashMap<LintId, LevelAndSource>) -> BuilderPush {
         let prev = self.cur;
         if !specs.is_empty() {
             self.cur = self.sets.list.len() as u32;
@@ -517,6 +597,64 @@ pub(crate) fn push(
         BuilderPush { prev, changed: prev != self.cur }
     }
 
+    /// Returns the lints set by an entry of the lint config, following
+    /// renames. Unknown lints are reported by `check_lint_config_names`.
+    fn lint_config_ids(&self, entry: &LintConfigEntry) -> &'s [LintId] {
+        let (tool_name, lint_name) = split_tool_name(&entry.name);
+        match self.store.check_lint_name(lint_name, tool_name) {
+            CheckLintNameResult::Ok(ids) => ids,
+            CheckLintNameResult::Tool(Ok(ids)) => ids,
+            CheckLintNameResult::Tool(Err((Some(ids), _))) => ids,
+            CheckLintNameResult::Warning(_, Some(new_name)) => {
+                match self.store.check_lint_name(&new_name, None) {
+                    CheckLintNameResult::Ok(ids) => ids,
+                    _ => &[],
+                }
+            }
+            _ => &[],
+        }
+    }
+
+    /// Reports the unknown, renamed and removed lints in the lint config, at
+    /// the levels set by the command line and the lint config.
+    fn check_lint_config_names(&self) {
+        let lint_config = match &self.lint_config {
+            Some(lint_config) => lint_config,
+            None => return,
+        };
+        let overrides = lint_config.overrides.iter().flat_map(|o| &o.lints);
+        for entry in lint_config.lints.iter().chain(overrides) {
+            let (tool_name, lint_name) = split_tool_name(&entry.name);
+            let span = Some(entry.span.into());
+            match self.store.check_lint_name(lint_name, tool_name) {
+                CheckLintNameResult::Warning(msg, _) => {
+                    self.struct_lint(builtin::RENAMED_AND_REMOVED_LINTS, span, |lint| {
+                        lint.build(&msg).emit()
+                    });
+                }
+                CheckLintNameResult::Tool(Err((Some(_), new_name))) => {
+                    self.struct_lint(builtin::RENAMED_AND_REMOVED_LINTS, span, |lint| {
+                        let msg = format!(
+                            "lint name `{}` is deprecated and may not have an effect in the future",
+                            entry.name
+                        );
+                        lint.build(&msg).help(&format!("use `{}` instead", new_name)).emit();
+                    });
+                }
+                CheckLintNameResult::NoLint(suggestion) => {
+                    self.struct_lint(builtin::UNKNOWN_LINTS, span, |lint| {
+                        let mut db = lint.build(&format!("unknown lint: `{}`", entry.name));
+                        if let Some(suggestion) = suggestion {
+                            db.help(&format!("did you mean: `{}`", suggestion));
+                        }
+                        db.emit();
+                    });
+                }
+                _ => {}
+            }
+        }
+    }
+
     /// Checks if the lint is gated on a feature that is not enabled.
     fn check_gated_lint(&self, lint_id: LintId, span: Span) {
         if let Some(feature) = lint_id.lint.feature_gate {
@@ -565,6 +703,14 @@ pub fn build_map(self) -> LintLevelMap {
     }
 }
 
+/// Splits the tool name off a lint name like `clippy::pedantic`.
+fn split_tool_name(name: &str) -> (Option<Symbol>, &str) {
+    match name.split_once("::") {
+        Some((tool_name, lint_name)) => (Some(Symbol::intern(tool_name)), lint_name),
+        None => (None, name),
+    }
+}
+
 fn is_known_lint_tool(m_item: Symbol, sess: &Session, attrs: &[ast::Attribute]) -> bool {
     if [sym::clippy, sym::rustc, sym::rustdoc].contains(&m_item) {
         return true;
@@ -615,9 +761,24 @@ fn visit_param(&mut self, param: &'tcx hir::Param<'tcx>) {
     }
 
     fn visit_item(&mut self, it: &'tcx hir::Item<'tcx>) {
+        // Out-of-line modules may be in a file with overrides in the lint config.
+        let config_push = match it.kind {
+            hir::ItemKind::Mod(ref module) => {
+                let sm = self.tcx.sess.source_map();
+                let out_of_line = sm.span_to_filename(it.span) != sm.span_to_filename(module.inner);
+                out_of_line.then(|| self.levels.push_lint_config(module.inner))
+            }
+            _ => None,
+        };
+        if config_push.as_ref().map_or(false, |push| push.changed) {
+            self.levels.register_id(it.hir_id());
+        }
         self.with_lint_attrs(it.hir_id(), |builder| {
             intravisit::walk_item(builder, it);
         });
+        if let Some(config_push) = config_push {
+            self.levels.pop(config_push);
+        }
     }
 
     fn visit_foreign_item(&mut self, it: &'tcx hir::ForeignItem<'tcx>) {
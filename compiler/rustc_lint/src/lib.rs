@@ -54,6 +54,7 @@
 mod internal;
 mod late;
 mod levels;
+mod lint_config;
 mod methods;
 mod non_ascii_idents;
 mod non_fmt_panic;
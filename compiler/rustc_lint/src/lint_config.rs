@@ -0,0 +1,495 @@
+//! Lint levels read from the file given with `--lint-config`.
+//!
+//! The file is written in a subset of [TOML]: tables, arrays of tables, keys,
+//! strings, arrays and inline tables. The `[lints]` table sets lint levels for
+//! the whole crate, and each entry of `[[overrides]]` sets lint levels for the
+//! modules loaded from files under any of its `paths`:
+//!
+//! ```toml
+//! [lints]
+//! unused = "deny"
+//! "clippy::pedantic" = "warn"
+//!
+//! [[overrides]]
+//! paths = ["src/generated"]
+//! lints = { dead_code = "allow", unused = "allow" }
+//! ```
+//!
+//! Relative paths are relative to the directory of the config file. Within a
+//! table, later entries take precedence over earlier ones, like lint flags on
+//! the command line. The levels of `[lints]` can be overridden by lint flags,
+//! and the levels of `[[overrides]]` by lint attributes.
+//!
+//! The file is loaded into the source map, so that the lint levels it sets
+//! have a span pointing at their entry.
+//!
+//! [TOML]: https://toml.io
+
+use rustc_session::lint::Level;
+use rustc_session::Session;
+use rustc_span::{BytePos, Span, DUMMY_SP};
+
+use std::path::{Path, PathBuf};
+
+#[cfg(test)]
+mod tests;
+
+/// The lint levels of a `--lint-config` file.
+#[derive(Debug, Default)]
+pub struct LintConfig {
+    /// The lint levels for the whole crate.
+    pub lints: Vec<LintConfigEntry>,
+    pub overrides: Vec<LintConfigOverride>,
+}
+
+/// The level of a lint or lint group, as set in the config file.
+#[derive(Debug)]
+pub struct LintConfigEntry {
+    /// The name of the lint, with dashes replaced by underscores.
+    pub name: String,
+    pub level: Level,
+    /// The span of the whole `name = "level"` entry.
+    pub span: Span,
+}
+
+/// Lint levels for the modules loaded from the files under some paths.
+#[derive(Debug)]
+pub struct LintConfigOverride {
+    pub paths: Vec<PathBuf>,
+    pub lints: Vec<LintConfigEntry>,
+}
+
+impl LintConfigOverride {
+    /// Whether this override applies to the module loaded from `file`.
+    pub fn applies_to(&self, file: &Path) -> bool {
+        self.paths.iter().any(|path| file.starts_with(path))
+    }
+}
+
+/// An error in a lint config file.
+#[derive(Debug)]
+pub struct ParseError {
+    pub span: Span,
+    pub message: String,
+}
+
+impl LintConfig {
+    /// Loads the config file at `path` into the source map and parses it.
+    /// Errors are reported, and `None` returned.
+    pub fn load(sess: &Session, path: &Path) -> Option<LintConfig> {
+        let file = match sess.source_map().load_file(path) {
+            Ok(file) => file,
+            Err(error) => {
+                sess.err(&format!("failed to read lint config `{}`: {}", path.display(), error));
+                return None;
+            }
+        };
+        let src = file.src.as_ref().expect("lint config loaded without its source");
+        let mut base_dir = sess.working_dir.local_path_if_available().join(path);
+        base_dir.pop();
+        match LintConfig::parse(src, file.start_pos, &base_dir) {
+            Ok(config) => Some(config),
+            Err(error) => {
+                sess.span_err(error.span, &error.message);
+                None
+            }
+        }
+    }
+
+    /// Parses a config file, whose text starts at `start_pos` in the source
+    /// map. Relative paths are resolved against `base_dir`.
+    pub fn parse(src: &str, start_pos: BytePos, base_dir: &Path) -> Result<LintConfig, ParseError> {
+        let root = Parser { src, pos: 0, start_pos }.parse_document()?;
+        let mut config = LintConfig::default();
+        for entry in root.entries {
+            match (&*entry.key, entry.value) {
+                ("lints", Value::Table(table)) => config.lints = lint_entries(table)?,
+                ("overrides", Value::Tables(tables)) => {
+                    for table in tables {
+                        config.overrides.push(lint_override(table, base_dir)?);
+                    }
+                }
+                ("lints", _) => return Err(expected(entry.value_span, "`lints` to be a table")),
+                ("overrides", _) => {
+                    return Err(expected(entry.value_span, "`overrides` to be an array of tables"));
+                }
+                (key, _) => return Err(unknown_key(entry.span, key)),
+            }
+        }
+        Ok(config)
+    }
+}
+
+fn lint_entries(table: Table) -> Result<Vec<LintConfigEntry>, ParseError> {
+    let mut lints = Vec::with_capacity(table.entries.len());
+    for entry in table.entries {
+        let level = match entry.value {
+            Value::String(level) => Level::from_str(&level).ok_or_else(|| ParseError {
+                span: entry.value_span,
+                message: format!(
+                    "invalid lint level `{}`, expected one of `allow`, `warn`, `deny` or `forbid`",
+                    level
+                ),
+            })?,
+            _ => return Err(expected(entry.value_span, "a lint level")),
+        };
+        let name = entry.key.replace("-", "_");
+        lints.push(LintConfigEntry { name, level, span: entry.span });
+    }
+    Ok(lints)
+}
+
+fn lint_override(table: Table, base_dir: &Path) -> Result<LintConfigOverride, ParseError> {
+    let mut paths = None;
+    let mut lints = Vec::new();
+    for entry in table.entries {
+        match (&*entry.key, entry.value) {
+            ("paths", Value::Array(values)) => {
+                let mut resolved = Vec::with_capacity(values.len());
+                for (value, span) in values {
+                    match value {
+                        Value::String(path) => resolved.push(base_dir.join(path)),
+                        _ => return Err(expected(span, "a path")),
+                    }
+                }
+                paths = Some(resolved);
+            }
+            ("lints", Value::Table(table)) => lints = lint_entries(table)?,
+            ("paths", _) => return Err(expected(entry.value_span, "`paths` to be an array")),
+            ("lints", _) => return Err(expected(entry.value_span, "`lints` to be a table")),
+            (key, _) => return Err(unknown_key(entry.span, key)),
+        }
+    }
+    match paths {
+        Some(paths) => Ok(LintConfigOverride { paths, lints }),
+        None => Err(ParseError { span: table.span, message: "override without `paths`".into() }),
+    }
+}
+
+fn expected(span: Span, what: &str) -> ParseError {
+    ParseError { span, message: format!("expected {}", what) }
+}
+
+fn unknown_key(span: Span, key: &str) -> ParseError {
+    ParseError { span, message: format!("unknown key `{}` in lint config", key) }
+}
+
+/// A table, with its entries in the order they were defined.
+#[derive(Debug)]
+struct Table {
+    /// The span of the header of the table, or `DUMMY_SP` if it has none.
+    span: Span,
+    entries: Vec<Entry>,
+}
+
+#[derive(Debug)]
+struct Entry {
+    key: String,
+    /// The span of the whole `key = value`, or of the header of the table.
+    span: Span,
+    value: Value,
+    value_span: Span,
+}
+
+#[derive(Debug)]
+enum Value {
+    String(String),
+    Array(Vec<(Value, Span)>),
+    Table(Table),
+    /// An array of tables, defined by `[[key]]` headers.
+    Tables(Vec<Table>),
+}
+
+impl Table {
+    fn new(span: Span) -> Table {
+        Table { span, entries: Vec::new() }
+    }
+
+    fn get(&mut self, key: &str) -> Option<&mut Entry> {
+        self.entries.iter_mut().find(|entry| entry.key == key)
+    }
+
+    /// Returns the table at `path` below this one, creating it if needed.
+    /// Arrays of tables are followed to their last table.
+    fn descend(&mut self, path: &[(String, Span)]) -> Result<&mut Table, ParseError> {
+        let mut table = self;
+        for (key, span) in path {
+            if table.get(key).is_none() {
+                let value = Value::Table(Table::new(DUMMY_SP));
+                table.entries.push(Entry {
+                    key: key.clone(),
+                    span: *span,
+                    value,
+                    value_span: *span,
+                });
+            }
+            table = match &mut table.get(key).unwrap().value {
+                Value::Table(table) => table,
+                Value::Tables(tables) => tables.last_mut().unwrap(),
+                _ => return Err(expected(*span, &format!("`{}` to be a table", key))),
+            };
+        }
+        Ok(table)
+    }
+
+    fn insert(
+        &mut self,
+        key: &[(String, Span)],
+        value: Value,
+        span: Span,
+        value_span: Span,
+    ) -> Result<(), ParseError> {
+        let ((key, key_span), parents) = key.split_last().unwrap();
+        let table = self.descend(parents)?;
+        if table.get(key).is_some() {
+            return Err(ParseError {
+                span: *key_span,
+                message: format!("duplicate key `{}`", key),
+            });
+        }
+        table.entries.push(Entry { key: key.clone(), span, value, value_span });
+        Ok(())
+    }
+}
+
+struct Parser<'a> {
+    src: &'a str,
+    pos: usize,
+    start_pos: BytePos,
+}
+
+impl Parser<'_> {
+    fn parse_document(&mut self) -> Result<Table, ParseError> {
+        let mut root = Table::new(DUMMY_SP);
+        let mut current = Vec::new();
+        loop {
+            self.skip_trivia();
+            let lo = self.pos;
+            match self.peek() {
+                None => return Ok(root),
+                Some('[') => {
+                    self.pos += 1;
+                    let is_array = self.eat('[');
+                    let path = self.parse_key()?;
+                    if !self.eat(']') || (is_array && !self.eat(']')) {
+                        return Err(self.error(self.pos, "expected `]`"));
+                    }
+                    let span = self.span(lo, self.pos);
+                    self.expect_line_end()?;
+                    let ((key, _), parents) = path.split_last().unwrap();
+                    let parent = root.descend(parents)?;
+                    match parent.get(key) {
+                        None => {
+                            let value = if is_array {
+                                Value::Tables(vec![Table::new(span)])
+                            } else {
+                                Value::Table(Table::new(span))
+                            };
+                            let key = key.clone();
+                            parent.entries.push(Entry { key, span, value, value_span: span });
+                        }
+                        Some(Entry { value: Value::Tables(tables), .. }) if is_array => {
+                            tables.push(Table::new(span));
+                        }
+                        // Tables which were only created implicitly can still
+                        // be defined once.
+                        Some(Entry { value: Value::Table(table), .. })
+                            if !is_array && table.span == DUMMY_SP =>
+                        {
+                            table.span = span;
+                        }
+                        Some(_) => {
+                            return Err(ParseError {
+                                span,
+                                message: format!("duplicate key `{}`", key),
+                            });
+                        }
+                    }
+                    current = path;
+                }
+                Some(_) => {
+                    let key = self.parse_key()?;
+                    if !self.eat('=') {
+                        return Err(self.error(self.pos, "expected `=`"));
+                    }
+                    self.skip_whitespace();
+                    let (value, value_span) = self.parse_value()?;
+                    let span = self.span(lo, self.pos);
+                    self.expect_line_end()?;
+                    root.descend(&current)?.insert(&key, value, span, value_span)?;
+                }
+            }
+        }
+    }
+
+    /// Parses a dotted key, and the whitespace after it.
+    fn parse_key(&mut self) -> Result<Vec<(String, Span)>, ParseError> {
+        let mut key = Vec::new();
+        loop {
+            self.skip_whitespace();
+            let lo = self.pos;
+            let segment = match self.peek() {
+                Some('"') | Some('\'') => self.parse_string()?,
+                _ => {
+                    let len = self.src[lo..]
+                        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '-'))
+                        .unwrap_or(self.src.len() - lo);
+                    if len == 0 {
+                        return Err(self.error(lo, "expected a key"));
+                    }
+                    self.pos += len;
+                    self.src[lo..self.pos].to_string()
+                }
+            };
+            key.push((segment, self.span(lo, self.pos)));
+            self.skip_whitespace();
+            if !self.eat('.') {
+                return Ok(key);
+            }
+        }
+    }
+
+    fn parse_value(&mut self) -> Result<(Value, Span), ParseError> {
+        let lo = self.pos;
+        let value = match self.peek() {
+            Some('"') | Some('\'') => Value::String(self.parse_string()?),
+            Some('[') => {
+                self.pos += 1;
+                let mut values = Vec::new();
+                loop {
+                    self.skip_trivia();
+                    if self.eat(']') {
+                        break;
+                    }
+                    values.push(self.parse_value()?);
+                    self.skip_trivia();
+                    if !self.eat(',') {
+                        self.skip_trivia();
+                        if !self.eat(']') {
+                            return Err(self.error(self.pos, "expected `,` or `]`"));
+                        }
+                        break;
+                    }
+                }
+                Value::Array(values)
+            }
+            Some('{') => {
+                self.pos += 1;
+                let mut table = Table::new(DUMMY_SP);
+                self.skip_whitespace();
+                if !self.eat('}') {
+                    loop {
+                        let entry_lo = self.pos;
+                        let key = self.parse_key()?;
+                        if !self.eat('=') {
+                            return Err(self.error(self.pos, "expected `=`"));
+                        }
+                        self.skip_whitespace();
+                        let (value, value_span) = self.parse_value()?;
+                        let span = self.span(entry_lo, self.pos);
+                        table.insert(&key, value, span, value_span)?;
+                        self.skip_whitespace();
+                        if self.eat('}') {
+                            break;
+                        }
+                        if !self.eat(',') {
+                            return Err(self.error(self.pos, "expected `,` or `}`"));
+                        }
+                        self.skip_whitespace();
+                    }
+                }
+                Value::Table(table)
+            }
+            _ => return Err(self.error(lo, "expected a string, an array or an inline table")),
+        };
+        Ok((value, self.span(lo, self.pos)))
+    }
+
+    /// Parses a basic (`"..."`) or literal (`'...'`) string.
+    fn parse_string(&mut self) -> Result<String, ParseError> {
+        let lo = self.pos;
+        let quote = self.peek().unwrap();
+        self.pos += 1;
+        let mut string = String::new();
+        loop {
+            let c = match self.peek() {
+                None | Some('\n') => return Err(self.error(lo, "unterminated string")),
+                Some(c) => c,
+            };
+            self.pos += c.len_utf8();
+            if c == quote {
+                return Ok(string);
+            }
+            if c == '\\' && quote == '"' {
+                let escaped = match self.peek() {
+                    Some('"') => '"',
+                    Some('\\') => '\\',
+                    Some('n') => '\n',
+                    Some('r') => '\r',
+                    Some('t') => '\t',
+                    _ => return Err(self.error(self.pos - 1, "unknown escape sequence")),
+                };
+                self.pos += 1;
+                string.push(escaped);
+            } else {
+                string.push(c);
+            }
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.src[self.pos..].chars().next()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        let eaten = self.peek() == Some(c);
+        if eaten {
+            self.pos += c.len_utf8();
+        }
+        eaten
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.eat(' ') || self.eat('\t') {}
+    }
+
+    fn skip_comment(&mut self) {
+        if self.peek() == Some('#') {
+            self.pos = self.src[self.pos..].find('\n').map_or(self.src.len(), |i| self.pos + i);
+        }
+    }
+
+    /// Skips whitespace, comments and newlines.
+    fn skip_trivia(&mut self) {
+        loop {
+            self.skip_whitespace();
+            self.skip_comment();
+            if !self.eat('\n') && !self.eat('\r') {
+                return;
+            }
+        }
+    }
+
+    /// Expects the end of the line, after whitespace and an optional comment.
+    fn expect_line_end(&mut self) -> Result<(), ParseError> {
+        self.skip_whitespace();
+        self.skip_comment();
+        self.eat('\r');
+        if self.peek().is_some() && !self.eat('\n') {
+            return Err(self.error(self.pos, "expected a newline"));
+        }
+        Ok(())
+    }
+
+    fn span(&self, lo: usize, hi: usize) -> Span {
+        Span::with_root_ctxt(
+            self.start_pos + BytePos(lo as u32),
+            self.start_pos + BytePos(hi as u32),
+        )
+    }
+
+    fn error(&self, pos: usize, message: &str) -> ParseError {
+        let hi = self.src[pos..].chars().next().map_or(pos, |c| pos + c.len_utf8());
+        ParseError { span: self.span(pos, hi), message: message.to_string() }
+    }
+}
@@ -0,0 +1,91 @@
+use super::*;
+
+fn parse(src: &str) -> Result<LintConfig, ParseError> {
+    LintConfig::parse(src, BytePos(0), Path::new("/ws"))
+}
+
+fn snippet<'a>(src: &'a str, span: Span) -> &'a str {
+    &src[span.lo().0 as usize..span.hi().0 as usize]
+}
+
+fn levels(entries: &[LintConfigEntry]) -> Vec<(&str, Level)> {
+    entries.iter().map(|entry| (&*entry.name, entry.level)).collect()
+}
+
+#[test]
+fn lints_and_overrides() {
+    let src = r#"
+# Comments are ignored.
+[lints]
+unused = "deny"     # so are trailing ones
+"clippy::pedantic" = 'warn'
+unused-variables = "allow"
+
+[[overrides]]
+paths = [
+    "src/generated",
+    "/abs/path", # a trailing comma is allowed
+]
+lints = { dead_code = "allow" }
+
+[[overrides]]
+paths = ["vendor"]
+
+[overrides.lints]
+warnings = "forbid"
+"#;
+    let config = parse(src).unwrap();
+    assert_eq!(
+        levels(&config.lints),
+        [
+            ("unused", Level::Deny),
+            ("clippy::pedantic", Level::Warn),
+            ("unused_variables", Level::Allow)
+        ]
+    );
+    assert_eq!(snippet(src, config.lints[0].span), "unused = \"deny\"");
+
+    let paths = config.overrides.iter().map(|o| o.paths.clone()).collect::<Vec<_>>();
+    assert_eq!(
+        paths,
+        [
+            vec![PathBuf::from("/ws/src/generated"), PathBuf::from("/abs/path")],
+            vec![PathBuf::from("/ws/vendor")]
+        ]
+    );
+    assert_eq!(levels(&config.overrides[0].lints), [("dead_code", Level::Allow)]);
+    assert_eq!(snippet(src, config.overrides[0].lints[0].span), "dead_code = \"allow\"");
+    assert_eq!(levels(&config.overrides[1].lints), [("warnings", Level::Forbid)]);
+
+    assert!(config.overrides[0].applies_to(Path::new("/ws/src/generated/a/b.rs")));
+    assert!(!config.overrides[0].applies_to(Path::new("/ws/src/generated_by_hand.rs")));
+    assert!(!config.overrides[1].applies_to(Path::new("/ws/src/lib.rs")));
+}
+
+#[test]
+fn dotted_keys() {
+    let config = parse("lints.unused = \"warn\"\n[lints]\ndead_code = \"allow\"\n").unwrap();
+    assert_eq!(levels(&config.lints), [("unused", Level::Warn), ("dead_code", Level::Allow)]);
+}
+
+#[test]
+fn errors() {
+    let cases = [
+        ("[lints]\nunused = \"dney\"\n", "\"dney\"", "invalid lint level `dney`"),
+        ("[lints]\nunused = \"deny\"\nunused = \"warn\"\n", "unused", "duplicate key `unused`"),
+        ("[lints]\n[lints]\n", "[lints]", "duplicate key `lints`"),
+        ("[lints]\nclippy.pedantic = \"warn\"\n", "clippy", "expected a lint level"),
+        ("[[overrides]]\nlints = {}\n", "[[overrides]]", "override without `paths`"),
+        ("[[overrides]]\npaths = [1]\n", "1", "expected a string"),
+        ("[lint]\n", "[lint]", "unknown key `lint`"),
+        ("lints = \"deny\"\n", "\"deny\"", "expected `lints` to be a table"),
+        ("[lints]\nunused = \"deny\" dead_code\n", "d", "expected a newline"),
+        ("[lints]\nunused = \"deny\n", "\"", "unterminated string"),
+        ("[lints\n", "\n", "expected `]`"),
+    ];
+    for &(src, span, message) in &cases {
+        let error = parse(src).unwrap_err();
+        assert_eq!(snippet(src, error.span), span, "wrong span for `{}`", src);
+        assert!(error.message.starts_with(message), "wrong error for `{}`: {}", src, error.message);
+    }
+}
@@ -43,7 +43,7 @@ pub enum Applicability {
 }
 
 /// Setting for how to handle a lint.
-#[derive(Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Debug, Hash)]
+#[derive(Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Debug, Hash, Encodable, Decodable)]
 pub enum Level {
     Allow,
     Warn,
@@ -87,6 +87,42 @@ pub fn from_symbol(x: Symbol) -> Option<Level> {
     }
 }
 
+/// Where the level of an emitted lint was set. This is attached to the
+/// diagnostic of the lint, so that emitters can report it.
+#[derive(Clone, Debug, PartialEq, Hash, Encodable, Decodable)]
+pub struct LintLevelOrigin {
+    /// The level the lint was emitted at.
+    pub level: Level,
+    /// The level set by `kind`, which is higher than `level` if it was capped.
+    pub set_level: Level,
+    /// The lint, or the lint group, whose level was set.
+    pub name: String,
+    pub kind: LintLevelOriginKind,
+    pub cap: Option<LintCap>,
+}
+
+#[derive(Clone, Debug, PartialEq, Hash, Encodable, Decodable)]
+pub enum LintLevelOriginKind {
+    /// The lint is at its default level.
+    Default,
+    /// A lint flag, like `-D warnings`.
+    CommandLine { flag: String },
+    /// A lint attribute, with the span of the lint name in it.
+    Attribute(Span),
+    /// An entry of the `--lint-config` file.
+    LintConfig(Span),
+}
+
+/// What lowered the level of a lint.
+#[derive(Clone, Copy, Debug, PartialEq, Hash, Encodable, Decodable)]
+pub enum LintCap {
+    /// The `--cap-lints` flag.
+    CapLints,
+    /// The driver, for instance rustdoc, which caps the lints it doesn't care
+    /// about.
+    Driver,
+}
+
 /// Specification of a single lint.
 #[derive(Copy, Clone, Debug)]
 pub struct Lint {
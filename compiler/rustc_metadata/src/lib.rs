@@ -32,3 +32,4 @@
 pub mod creader;
 pub mod dynamic_lib;
 pub mod locator;
+pub mod metadata_cache;
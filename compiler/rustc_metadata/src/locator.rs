@@ -213,6 +213,7 @@
 //! metadata::locator or metadata::creader for all the juicy details!
 
 use crate::creader::Library;
+use crate::metadata_cache;
 use crate::rmeta::{rustc_version, MetadataBlob, METADATA_HEADER};
 
 use rustc_data_structures::fx::{FxHashMap, FxHashSet};
@@ -539,21 +540,26 @@ fn extract_one(
         let mut err_data: Option<Vec<PathBuf>> = None;
         for (lib, kind) in m {
             info!("{} reading metadata from: {}", flavor, lib.display());
-            let (hash, metadata) =
-                match get_metadata_section(self.target, flavor, &lib, self.metadata_loader) {
-                    Ok(blob) => {
-                        if let Some(h) = self.crate_matches(&blob, &lib) {
-                            (h, blob)
-                        } else {
-                            info!("metadata mismatch");
-                            continue;
-                        }
-                    }
-                    Err(err) => {
-                        warn!("no metadata found: {}", err);
+            let blob = metadata_cache::get_metadata_section(
+                self.target,
+                flavor,
+                &lib,
+                self.metadata_loader,
+            );
+            let (hash, metadata) = match blob {
+                Ok(blob) => {
+                    if let Some(h) = self.crate_matches(&blob, &lib) {
+                        (h, blob)
+                    } else {
+                        info!("metadata mismatch");
                         continue;
                     }
-                };
+                }
+                Err(err) => {
+                    warn!("no metadata found: {}", err);
+                    continue;
+                }
+            };
             // If we see multiple hashes, emit an error about duplicate candidates.
             if slot.as_ref().map_or(false, |s| s.0 != hash) {
                 if let Some(candidates) = err_data {
@@ -727,7 +733,7 @@ fn find_commandline_library(&mut self) -> Result<Option<Library>, CrateError> {
     }
 }
 
-fn get_metadata_section(
+crate fn get_metadata_section(
     target: &Target,
     flavor: CrateFlavor,
     filename: &Path,
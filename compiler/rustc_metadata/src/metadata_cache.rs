@@ -0,0 +1,166 @@
+//! A cache of the metadata blobs of crates, for the compiler daemon of `--daemon`.
+//!
+//! The daemon forks a process for every compilation. Each of them records the crate files it had
+//! to load metadata from, and the daemon then loads these files into the cache, so that the
+//! processes it forks later inherit their blobs instead of reading them again. The blobs are copied
+//! into memory, rather than mapped like in a compilation, since a mapped file which is truncated
+//! would crash the processes which use it. The blobs of rlibs and dylibs are kept extracted and
+//! decompressed.
+//!
+//! A blob is reused for a compilation for the same target, of a file which has the same size,
+//! modification time and inode as when it was loaded. The `CrateMetadata` decoded from a blob
+//! refers to the session it was decoded in, so every compilation still builds its own.
+
+use crate::locator::{self, CrateFlavor};
+use crate::rmeta::MetadataBlob;
+
+use rustc_data_structures::fx::FxHashMap;
+use rustc_data_structures::owning_ref::OwningRef;
+use rustc_middle::middle::cstore::MetadataLoader;
+use rustc_target::spec::Target;
+
+use std::fs;
+use std::lazy::SyncLazy;
+use std::mem;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+use tracing::info;
+
+/// Whether this is the compiler daemon, or a process it forked.
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// The blobs, by the LLVM target triple they were loaded for and the path of their file.
+static CACHE: SyncLazy<Mutex<FxHashMap<(String, PathBuf), CachedBlob>>> =
+    SyncLazy::new(Default::default);
+
+/// The files whose metadata was loaded without the cache.
+static LOADED: SyncLazy<Mutex<Vec<PathBuf>>> = SyncLazy::new(Default::default);
+
+struct CachedBlob {
+    flavor: CrateFlavor,
+    stamp: Stamp,
+    /// The bytes of the blob, which the compiler thread of the forked processes shares with their
+    /// main thread.
+    bytes: Arc<Vec<u8>>,
+}
+
+fn blob_of(bytes: &Arc<Vec<u8>>) -> MetadataBlob {
+    let bytes = OwningRef::new(bytes.clone()).map(|bytes| &bytes[..]);
+    MetadataBlob::new(rustc_erase_owner!(bytes.map_owner_box()))
+}
+
+/// The size and modification time of a file, and on Unix its inode, which changes when it is
+/// replaced by another file of the same size within the precision of the modification time.
+#[derive(PartialEq)]
+struct Stamp {
+    len: u64,
+    modified: SystemTime,
+    #[cfg(unix)]
+    inode: (u64, u64),
+}
+
+impl Stamp {
+    fn of(path: &Path) -> Result<Stamp, String> {
+        let metadata = path
+            .metadata()
+            .map_err(|e| format!("failed to read the metadata of '{}': {}", path.display(), e))?;
+        let modified = metadata.modified().map_err(|e| {
+            format!("failed to read the modification time of '{}': {}", path.display(), e)
+        })?;
+        Ok(Stamp {
+            len: metadata.len(),
+            modified,
+            #[cfg(unix)]
+            inode: {
+                use std::os::unix::fs::MetadataExt;
+                (metadata.dev(), metadata.ino())
+            },
+        })
+    }
+}
+
+/// Enables the cache. The compiler daemon calls this before forking any process.
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Like `locator::get_metadata_section`, but returns the cached blob of `path` if the file hasn't
+/// changed since it was cached, and records the files it loads otherwise.
+crate fn get_metadata_section(
+    target: &Target,
+    flavor: CrateFlavor,
+    path: &Path,
+    loader: &dyn MetadataLoader,
+) -> Result<MetadataBlob, String> {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return locator::get_metadata_section(target, flavor, path, loader);
+    }
+    if let Some(blob) = get(target, flavor, path) {
+        return Ok(blob);
+    }
+    let blob = locator::get_metadata_section(target, flavor, path, loader)?;
+    LOADED.lock().unwrap().push(path.to_path_buf());
+    Ok(blob)
+}
+
+fn get(target: &Target, flavor: CrateFlavor, path: &Path) -> Option<MetadataBlob> {
+    let cache = CACHE.lock().unwrap();
+    let cached = cache.get(&(target.llvm_target.clone(), path.to_path_buf()))?;
+    if cached.flavor != flavor || Stamp::of(path).ok()? != cached.stamp {
+        return None;
+    }
+    info!("reusing the cached metadata of '{}'", path.display());
+    Some(blob_of(&cached.bytes))
+}
+
+/// Returns the files whose metadata was loaded without the cache since the last call. The
+/// processes forked by the daemon report them to it when their compilation ends.
+pub fn take_loaded() -> Vec<PathBuf> {
+    mem::take(&mut *LOADED.lock().unwrap())
+}
+
+/// Loads the metadata of the crate file `path` into the cache, for the compilations for `target`,
+/// with `loader` for rlibs and dylibs. A file which can't be loaded anymore is removed from the
+/// cache.
+pub fn load(path: &Path, target: &Target, loader: &dyn MetadataLoader) -> Result<(), String> {
+    let result = load_blob(path, target, loader);
+    let key = (target.llvm_target.clone(), path.to_path_buf());
+    let mut cache = CACHE.lock().unwrap();
+    match result {
+        Ok(cached) => {
+            cache.insert(key, cached);
+            Ok(())
+        }
+        Err(e) => {
+            cache.remove(&key);
+            Err(e)
+        }
+    }
+}
+
+fn load_blob(
+    path: &Path,
+    target: &Target,
+    loader: &dyn MetadataLoader,
+) -> Result<CachedBlob, String> {
+    // Like `locator::list_file_metadata`, tell the flavors apart by the extension of the file.
+    let flavor = match path.extension().and_then(|ext| ext.to_str()) {
+        Some("rlib") => CrateFlavor::Rlib,
+        Some("rmeta") => CrateFlavor::Rmeta,
+        _ => CrateFlavor::Dylib,
+    };
+    // Read the stamp first, so that a file which changes while it is loaded doesn't match it.
+    let stamp = Stamp::of(path)?;
+    let bytes = match flavor {
+        CrateFlavor::Rmeta => fs::read(path)
+            .map_err(|_| format!("failed to read rmeta metadata: '{}'", path.display()))?,
+        _ => locator::get_metadata_section(target, flavor, path, loader)?.bytes().to_vec(),
+    };
+    let bytes = Arc::new(bytes);
+    if !blob_of(&bytes).is_compatible() {
+        return Err(format!("incompatible metadata version found: '{}'", path.display()));
+    }
+    Ok(CachedBlob { flavor, stamp, bytes })
+}
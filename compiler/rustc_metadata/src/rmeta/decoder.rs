@@ -581,6 +581,10 @@ impl MetadataBlob {
         MetadataBlob(metadata_ref)
     }
 
+    crate fn bytes(&self) -> &[u8] {
+        &self.0
+    }
+
     crate fn is_compatible(&self) -> bool {
         self.raw_bytes().starts_with(METADATA_HEADER)
     }
@@ -7,7 +7,7 @@
 use rustc_hir::HirId;
 use rustc_session::lint::{
     builtin::{self, FORBIDDEN_LINT_GROUPS},
-    Level, Lint, LintId,
+    Level, Lint, LintCap, LintId, LintLevelOrigin, LintLevelOriginKind,
 };
 use rustc_session::{DiagnosticMessageId, Session};
 use rustc_span::hygiene::MacroKind;
@@ -22,28 +22,35 @@ pub enum LintLevelSource {
     Default,
 
     /// Lint level was set by an attribute.
-    Node(Symbol, Span, Option<Symbol> /* RFC 2383 reason */),
+    /// The provided `Level` is the level specified in the attribute.
+    Node(Symbol, Span, Option<Symbol> /* RFC 2383 reason */, Level),
 
     /// Lint level was set by a command-line flag.
     /// The provided `Level` is the level specified on the command line.
     /// (The actual level may be lower due to `--cap-lints`.)
     CommandLine(Symbol, Level),
+
+    /// Lint level was set by the file given with `--lint-config`, in the entry
+    /// at the provided `Span`, to the provided `Level`.
+    LintConfig(Symbol, Span, Level),
 }
 
 impl LintLevelSource {
     pub fn name(&self) -> Symbol {
         match *self {
             LintLevelSource::Default => symbol::kw::Default,
-            LintLevelSource::Node(name, _, _) => name,
+            LintLevelSource::Node(name, _, _, _) => name,
             LintLevelSource::CommandLine(name, _) => name,
+            LintLevelSource::LintConfig(name, _, _) => name,
         }
     }
 
     pub fn span(&self) -> Span {
         match *self {
             LintLevelSource::Default => DUMMY_SP,
-            LintLevelSource::Node(_, span, _) => span,
+            LintLevelSource::Node(_, span, _, _) => span,
             LintLevelSource::CommandLine(_, _) => DUMMY_SP,
+            LintLevelSource::LintConfig(_, span, _) => span,
         }
     }
 }
@@ -324,7 +331,7 @@ fn struct_lint_level_impl(
                     );
                 }
             }
-            LintLevelSource::Node(lint_attr_name, src, reason) => {
+            LintLevelSource::Node(lint_attr_name, src, reason, _) => {
                 if let Some(rationale) = reason {
                     err.note(&rationale.as_str());
                 }
@@ -346,7 +353,27 @@ fn struct_lint_level_impl(
                     );
                 }
             }
+            LintLevelSource::LintConfig(lint_config_name, src, _) => {
+                let loc = sess.source_map().lookup_char_pos(src.lo());
+                let location = format!("{}:{}", loc.file.name.prefer_local(), loc.line);
+                let level_str = level.as_str();
+                let msg = if lint_config_name.as_str() == name {
+                    format!("`#[{}({})]` set in lint config at {}", level_str, name, location)
+                } else {
+                    format!(
+                        "`#[{}({})]` implied by `{}` set in lint config at {}",
+                        level_str, name, lint_config_name, location
+                    )
+                };
+                sess.diag_note_once(&mut err, DiagnosticMessageId::from(lint), &msg);
+            }
+        }
+
+        let origin = lint_level_origin(sess, lint, level, src);
+        if sess.opts.debugging_opts.explain_lint_levels {
+            err.note(&explain_lint_level(sess, &name, &origin));
         }
+        err.set_lint_level_origin(origin);
 
         err.code(DiagnosticId::Lint { name, has_future_breakage });
 
@@ -378,6 +405,88 @@ fn struct_lint_level_impl(
     struct_lint_level_impl(sess, lint, level, src, span, Box::new(decorate))
 }
 
+/// Describes where the level of `lint` was set, and whether it was capped, for the diagnostic of
+/// the lint.
+fn lint_level_origin(
+    sess: &Session,
+    lint: &'static Lint,
+    level: Level,
+    src: LintLevelSource,
+) -> LintLevelOrigin {
+    let (set_level, kind) = match src {
+        LintLevelSource::Default => {
+            (lint.default_level(sess.edition()), LintLevelOriginKind::Default)
+        }
+        LintLevelSource::CommandLine(lint_flag_val, orig_level) => {
+            let flag = match orig_level {
+                Level::Warn => "-W",
+                Level::Deny => "-D",
+                Level::Forbid => "-F",
+                Level::Allow => "-A",
+            };
+            let flag = format!("{} {}", flag, lint_flag_val.as_str().replace("_", "-"));
+            (orig_level, LintLevelOriginKind::CommandLine { flag })
+        }
+        LintLevelSource::Node(_, span, _, set_level) => {
+            (set_level, LintLevelOriginKind::Attribute(span))
+        }
+        LintLevelSource::LintConfig(_, span, set_level) => {
+            (set_level, LintLevelOriginKind::LintConfig(span))
+        }
+    };
+    let name = match src {
+        LintLevelSource::Default => lint.name_lower(),
+        _ => src.name().to_string(),
+    };
+    // `--cap-lints` is applied before the caps of the driver, so it is the one which lowered the
+    // level if both would have.
+    let cap = (level < set_level).then(|| {
+        if sess.opts.lint_cap == Some(level) {
+            LintCap::CapLints
+        } else {
+            LintCap::Driver
+        }
+    });
+    LintLevelOrigin { level, set_level, name, kind, cap }
+}
+
+/// The note added to lints by `-Z explain-lint-levels`.
+fn explain_lint_level(sess: &Session, lint_name: &str, origin: &LintLevelOrigin) -> String {
+    let location = |span: Span| {
+        let loc = sess.source_map().lookup_char_pos(span.lo());
+        format!("{}:{}:{}", loc.file.name.prefer_local(), loc.line, loc.col.0 + 1)
+    };
+    let source = match origin.kind {
+        LintLevelOriginKind::Default => "its default".to_string(),
+        LintLevelOriginKind::CommandLine { ref flag } => {
+            format!("set by `{}` on the command line", flag)
+        }
+        LintLevelOriginKind::Attribute(span) => format!(
+            "set by `#[{}({})]` at {}",
+            origin.set_level.as_str(),
+            origin.name,
+            location(span)
+        ),
+        LintLevelOriginKind::LintConfig(span) => {
+            format!("set by `{}` in the lint config at {}", origin.name, location(span))
+        }
+    };
+    let mut note =
+        format!("the level of `{}` is `{}`, {}", lint_name, origin.level.as_str(), source);
+    match origin.cap {
+        Some(LintCap::CapLints) => note.push_str(&format!(
+            ", lowered from `{}` by `--cap-lints {}`",
+            origin.set_level.as_str(),
+            origin.level.as_str()
+        )),
+        Some(LintCap::Driver) => {
+            note.push_str(&format!(", lowered from `{}` by the driver", origin.set_level.as_str()))
+        }
+        None => {}
+    }
+    note
+}
+
 /// Returns whether `span` originates in a foreign crate's external macro.
 ///
 /// This is used to test whether a lint should not even begin to figure out whether it should
@@ -1,12 +1,19 @@
 //! Diagnostics related methods for `TyS`.
 
 use crate::ty::TyKind::*;
-use crate::ty::{InferTy, TyCtxt, TyS};
-use rustc_errors::{Applicability, DiagnosticBuilder};
+use crate::ty::{InferTy, Ty, TyCtxt, TyS};
+use rustc_errors::translation::DiagnosticArgValue;
+use rustc_errors::{Applicability, DiagnosticBuilder, IntoDiagnosticArg};
 use rustc_hir as hir;
 use rustc_hir::def_id::DefId;
 use rustc_hir::{QPath, TyKind, WhereBoundPredicate, WherePredicate};
 
+impl<'tcx> IntoDiagnosticArg for Ty<'tcx> {
+    fn into_diagnostic_arg(self) -> DiagnosticArgValue {
+        DiagnosticArgValue::Str(self.to_string())
+    }
+}
+
 impl<'tcx> TyS<'tcx> {
     /// Similar to `TyS::is_primitive`, but also considers inferred numeric values to be primitive.
     pub fn is_primitive_ty(&self) -> bool {
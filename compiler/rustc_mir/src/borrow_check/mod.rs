@@ -362,7 +362,8 @@ fn do_mir_borrowck<'a, 'tcx>(
             |lint| {
                 let mut diag = lint.build("");
 
-                diag.message = initial_diag.styled_message().clone();
+                diag.message = initial_diag.message.clone();
+                diag.args = initial_diag.args.clone();
                 diag.span = initial_diag.span.clone();
 
                 diag.buffer(&mut mbcx.errors_buffer);
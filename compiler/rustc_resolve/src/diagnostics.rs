@@ -935,7 +935,7 @@ fn lookup_import_candidates_from_module<FilterFn>(
             self.lookup_import_candidates(ident, Namespace::MacroNS, parent_scope, |res| {
                 matches!(res, Res::Def(DefKind::Macro(MacroKind::Bang), _))
             });
-        show_candidates(err, None, &import_suggestions, false, true);
+        show_candidates(err, None, &[], &import_suggestions, false, true);
 
         if macro_kind == MacroKind::Derive && (ident.name == sym::Send || ident.name == sym::Sync) {
             let msg = format!("unsafe traits like `{}` should be implemented explicitly", ident);
@@ -1615,6 +1615,8 @@ fn find_span_immediately_after_crate_name(
     err: &mut DiagnosticBuilder<'_>,
     // This is `None` if all placement locations are inside expansions
     use_placement_span: Option<Span>,
+    // The path which failed to resolve, if it should refer to the imported candidate instead
+    path: &[Segment],
     candidates: &[ImportSuggestion],
     instead: bool,
     found_use: bool,
@@ -1648,7 +1650,29 @@ fn find_span_immediately_after_crate_name(
             *candidate = format!("use {};\n{}", candidate, additional_newline);
         }
 
-        err.span_suggestions(span, &msg, path_strings.into_iter(), Applicability::Unspecified);
+        match path {
+            // The import is of no use if the path still has its prefix, e.g. `a::bar` when `bar`
+            // isn't in `a`, so remove the prefix as part of the suggestion. The import may be in
+            // another file than the path.
+            [first, .., last] if !path.iter().any(|seg| seg.ident.span.from_expansion()) => {
+                let prefix = first.ident.span.until(last.ident.span);
+                err.multipart_suggestions(
+                    &msg,
+                    path_strings
+                        .into_iter()
+                        .map(|candidate| vec![(span, candidate), (prefix, String::new())]),
+                    Applicability::MaybeIncorrect,
+                );
+            }
+            _ => {
+                err.span_suggestions(
+                    span,
+                    &msg,
+                    path_strings.into_iter(),
+                    Applicability::MaybeIncorrect,
+                );
+            }
+        }
     } else {
         msg.push(':');
 
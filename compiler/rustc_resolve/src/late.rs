@@ -1832,11 +1832,14 @@ fn smart_resolve_path_fragment(
                 let instead = res.is_some();
                 let suggestion =
                     if res.is_none() { this.report_missing_type_error(path) } else { None };
+                // Qualified paths can't simply refer to an import instead.
+                let path = if qself.is_none() { path.to_vec() } else { Vec::new() };
 
                 this.r.use_injections.push(UseError {
                     err,
                     candidates,
                     def_id,
+                    path,
                     instead,
                     suggestion,
                 });
@@ -1902,6 +1905,7 @@ fn smart_resolve_path_fragment(
                     err,
                     candidates,
                     def_id,
+                    path: path.to_vec(),
                     instead: false,
                     suggestion: None,
                 });
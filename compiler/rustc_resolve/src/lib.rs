@@ -681,6 +681,8 @@ struct UseError<'a> {
     candidates: Vec<ImportSuggestion>,
     /// The `DefId` of the module to place the use-statements in.
     def_id: DefId,
+    /// The path which failed to resolve, if the candidates should replace it. Empty otherwise.
+    path: Vec<Segment>,
     /// Whether the diagnostic should say "instead" (as in `consider importing ... instead`).
     instead: bool,
     /// Extra free-form suggestion.
@@ -2893,7 +2895,7 @@ fn report_errors(&mut self, krate: &Crate) {
     }
 
     fn report_with_use_injections(&mut self, krate: &Crate) {
-        for UseError { mut err, candidates, def_id, instead, suggestion } in
+        for UseError { mut err, candidates, def_id, path, instead, suggestion } in
             self.use_injections.drain(..)
         {
             let (span, found_use) = if let Some(def_id) = def_id.as_local() {
@@ -2902,7 +2904,14 @@ fn report_with_use_injections(&mut self, krate: &Crate) {
                 (None, false)
             };
             if !candidates.is_empty() {
-                diagnostics::show_candidates(&mut err, span, &candidates, instead, found_use);
+                diagnostics::show_candidates(
+                    &mut err,
+                    span,
+                    &path,
+                    &candidates,
+                    instead,
+                    found_use,
+                );
             } else if let Some((span, msg, sugg, appl)) = suggestion {
                 err.span_suggestion(span, msg, sugg, appl);
             }
@@ -298,6 +298,8 @@ pub enum ErrorOutputType {
         /// human output.
         json_rendered: HumanReadableErrorType,
     },
+    /// A single SARIF log, for code-scanning tools.
+    Sarif,
 }
 
 impl Default for ErrorOutputType {
@@ -678,6 +680,7 @@ fn default() -> Options {
             debuginfo: DebugInfo::None,
             lint_opts: Vec::new(),
             lint_cap: None,
+            lint_config: None,
             describe_lints: false,
             output_types: OutputTypes(BTreeMap::new()),
             search_paths: vec![],
@@ -1121,8 +1124,8 @@ pub fn rustc_optgroups() -> Vec<RustcOptGroup> {
         opt::opt_s(
             "",
             "error-format",
-            "How errors and other messages are produced",
-            "human|json|short",
+            "How errors and other messages are produced (`sarif` is unstable)",
+            "human|json|short|sarif",
         ),
         opt::multi_s("", "json", "Configure the JSON output of the compiler", "CONFIG"),
         opt::opt_s(
@@ -1149,6 +1152,14 @@ pub fn rustc_optgroups() -> Vec<RustcOptGroup> {
             "Remap source names in all output (compiler messages and output files)",
             "FROM=TO",
         ),
+        opt::opt("", "lint-config", "Read lint levels from a TOML file", "PATH"),
+        opt::opt("", "daemon", "Serve compiler invocations on a Unix socket", "SOCKET"),
+        opt::opt(
+            "",
+            "daemon-connect",
+            "Send this invocation to the daemon listening on a Unix socket",
+            "SOCKET",
+        ),
     ]);
     opts
 }
@@ -1282,12 +1293,13 @@ pub fn parse_error_format(
             Some("json") => ErrorOutputType::Json { pretty: false, json_rendered },
             Some("pretty-json") => ErrorOutputType::Json { pretty: true, json_rendered },
             Some("short") => ErrorOutputType::HumanReadable(HumanReadableErrorType::Short(color)),
+            Some("sarif") => ErrorOutputType::Sarif,
 
             Some(arg) => early_error(
                 ErrorOutputType::HumanReadable(HumanReadableErrorType::Default(color)),
                 &format!(
-                    "argument for `--error-format` must be `human`, `json` or \
-                     `short` (instead was `{}`)",
+                    "argument for `--error-format` must be `human`, `json`, `short` \
+                     or the unstable `sarif` (instead was `{}`)",
                     arg
                 ),
             ),
@@ -1366,6 +1378,9 @@ fn check_debug_option_stability(
                 "`--error-format=human-annotate-rs` is unstable",
             );
         }
+        if let ErrorOutputType::Sarif = error_format {
+            early_error(ErrorOutputType::default(), "`--error-format=sarif` is unstable");
+        }
     }
 }
 
@@ -1924,6 +1939,7 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
         .unwrap_or_else(|e| early_error(error_format, &e[..]));
 
     let (lint_opts, describe_lints, lint_cap) = get_cmd_lint_options(matches, error_format);
+    let lint_config = matches.opt_str("lint-config").map(PathBuf::from);
 
     let mut debugging_opts = DebuggingOptions::build(matches, error_format);
     check_debug_option_stability(&debugging_opts, error_format, json_rendered);
@@ -2098,6 +2114,7 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
         debuginfo,
         lint_opts,
         lint_cap,
+        lint_config,
         describe_lints,
         output_types,
         search_paths,
@@ -8,6 +8,9 @@
 use rustc_target::spec::{CodeModel, LinkerFlavor, MergeFunctions, PanicStrategy, SanitizerSet};
 use rustc_target::spec::{RelocModel, RelroLevel, SplitDebuginfo, TargetTriple, TlsModel};
 
+use rustc_data_structures::profiling::TimePassesFormat;
+use rustc_errors::emitter::{HumanReadableErrorType, TerminalUrl};
+use rustc_errors::ColorConfig;
 use rustc_feature::UnstableFeatures;
 use rustc_span::edition::Edition;
 use rustc_span::SourceFileHashAlgorithm;
@@ -130,6 +133,7 @@ pub struct Options {
         debuginfo: DebugInfo [TRACKED],
         lint_opts: Vec<(String, lint::Level)> [TRACKED],
         lint_cap: Option<lint::Level> [TRACKED],
+        lint_config: Option<PathBuf> [TRACKED],
         describe_lints: bool [UNTRACKED],
         output_types: OutputTypes [TRACKED],
         search_paths: Vec<SearchPath> [UNTRACKED],
@@ -369,6 +373,11 @@ mod desc {
     pub const parse_wasi_exec_model: &str = "either `command` or `reactor`";
     pub const parse_split_debuginfo: &str =
         "one of supported split-debuginfo modes (`off` or `dsymutil`)";
+    pub const parse_terminal_url: &str =
+        "either a boolean (`yes`, `no`, `on`, `off`, etc), or `auto`";
+    pub const parse_time_passes_format: &str = "either `text` or `json`";
+    pub const parse_error_output_file: &str =
+        "a path, optionally followed by `:human`, `:short`, `:json`, `:pretty-json` or `:sarif`";
 }
 
 mod parse {
@@ -863,6 +872,58 @@ mod parse {
         }
         true
     }
+
+    crate fn parse_terminal_url(slot: &mut TerminalUrl, v: Option<&str>) -> bool {
+        *slot = match v {
+            Some("y") | Some("yes") | Some("on") | None => TerminalUrl::Yes,
+            Some("n") | Some("no") | Some("off") => TerminalUrl::No,
+            Some("auto") => TerminalUrl::Auto,
+            _ => return false,
+        };
+        true
+    }
+
+    crate fn parse_time_passes_format(slot: &mut TimePassesFormat, v: Option<&str>) -> bool {
+        *slot = match v {
+            Some("text") => TimePassesFormat::Text,
+            Some("json") => TimePassesFormat::Json,
+            _ => return false,
+        };
+        true
+    }
+
+    crate fn parse_error_output_file(
+        slot: &mut Option<(PathBuf, ErrorOutputType)>,
+        v: Option<&str>,
+    ) -> bool {
+        let v = match v {
+            Some(v) => v,
+            None => return false,
+        };
+        // Colors are never written to a file.
+        let human = |kind: fn(ColorConfig) -> HumanReadableErrorType| {
+            ErrorOutputType::HumanReadable(kind(ColorConfig::Never))
+        };
+        let json = |pretty| ErrorOutputType::Json {
+            pretty,
+            json_rendered: HumanReadableErrorType::Default(ColorConfig::Never),
+        };
+        // The format is optional, so that paths containing colons (e.g. on
+        // Windows) can be written as they are.
+        let (path, format) = match v.rsplit_once(':') {
+            Some((path, "human")) => (path, human(HumanReadableErrorType::Default)),
+            Some((path, "short")) => (path, human(HumanReadableErrorType::Short)),
+            Some((path, "json")) => (path, json(false)),
+            Some((path, "pretty-json")) => (path, json(true)),
+            Some((path, "sarif")) => (path, ErrorOutputType::Sarif),
+            _ => (v, json(false)),
+        };
+        if path.is_empty() {
+            return false;
+        }
+        *slot = Some((PathBuf::from(path), format));
+        true
+    }
 }
 
 options! {
@@ -1055,6 +1116,11 @@ mod parse {
         "emits a future-incompatibility report for lints (RFC 2834)"),
     emit_stack_sizes: bool = (false, parse_bool, [UNTRACKED],
         "emit a section containing stack size metadata (default: no)"),
+    error_output_file: Option<(PathBuf, ErrorOutputType)> = (None, parse_error_output_file,
+        [UNTRACKED], "also write all diagnostics to this file, in the given format \
+        (`human`, `short`, `json`, `pretty-json` or `sarif`; default: `json`)"),
+    explain_lint_levels: bool = (false, parse_bool, [TRACKED],
+        "add a note to every lint saying where its level was set (default: no)"),
     fewer_names: Option<bool> = (None, parse_opt_bool, [TRACKED],
         "reduce memory use by retaining fewer names within compilation artifacts (LLVM-IR) \
         (default: no)"),
@@ -1248,6 +1314,9 @@ mod parse {
         "which mangling version to use for symbol names ('legacy' (default) or 'v0')"),
     teach: bool = (false, parse_bool, [TRACKED],
         "show extended diagnostic help (default: no)"),
+    terminal_urls: TerminalUrl = (TerminalUrl::No, parse_terminal_url, [UNTRACKED],
+        "write file locations and error codes in human readable diagnostics as hyperlinks, \
+        `auto` only does so in terminals known to support them (default: no)"),
     terminal_width: Option<usize> = (None, parse_opt_number, [UNTRACKED],
         "set the current terminal width"),
     tune_cpu: Option<String> = (None, parse_opt_string, [TRACKED],
@@ -1268,10 +1337,18 @@ mod parse {
         "measure time of each LLVM pass (default: no)"),
     time_passes: bool = (false, parse_bool, [UNTRACKED],
         "measure time of each rustc pass (default: no)"),
+    time_passes_format: TimePassesFormat = (TimePassesFormat::Text, parse_time_passes_format,
+        [UNTRACKED], "the format of the output of -Z time and -Z time-passes: `text` (default) \
+        or `json`"),
     tls_model: Option<TlsModel> = (None, parse_tls_model, [TRACKED],
         "choose the TLS model to use (`rustc --print tls-models` for details)"),
     trace_macros: bool = (false, parse_bool, [UNTRACKED],
         "for every macro invocation, print its name and arguments (default: no)"),
+    translate_additional_ftl: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
+        "additional translation catalogue whose messages are preferred over those of the \
+        language of `-Z translate-lang`, for testing translations"),
+    translate_lang: Option<String> = (None, parse_opt_string, [UNTRACKED],
+        "language to translate diagnostics to, e.g. `fr` (default: `en-US`)"),
     trap_unreachable: Option<bool> = (None, parse_opt_bool, [TRACKED],
         "generate trap instructions for unreachable intrinsics (default: use target setting, usually yes)"),
     treat_err_as_bug: Option<NonZeroUsize> = (None, parse_treat_err_as_bug, [TRACKED],
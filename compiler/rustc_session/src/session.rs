@@ -12,15 +12,19 @@
 use rustc_data_structures::flock;
 use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_data_structures::jobserver::{self, Client};
-use rustc_data_structures::profiling::{duration_to_secs_str, SelfProfiler, SelfProfilerRef};
+use rustc_data_structures::profiling::{
+    duration_to_secs_str, set_time_passes_crate_name, SelfProfiler, SelfProfilerRef,
+};
 use rustc_data_structures::sync::{
     self, AtomicU64, AtomicUsize, Lock, Lrc, OnceCell, OneThread, Ordering, Ordering::SeqCst,
 };
 use rustc_errors::annotate_snippet_emitter_writer::AnnotateSnippetEmitterWriter;
-use rustc_errors::emitter::{Emitter, EmitterWriter, HumanReadableErrorType};
+use rustc_errors::emitter::{Emitter, EmitterWriter, HumanReadableErrorType, TeeEmitter};
 use rustc_errors::json::JsonEmitter;
 use rustc_errors::registry::Registry;
-use rustc_errors::{Diagnostic, DiagnosticBuilder, DiagnosticId, ErrorReported};
+use rustc_errors::sarif::SarifEmitter;
+use rustc_errors::translation::{TranslationError, Translator};
+use rustc_errors::{Diagnostic, DiagnosticBuilder, DiagnosticId, DiagnosticMessage, ErrorReported};
 use rustc_lint_defs::FutureBreakage;
 pub use rustc_span::crate_disambiguator::CrateDisambiguator;
 use rustc_span::source_map::{FileLoader, MultiSpan, RealFileLoader, SourceMap, Span};
@@ -33,7 +37,8 @@
 use std::cell::{self, RefCell};
 use std::env;
 use std::fmt;
-use std::io::Write;
+use std::fs::File;
+use std::io::{BufWriter, Write};
 use std::num::NonZeroU32;
 use std::ops::{Div, Mul};
 use std::path::PathBuf;
@@ -392,7 +397,7 @@ pub fn struct_span_err<S: Into<MultiSpan>>(&self, sp: S, msg: &str) -> Diagnosti
     pub fn struct_span_err_with_code<S: Into<MultiSpan>>(
         &self,
         sp: S,
-        msg: &str,
+        msg: impl Into<DiagnosticMessage>,
         code: DiagnosticId,
     ) -> DiagnosticBuilder<'_> {
         self.diagnostic().struct_span_err_with_code(sp, msg, code)
@@ -410,7 +415,7 @@ pub fn struct_span_fatal<S: Into<MultiSpan>>(&self, sp: S, msg: &str) -> Diagnos
     pub fn struct_span_fatal_with_code<S: Into<MultiSpan>>(
         &self,
         sp: S,
-        msg: &str,
+        msg: impl Into<DiagnosticMessage>,
         code: DiagnosticId,
     ) -> DiagnosticBuilder<'_> {
         self.diagnostic().struct_span_fatal_with_code(sp, msg, code)
@@ -1181,12 +1186,13 @@ pub fn first_attr_value_str_by_name(
 
 fn default_emitter(
     sopts: &config::Options,
+    error_format: config::ErrorOutputType,
     registry: rustc_errors::registry::Registry,
     source_map: Lrc<SourceMap>,
     emitter_dest: Option<Box<dyn Write + Send>>,
 ) -> Box<dyn Emitter + sync::Send> {
     let macro_backtrace = sopts.debugging_opts.macro_backtrace;
-    match (sopts.error_format, emitter_dest) {
+    match (error_format, emitter_dest) {
         (config::ErrorOutputType::HumanReadable(kind), dst) => {
             let (short, color_config) = kind.unzip();
 
@@ -1203,7 +1209,8 @@ fn default_emitter(
                         sopts.debugging_opts.teach,
                         sopts.debugging_opts.terminal_width,
                         macro_backtrace,
-                    ),
+                    )
+                    .terminal_urls(sopts.debugging_opts.terminal_urls),
                     Some(dst) => EmitterWriter::new(
                         dst,
                         Some(source_map),
@@ -1240,6 +1247,12 @@ fn default_emitter(
             )
             .ui_testing(sopts.debugging_opts.ui_testing),
         ),
+        (config::ErrorOutputType::Sarif, None) => {
+            Box::new(SarifEmitter::stderr(Some(registry), source_map))
+        }
+        (config::ErrorOutputType::Sarif, Some(dst)) => {
+            Box::new(SarifEmitter::new(dst, Some(registry), source_map))
+        }
     }
 }
 
@@ -1299,12 +1312,56 @@ pub fn build_session(
         sopts.file_path_mapping(),
         hash_kind,
     ));
-    let emitter = default_emitter(&sopts, registry, source_map.clone(), write_dest);
+    let mut emitter = default_emitter(
+        &sopts,
+        sopts.error_format,
+        registry.clone(),
+        source_map.clone(),
+        write_dest,
+    );
+    if let Some((path, format)) = &sopts.debugging_opts.error_output_file {
+        let file = File::create(path).unwrap_or_else(|e| {
+            early_error(
+                sopts.error_format,
+                &format!("failed to create error output file `{}`: {}", path.display(), e),
+            )
+        });
+        let file_emitter = default_emitter(
+            &sopts,
+            *format,
+            registry,
+            source_map.clone(),
+            Some(Box::new(BufWriter::new(file))),
+        );
+        emitter = Box::new(TeeEmitter::new(emitter, file_emitter));
+    }
+
+    // Catalogues which can't be loaded aren't fatal: the diagnostics are then
+    // emitted with the built-in English messages.
+    let translator = Translator::new(
+        &sysroot,
+        sopts.debugging_opts.translate_lang.as_deref(),
+        sopts.debugging_opts.translate_additional_ftl.as_deref(),
+    );
+    let (translator, translation_error) = match translator {
+        Ok(translator) => (translator, None),
+        Err(e) => (Translator::fallback(), Some(e)),
+    };
 
     let span_diagnostic = rustc_errors::Handler::with_emitter_and_flags(
         emitter,
         sopts.debugging_opts.diagnostic_handler_flags(can_emit_warnings),
-    );
+    )
+    .with_translator(translator);
+    if let Some(e) = translation_error {
+        let mut diag = span_diagnostic.struct_warn(&e.to_string());
+        if let TranslationError::Parse { errors, .. } = &e {
+            for error in errors {
+                diag.note(&error.to_string());
+            }
+        }
+        diag.note("diagnostics are emitted with the built-in English messages").emit();
+    }
 
     let self_profiler = if let SwitchWithOptPath::Enabled(ref d) = sopts.debugging_opts.self_profile
     {
@@ -1368,9 +1425,11 @@ pub fn build_session(
         CguReuseTracker::new_disabled()
     };
 
+    set_time_passes_crate_name(sopts.crate_name.clone());
     let prof = SelfProfilerRef::new(
         self_profiler,
-        sopts.debugging_opts.time_passes || sopts.debugging_opts.time,
+        (sopts.debugging_opts.time_passes || sopts.debugging_opts.time)
+            .then(|| sopts.debugging_opts.time_passes_format),
         sopts.debugging_opts.time_passes,
     );
 
@@ -1541,6 +1600,7 @@ pub fn early_error_no_abort(output: config::ErrorOutputType, msg: &str) {
         config::ErrorOutputType::Json { pretty, json_rendered } => {
             Box::new(JsonEmitter::basic(pretty, json_rendered, None, false))
         }
+        config::ErrorOutputType::Sarif => Box::new(SarifEmitter::basic()),
     };
     let handler = rustc_errors::Handler::with_emitter(true, None, emitter);
     handler.struct_fatal(msg).emit();
@@ -1560,6 +1620,7 @@ pub fn early_warn(output: config::ErrorOutputType, msg: &str) {
         config::ErrorOutputType::Json { pretty, json_rendered } => {
             Box::new(JsonEmitter::basic(pretty, json_rendered, None, false))
         }
+        config::ErrorOutputType::Sarif => Box::new(SarifEmitter::basic()),
     };
     let handler = rustc_errors::Handler::with_emitter(true, None, emitter);
     handler.struct_warn(msg).emit();
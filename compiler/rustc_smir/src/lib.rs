@@ -0,0 +1,167 @@
+//! A stable interface to the compiler for analysis tools.
+//!
+//! Tools built on `rustc_driver::Callbacks` use the types of `rustc_middle`, and
+//! break whenever they change, which is on almost every nightly. This crate
+//! runs the compiler like `rustc_driver` does, and gives the tool the items,
+//! types and MIR of the crate being compiled as the types of `stable_mir`,
+//! which only change in a breaking way with the major version of
+//! `stable_mir::VERSION`.
+//!
+//! ```ignore (needs the rustc_private feature)
+//! struct Tool;
+//!
+//! impl rustc_smir::Callbacks for Tool {
+//!     fn after_analysis(&mut self, cx: &rustc_smir::Context<'_>) -> rustc_smir::Compilation {
+//!         for item in cx.items() {
+//!             println!("{}: {} basic blocks", item.name, cx.body(&item).blocks.len());
+//!         }
+//!         rustc_smir::Compilation::Stop
+//!     }
+//! }
+//!
+//! fn main() {
+//!     let args = std::env::args().collect::<Vec<_>>();
+//!     std::process::exit(rustc_smir::run(&args, &mut Tool).map_or(1, |()| 0));
+//! }
+//! ```
+//!
+//! The `rustc_internal` module converts the types of the compiler to the stable
+//! ones, for tools which still use both. It is as unstable as the compiler.
+
+#![doc(html_root_url = "https://doc.rust-lang.org/nightly/nightly-rustc/")]
+#![feature(nll)]
+
+pub mod rustc_internal;
+pub mod stable_mir;
+
+use rustc_internal::{internal_def_id, Stable};
+use stable_mir::mir::Body;
+use stable_mir::ty::{FnSig, Ty};
+use stable_mir::{CrateItem, DefId, ItemKind};
+
+use rustc_hir::def::{CtorKind, DefKind};
+use rustc_hir::def_id::LOCAL_CRATE;
+use rustc_interface::interface;
+use rustc_interface::Queries;
+use rustc_middle::ty::TyCtxt;
+
+/// The compiler, after it has analyzed the crate.
+pub struct Context<'tcx> {
+    tcx: TyCtxt<'tcx>,
+}
+
+impl<'tcx> Context<'tcx> {
+    pub fn crate_name(&self) -> String {
+        self.tcx.crate_name(LOCAL_CRATE).to_string()
+    }
+
+    /// Returns the items of the local crate which have a body.
+    pub fn items(&self) -> Vec<CrateItem> {
+        let tcx = self.tcx;
+        tcx.body_owners()
+            .filter_map(|def_id| {
+                let kind = match tcx.def_kind(def_id) {
+                    DefKind::Fn => ItemKind::Fn,
+                    DefKind::AssocFn => ItemKind::AssocFn,
+                    DefKind::Closure => ItemKind::Closure,
+                    DefKind::Generator => ItemKind::Generator,
+                    DefKind::Const => ItemKind::Const,
+                    DefKind::AssocConst => ItemKind::AssocConst,
+                    DefKind::Static => ItemKind::Static,
+                    _ => return None,
+                };
+                let def_id = def_id.to_def_id();
+                Some(CrateItem {
+                    def_id: def_id.stable(tcx),
+                    name: tcx.def_path_str(def_id),
+                    kind,
+                    span: tcx.def_span(def_id).stable(tcx),
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the MIR of `item`: the optimized MIR of functions, closures and
+    /// generators, and the MIR which is evaluated for constants and statics.
+    pub fn body(&self, item: &CrateItem) -> Body {
+        let def_id = internal_def_id(item.def_id);
+        match item.kind {
+            ItemKind::Const | ItemKind::AssocConst | ItemKind::Static => {
+                self.tcx.mir_for_ctfe(def_id).stable(self.tcx)
+            }
+            _ => self.tcx.optimized_mir(def_id).stable(self.tcx),
+        }
+    }
+
+    /// Returns the path of `def_id`, like `std::ptr::read`.
+    pub fn def_path(&self, def_id: DefId) -> String {
+        self.tcx.def_path_str(internal_def_id(def_id))
+    }
+
+    /// Returns the type of `def_id`, which may be an item of any crate.
+    pub fn type_of(&self, def_id: DefId) -> Ty {
+        self.tcx.type_of(internal_def_id(def_id)).stable(self.tcx)
+    }
+
+    /// Returns the signature of `def_id` if it is a function, which may be an
+    /// item of any crate.
+    pub fn fn_sig(&self, def_id: DefId) -> Option<FnSig> {
+        let def_id = internal_def_id(def_id);
+        match self.tcx.def_kind(def_id) {
+            DefKind::Fn | DefKind::AssocFn | DefKind::Ctor(_, CtorKind::Fn) => {
+                Some(self.tcx.fn_sig(def_id).skip_binder().stable(self.tcx))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// The callbacks of a tool, which the compiler calls while it compiles the
+/// crate.
+pub trait Callbacks {
+    /// Called after the crate has been analyzed without errors, before code
+    /// generation.
+    fn after_analysis(&mut self, cx: &Context<'_>) -> Compilation;
+}
+
+/// Whether to stop or continue the compilation after a callback.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Compilation {
+    Stop,
+    Continue,
+}
+
+/// An error of the compilation, which has been reported already.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CompilerError;
+
+/// Runs the compiler with the command line `args`, which start with the name of
+/// the program, and calls `callbacks`.
+pub fn run(args: &[String], callbacks: &mut (dyn Callbacks + Send)) -> Result<(), CompilerError> {
+    let mut callbacks = DriverCallbacks { callbacks };
+    rustc_driver::catch_fatal_errors(|| rustc_driver::RunCompiler::new(args, &mut callbacks).run())
+        .and_then(|result| result)
+        .map_err(|_| CompilerError)
+}
+
+struct DriverCallbacks<'a> {
+    callbacks: &'a mut (dyn Callbacks + Send),
+}
+
+impl rustc_driver::Callbacks for DriverCallbacks<'_> {
+    fn after_analysis<'tcx>(
+        &mut self,
+        _compiler: &interface::Compiler,
+        queries: &'tcx Queries<'tcx>,
+    ) -> rustc_driver::Compilation {
+        let compilation = queries
+            .global_ctxt()
+            .unwrap()
+            .peek_mut()
+            .enter(|tcx| self.callbacks.after_analysis(&Context { tcx }));
+        match compilation {
+            Compilation::Stop => rustc_driver::Compilation::Stop,
+            Compilation::Continue => rustc_driver::Compilation::Continue,
+        }
+    }
+}
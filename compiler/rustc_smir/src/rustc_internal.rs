@@ -0,0 +1,435 @@
+//! The conversions between the types of the compiler and the ones of
+//! `stable_mir`.
+//!
+//! This module is as unstable as the compiler itself. It is meant for tools
+//! which move to `stable_mir` gradually, and still use the compiler's types
+//! for the rest.
+
+use crate::stable_mir::mir::{self, BasicBlockIdx};
+use crate::stable_mir::ty::{FloatTy, FnSig, IntTy, Mutability, Safety, Ty, UintTy};
+use crate::stable_mir::{self, LineCol};
+use crate::Context;
+
+use rustc_hir as hir;
+use rustc_hir::def_id::{CrateNum, DefId, DefIndex};
+use rustc_middle::mir::BasicBlock;
+use rustc_middle::ty::adjustment::PointerCast;
+use rustc_middle::ty::{self, ParamEnv, TyCtxt};
+
+/// Returns the context of `tcx`, for the functions of this crate which take one.
+pub fn context(tcx: TyCtxt<'_>) -> Context<'_> {
+    Context { tcx }
+}
+
+/// Returns the compiler's `DefId` of `def_id`.
+pub fn internal_def_id(def_id: stable_mir::DefId) -> DefId {
+    DefId { krate: CrateNum::from_u32(def_id.krate), index: DefIndex::from_u32(def_id.index) }
+}
+
+/// A type of the compiler which has a stable equivalent.
+pub trait Stable<'tcx> {
+    type T;
+
+    fn stable(&self, tcx: TyCtxt<'tcx>) -> Self::T;
+}
+
+impl<'tcx> Stable<'tcx> for DefId {
+    type T = stable_mir::DefId;
+
+    fn stable(&self, _: TyCtxt<'tcx>) -> Self::T {
+        stable_mir::DefId { krate: self.krate.as_u32(), index: self.index.as_u32() }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for rustc_span::Span {
+    type T = stable_mir::Span;
+
+    fn stable(&self, tcx: TyCtxt<'tcx>) -> Self::T {
+        let source_map = tcx.sess.source_map();
+        let lo = source_map.lookup_char_pos(self.lo());
+        let hi = source_map.lookup_char_pos(self.hi());
+        stable_mir::Span {
+            file: lo.file.name.prefer_local().to_string(),
+            lo: LineCol { line: lo.line, col: lo.col.0 + 1 },
+            hi: LineCol { line: hi.line, col: hi.col.0 + 1 },
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for ty::Ty<'tcx> {
+    type T = Ty;
+
+    fn stable(&self, tcx: TyCtxt<'tcx>) -> Self::T {
+        let types = |substs: ty::subst::SubstsRef<'tcx>| {
+            substs.types().map(|ty| ty.stable(tcx)).collect::<Vec<_>>()
+        };
+        match *self.kind() {
+            ty::Bool => Ty::Bool,
+            ty::Char => Ty::Char,
+            ty::Int(int_ty) => Ty::Int(match int_ty {
+                ty::IntTy::Isize => IntTy::Isize,
+                ty::IntTy::I8 => IntTy::I8,
+                ty::IntTy::I16 => IntTy::I16,
+                ty::IntTy::I32 => IntTy::I32,
+                ty::IntTy::I64 => IntTy::I64,
+                ty::IntTy::I128 => IntTy::I128,
+            }),
+            ty::Uint(uint_ty) => Ty::Uint(match uint_ty {
+                ty::UintTy::Usize => UintTy::Usize,
+                ty::UintTy::U8 => UintTy::U8,
+                ty::UintTy::U16 => UintTy::U16,
+                ty::UintTy::U32 => UintTy::U32,
+                ty::UintTy::U64 => UintTy::U64,
+                ty::UintTy::U128 => UintTy::U128,
+            }),
+            ty::Float(float_ty) => Ty::Float(match float_ty {
+                ty::FloatTy::F32 => FloatTy::F32,
+                ty::FloatTy::F64 => FloatTy::F64,
+            }),
+            ty::Str => Ty::Str,
+            ty::Adt(adt_def, substs) => Ty::Adt(adt_def.did.stable(tcx), types(substs)),
+            ty::Foreign(def_id) => Ty::Foreign(def_id.stable(tcx)),
+            ty::Array(ty, len) => {
+                Ty::Array(Box::new(ty.stable(tcx)), len.try_eval_usize(tcx, ParamEnv::reveal_all()))
+            }
+            ty::Slice(ty) => Ty::Slice(Box::new(ty.stable(tcx))),
+            ty::RawPtr(ty::TypeAndMut { ty, mutbl }) => {
+                Ty::RawPtr(Box::new(ty.stable(tcx)), mutbl.stable(tcx))
+            }
+            ty::Ref(_, ty, mutbl) => Ty::Ref(Box::new(ty.stable(tcx)), mutbl.stable(tcx)),
+            ty::FnDef(def_id, substs) => Ty::FnDef(def_id.stable(tcx), types(substs)),
+            ty::FnPtr(sig) => Ty::FnPtr(Box::new(sig.skip_binder().stable(tcx))),
+            ty::Dynamic(predicates, _) => {
+                Ty::Dynamic(predicates.principal_def_id().map(|def_id| def_id.stable(tcx)))
+            }
+            ty::Closure(def_id, _) => Ty::Closure(def_id.stable(tcx)),
+            ty::Generator(def_id, ..) => Ty::Generator(def_id.stable(tcx)),
+            ty::Never => Ty::Never,
+            ty::Tuple(substs) => Ty::Tuple(types(substs)),
+            ty::Param(param) => Ty::Param(param.name.to_string()),
+            _ => Ty::Unsupported(self.to_string()),
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for hir::Mutability {
+    type T = Mutability;
+
+    fn stable(&self, _: TyCtxt<'tcx>) -> Self::T {
+        match self {
+            hir::Mutability::Not => Mutability::Not,
+            hir::Mutability::Mut => Mutability::Mut,
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for ty::FnSig<'tcx> {
+    type T = FnSig;
+
+    fn stable(&self, tcx: TyCtxt<'tcx>) -> Self::T {
+        FnSig {
+            inputs: self.inputs().iter().map(|ty| ty.stable(tcx)).collect(),
+            output: self.output().stable(tcx),
+            c_variadic: self.c_variadic,
+            safety: match self.unsafety {
+                hir::Unsafety::Normal => Safety::Safe,
+                hir::Unsafety::Unsafe => Safety::Unsafe,
+            },
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for rustc_middle::mir::Body<'tcx> {
+    type T = mir::Body;
+
+    fn stable(&self, tcx: TyCtxt<'tcx>) -> Self::T {
+        mir::Body {
+            blocks: self
+                .basic_blocks()
+                .iter()
+                .map(|block| mir::BasicBlock {
+                    statements: block.statements.iter().map(|stmt| stmt.stable(tcx)).collect(),
+                    terminator: block.terminator().stable(tcx),
+                    is_cleanup: block.is_cleanup,
+                })
+                .collect(),
+            locals: self.local_decls.iter().map(|decl| decl.ty.stable(tcx)).collect(),
+            arg_count: self.arg_count,
+            span: self.span.stable(tcx),
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for rustc_middle::mir::Statement<'tcx> {
+    type T = mir::Statement;
+
+    fn stable(&self, tcx: TyCtxt<'tcx>) -> Self::T {
+        use rustc_middle::mir::StatementKind::*;
+        let kind = match &self.kind {
+            Assign(assign) => {
+                mir::StatementKind::Assign(assign.0.stable(tcx), assign.1.stable(tcx))
+            }
+            SetDiscriminant { place, variant_index } => mir::StatementKind::SetDiscriminant {
+                place: place.stable(tcx),
+                variant_index: variant_index.as_usize(),
+            },
+            StorageLive(local) => mir::StatementKind::StorageLive(local.as_usize()),
+            StorageDead(local) => mir::StatementKind::StorageDead(local.as_usize()),
+            LlvmInlineAsm(_) => mir::StatementKind::InlineAsm,
+            CopyNonOverlapping(copy) => mir::StatementKind::CopyNonOverlapping {
+                src: copy.src.stable(tcx),
+                dst: copy.dst.stable(tcx),
+                count: copy.count.stable(tcx),
+            },
+            FakeRead(..) | Retag(..) | AscribeUserType(..) | Coverage(..) | Nop => {
+                mir::StatementKind::Nop
+            }
+        };
+        mir::Statement { kind, span: self.source_info.span.stable(tcx) }
+    }
+}
+
+fn block(block: &BasicBlock) -> BasicBlockIdx {
+    block.as_usize()
+}
+
+impl<'tcx> Stable<'tcx> for rustc_middle::mir::Terminator<'tcx> {
+    type T = mir::Terminator;
+
+    fn stable(&self, tcx: TyCtxt<'tcx>) -> Self::T {
+        use rustc_middle::mir::TerminatorKind::*;
+        let kind = match &self.kind {
+            Goto { target } => mir::TerminatorKind::Goto { target: block(target) },
+            SwitchInt { discr, targets, .. } => mir::TerminatorKind::SwitchInt {
+                discr: discr.stable(tcx),
+                targets: targets.iter().map(|(value, target)| (value, block(&target))).collect(),
+                otherwise: block(&targets.otherwise()),
+            },
+            Resume => mir::TerminatorKind::Resume,
+            Abort => mir::TerminatorKind::Abort,
+            Return => mir::TerminatorKind::Return,
+            Unreachable => mir::TerminatorKind::Unreachable,
+            Drop { place, target, unwind } => mir::TerminatorKind::Drop {
+                place: place.stable(tcx),
+                target: block(target),
+                unwind: unwind.as_ref().map(block),
+            },
+            DropAndReplace { place, value, target, unwind } => {
+                mir::TerminatorKind::DropAndReplace {
+                    place: place.stable(tcx),
+                    value: value.stable(tcx),
+                    target: block(target),
+                    unwind: unwind.as_ref().map(block),
+                }
+            }
+            Call { func, args, destination, cleanup, .. } => mir::TerminatorKind::Call {
+                func: func.stable(tcx),
+                args: args.iter().map(|arg| arg.stable(tcx)).collect(),
+                destination: destination
+                    .as_ref()
+                    .map(|(place, target)| (place.stable(tcx), block(target))),
+                cleanup: cleanup.as_ref().map(block),
+            },
+            Assert { cond, expected, msg, target, cleanup } => mir::TerminatorKind::Assert {
+                cond: cond.stable(tcx),
+                expected: *expected,
+                msg: format!("{:?}", msg),
+                target: block(target),
+                cleanup: cleanup.as_ref().map(block),
+            },
+            Yield { value, resume, resume_arg, drop } => mir::TerminatorKind::Yield {
+                value: value.stable(tcx),
+                resume: block(resume),
+                resume_arg: resume_arg.stable(tcx),
+                drop: drop.as_ref().map(block),
+            },
+            GeneratorDrop => mir::TerminatorKind::GeneratorDrop,
+            FalseEdge { real_target, imaginary_target } => mir::TerminatorKind::FalseEdge {
+                real_target: block(real_target),
+                imaginary_target: block(imaginary_target),
+            },
+            FalseUnwind { real_target, unwind } => mir::TerminatorKind::FalseUnwind {
+                real_target: block(real_target),
+                unwind: unwind.as_ref().map(block),
+            },
+            InlineAsm { destination, .. } => {
+                mir::TerminatorKind::InlineAsm { destination: destination.as_ref().map(block) }
+            }
+        };
+        mir::Terminator { kind, span: self.source_info.span.stable(tcx) }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for rustc_middle::mir::Place<'tcx> {
+    type T = mir::Place;
+
+    fn stable(&self, tcx: TyCtxt<'tcx>) -> Self::T {
+        use rustc_middle::mir::ProjectionElem::*;
+        mir::Place {
+            local: self.local.as_usize(),
+            projection: self
+                .projection
+                .iter()
+                .map(|elem| match elem {
+                    Deref => mir::ProjectionElem::Deref,
+                    Field(field, ty) => {
+                        mir::ProjectionElem::Field(field.as_usize(), ty.stable(tcx))
+                    }
+                    Index(local) => mir::ProjectionElem::Index(local.as_usize()),
+                    ConstantIndex { offset, min_length, from_end } => {
+                        mir::ProjectionElem::ConstantIndex { offset, min_length, from_end }
+                    }
+                    Subslice { from, to, from_end } => {
+                        mir::ProjectionElem::Subslice { from, to, from_end }
+                    }
+                    Downcast(_, variant) => mir::ProjectionElem::Downcast(variant.as_usize()),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for rustc_middle::mir::Operand<'tcx> {
+    type T = mir::Operand;
+
+    fn stable(&self, tcx: TyCtxt<'tcx>) -> Self::T {
+        use rustc_middle::mir::Operand::*;
+        match self {
+            Copy(place) => mir::Operand::Copy(place.stable(tcx)),
+            Move(place) => mir::Operand::Move(place.stable(tcx)),
+            Constant(constant) => mir::Operand::Constant(mir::Constant {
+                ty: constant.ty().stable(tcx),
+                value: constant.literal.to_string(),
+                span: constant.span.stable(tcx),
+            }),
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for rustc_middle::mir::Rvalue<'tcx> {
+    type T = mir::Rvalue;
+
+    fn stable(&self, tcx: TyCtxt<'tcx>) -> Self::T {
+        use rustc_middle::mir::Rvalue::*;
+        match self {
+            Use(operand) => mir::Rvalue::Use(operand.stable(tcx)),
+            Repeat(operand, count) => mir::Rvalue::Repeat(
+                operand.stable(tcx),
+                count.try_eval_usize(tcx, ParamEnv::reveal_all()),
+            ),
+            Ref(_, kind, place) => mir::Rvalue::Ref(kind.stable(tcx), place.stable(tcx)),
+            ThreadLocalRef(def_id) => mir::Rvalue::ThreadLocalRef(def_id.stable(tcx)),
+            AddressOf(mutbl, place) => mir::Rvalue::AddressOf(mutbl.stable(tcx), place.stable(tcx)),
+            Len(place) => mir::Rvalue::Len(place.stable(tcx)),
+            Cast(kind, operand, ty) => {
+                mir::Rvalue::Cast(kind.stable(tcx), operand.stable(tcx), ty.stable(tcx))
+            }
+            BinaryOp(op, operands) => mir::Rvalue::BinaryOp(
+                op.stable(tcx),
+                operands.0.stable(tcx),
+                operands.1.stable(tcx),
+            ),
+            CheckedBinaryOp(op, operands) => mir::Rvalue::CheckedBinaryOp(
+                op.stable(tcx),
+                operands.0.stable(tcx),
+                operands.1.stable(tcx),
+            ),
+            NullaryOp(op, ty) => mir::Rvalue::NullaryOp(
+                match op {
+                    rustc_middle::mir::NullOp::SizeOf => mir::NullOp::SizeOf,
+                    rustc_middle::mir::NullOp::Box => mir::NullOp::Box,
+                },
+                ty.stable(tcx),
+            ),
+            UnaryOp(op, operand) => mir::Rvalue::UnaryOp(
+                match op {
+                    rustc_middle::mir::UnOp::Not => mir::UnOp::Not,
+                    rustc_middle::mir::UnOp::Neg => mir::UnOp::Neg,
+                },
+                operand.stable(tcx),
+            ),
+            Discriminant(place) => mir::Rvalue::Discriminant(place.stable(tcx)),
+            Aggregate(kind, operands) => mir::Rvalue::Aggregate(
+                kind.stable(tcx),
+                operands.iter().map(|operand| operand.stable(tcx)).collect(),
+            ),
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for rustc_middle::mir::BorrowKind {
+    type T = mir::BorrowKind;
+
+    fn stable(&self, _: TyCtxt<'tcx>) -> Self::T {
+        use rustc_middle::mir::BorrowKind::*;
+        match self {
+            Shared => mir::BorrowKind::Shared,
+            Shallow => mir::BorrowKind::Shallow,
+            Unique => mir::BorrowKind::Unique,
+            Mut { .. } => mir::BorrowKind::Mut,
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for rustc_middle::mir::CastKind {
+    type T = mir::CastKind;
+
+    fn stable(&self, _: TyCtxt<'tcx>) -> Self::T {
+        use rustc_middle::mir::CastKind::*;
+        match self {
+            Misc => mir::CastKind::Misc,
+            Pointer(PointerCast::ReifyFnPointer) => mir::CastKind::ReifyFnPointer,
+            Pointer(PointerCast::UnsafeFnPointer) => mir::CastKind::UnsafeFnPointer,
+            Pointer(PointerCast::ClosureFnPointer(_)) => mir::CastKind::ClosureFnPointer,
+            Pointer(PointerCast::MutToConstPointer) => mir::CastKind::MutToConstPointer,
+            Pointer(PointerCast::ArrayToPointer) => mir::CastKind::ArrayToPointer,
+            Pointer(PointerCast::Unsize) => mir::CastKind::Unsize,
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for rustc_middle::mir::BinOp {
+    type T = mir::BinOp;
+
+    fn stable(&self, _: TyCtxt<'tcx>) -> Self::T {
+        use rustc_middle::mir::BinOp::*;
+        match self {
+            Add => mir::BinOp::Add,
+            Sub => mir::BinOp::Sub,
+            Mul => mir::BinOp::Mul,
+            Div => mir::BinOp::Div,
+            Rem => mir::BinOp::Rem,
+            BitXor => mir::BinOp::BitXor,
+            BitAnd => mir::BinOp::BitAnd,
+            BitOr => mir::BinOp::BitOr,
+            Shl => mir::BinOp::Shl,
+            Shr => mir::BinOp::Shr,
+            Eq => mir::BinOp::Eq,
+            Lt => mir::BinOp::Lt,
+            Le => mir::BinOp::Le,
+            Ne => mir::BinOp::Ne,
+            Ge => mir::BinOp::Ge,
+            Gt => mir::BinOp::Gt,
+            Offset => mir::BinOp::Offset,
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for rustc_middle::mir::AggregateKind<'tcx> {
+    type T = mir::AggregateKind;
+
+    fn stable(&self, tcx: TyCtxt<'tcx>) -> Self::T {
+        use rustc_middle::mir::AggregateKind::*;
+        match self {
+            Array(ty) => mir::AggregateKind::Array(ty.stable(tcx)),
+            Tuple => mir::AggregateKind::Tuple,
+            Adt(adt_def, variant, substs, ..) => mir::AggregateKind::Adt(
+                adt_def.did.stable(tcx),
+                variant.as_usize(),
+                substs.types().map(|ty| ty.stable(tcx)).collect(),
+            ),
+            Closure(def_id, _) => mir::AggregateKind::Closure(def_id.stable(tcx)),
+            Generator(def_id, ..) => mir::AggregateKind::Generator(def_id.stable(tcx)),
+        }
+    }
+}
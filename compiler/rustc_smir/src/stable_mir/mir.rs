@@ -0,0 +1,247 @@
+use super::ty::{Mutability, Ty};
+use super::{DefId, Span};
+
+/// The index of a local in `Body::locals`.
+pub type Local = usize;
+
+/// The index of a basic block in `Body::blocks`.
+pub type BasicBlockIdx = usize;
+
+/// The MIR of a function, closure, constant or static.
+#[derive(Clone, Debug)]
+pub struct Body {
+    pub blocks: Vec<BasicBlock>,
+    /// The types of the locals: the return place first, then the `arg_count`
+    /// arguments, then the other variables and temporaries.
+    pub locals: Vec<Ty>,
+    pub arg_count: usize,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug)]
+pub struct BasicBlock {
+    pub statements: Vec<Statement>,
+    pub terminator: Terminator,
+    /// Whether the block only runs while unwinding.
+    pub is_cleanup: bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct Statement {
+    pub kind: StatementKind,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub enum StatementKind {
+    Assign(Place, Rvalue),
+    SetDiscriminant {
+        place: Place,
+        variant_index: usize,
+    },
+    StorageLive(Local),
+    StorageDead(Local),
+    /// `llvm_asm!`.
+    InlineAsm,
+    CopyNonOverlapping {
+        src: Operand,
+        dst: Operand,
+        count: Operand,
+    },
+    /// A statement which does nothing when the code runs, like the ones the
+    /// compiler only uses for borrow checking.
+    Nop,
+}
+
+#[derive(Clone, Debug)]
+pub struct Terminator {
+    pub kind: TerminatorKind,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub enum TerminatorKind {
+    Goto {
+        target: BasicBlockIdx,
+    },
+    SwitchInt {
+        discr: Operand,
+        targets: Vec<(u128, BasicBlockIdx)>,
+        otherwise: BasicBlockIdx,
+    },
+    Resume,
+    Abort,
+    Return,
+    Unreachable,
+    Drop {
+        place: Place,
+        target: BasicBlockIdx,
+        unwind: Option<BasicBlockIdx>,
+    },
+    DropAndReplace {
+        place: Place,
+        value: Operand,
+        target: BasicBlockIdx,
+        unwind: Option<BasicBlockIdx>,
+    },
+    Call {
+        func: Operand,
+        args: Vec<Operand>,
+        destination: Option<(Place, BasicBlockIdx)>,
+        cleanup: Option<BasicBlockIdx>,
+    },
+    Assert {
+        cond: Operand,
+        expected: bool,
+        msg: String,
+        target: BasicBlockIdx,
+        cleanup: Option<BasicBlockIdx>,
+    },
+    Yield {
+        value: Operand,
+        resume: BasicBlockIdx,
+        resume_arg: Place,
+        drop: Option<BasicBlockIdx>,
+    },
+    GeneratorDrop,
+    FalseEdge {
+        real_target: BasicBlockIdx,
+        imaginary_target: BasicBlockIdx,
+    },
+    FalseUnwind {
+        real_target: BasicBlockIdx,
+        unwind: Option<BasicBlockIdx>,
+    },
+    /// `asm!`.
+    InlineAsm {
+        destination: Option<BasicBlockIdx>,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Place {
+    pub local: Local,
+    pub projection: Vec<ProjectionElem>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ProjectionElem {
+    Deref,
+    /// A field, with its type.
+    Field(usize, Ty),
+    /// An index, given by a local.
+    Index(Local),
+    ConstantIndex {
+        offset: u64,
+        min_length: u64,
+        from_end: bool,
+    },
+    Subslice {
+        from: u64,
+        to: u64,
+        from_end: bool,
+    },
+    /// The downcast of an enum to one of its variants.
+    Downcast(usize),
+}
+
+#[derive(Clone, Debug)]
+pub enum Operand {
+    Copy(Place),
+    Move(Place),
+    Constant(Constant),
+}
+
+#[derive(Clone, Debug)]
+pub struct Constant {
+    pub ty: Ty,
+    /// The value, as printed by the compiler.
+    pub value: String,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub enum Rvalue {
+    Use(Operand),
+    /// `[x; n]`, with `n` if it is known.
+    Repeat(Operand, Option<u64>),
+    Ref(BorrowKind, Place),
+    ThreadLocalRef(DefId),
+    AddressOf(Mutability, Place),
+    Len(Place),
+    Cast(CastKind, Operand, Ty),
+    BinaryOp(BinOp, Operand, Operand),
+    /// A binary operation which also returns whether it overflowed.
+    CheckedBinaryOp(BinOp, Operand, Operand),
+    NullaryOp(NullOp, Ty),
+    UnaryOp(UnOp, Operand),
+    Discriminant(Place),
+    Aggregate(AggregateKind, Vec<Operand>),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BorrowKind {
+    Shared,
+    Shallow,
+    Unique,
+    Mut,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum CastKind {
+    Misc,
+    ReifyFnPointer,
+    UnsafeFnPointer,
+    ClosureFnPointer,
+    MutToConstPointer,
+    ArrayToPointer,
+    Unsize,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BinOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    BitXor,
+    BitAnd,
+    BitOr,
+    Shl,
+    Shr,
+    Eq,
+    Lt,
+    Le,
+    Ne,
+    Ge,
+    Gt,
+    Offset,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum NullOp {
+    SizeOf,
+    Box,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum UnOp {
+    Not,
+    Neg,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum AggregateKind {
+    Array(Ty),
+    Tuple,
+    /// A struct, enum or union, with the index of its variant and its generic
+    /// arguments.
+    Adt(DefId, usize, Vec<Ty>),
+    Closure(DefId),
+    Generator(DefId),
+}
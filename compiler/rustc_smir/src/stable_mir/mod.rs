@@ -0,0 +1,57 @@
+//! The stable types of the compiler's items, types and MIR.
+//!
+//! These types are plain data owned by the tool: they don't borrow from the
+//! compiler, and only refer to other definitions with `DefId`s, which the
+//! `Context` they came from can look up.
+
+pub mod mir;
+pub mod ty;
+
+/// The version of this API. A new major version may break tools, while a new
+/// minor version only adds items, or variants to the `#[non_exhaustive]` enums.
+pub const VERSION: (u32, u32) = (0, 1);
+
+/// The identifier of a definition, valid in the compilation it came from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DefId {
+    pub(crate) krate: u32,
+    pub(crate) index: u32,
+}
+
+/// An item of the local crate which has a body.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CrateItem {
+    pub def_id: DefId,
+    /// The path of the item, like `module::Type::method`.
+    pub name: String,
+    pub kind: ItemKind,
+    pub span: Span,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ItemKind {
+    Fn,
+    /// A method, or another function of a trait or impl.
+    AssocFn,
+    Closure,
+    Generator,
+    Const,
+    AssocConst,
+    Static,
+}
+
+/// A range of source code. Lines and columns start at 1, and columns count
+/// characters.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Span {
+    pub file: String,
+    pub lo: LineCol,
+    pub hi: LineCol,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LineCol {
+    pub line: usize,
+    pub col: usize,
+}
@@ -0,0 +1,82 @@
+use super::DefId;
+
+/// A type. Lifetimes are erased, and so are the generic arguments which aren't
+/// types.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Ty {
+    Bool,
+    Char,
+    Int(IntTy),
+    Uint(UintTy),
+    Float(FloatTy),
+    Str,
+    /// A struct, enum or union, with its generic arguments.
+    Adt(DefId, Vec<Ty>),
+    Foreign(DefId),
+    /// An array, with its length if it is known.
+    Array(Box<Ty>, Option<u64>),
+    Slice(Box<Ty>),
+    RawPtr(Box<Ty>, Mutability),
+    Ref(Box<Ty>, Mutability),
+    /// The type of a function item, with its generic arguments.
+    FnDef(DefId, Vec<Ty>),
+    FnPtr(Box<FnSig>),
+    /// A trait object, with its principal trait if it has one.
+    Dynamic(Option<DefId>),
+    Closure(DefId),
+    Generator(DefId),
+    Never,
+    Tuple(Vec<Ty>),
+    /// A generic parameter, with its name.
+    Param(String),
+    /// A type which this API doesn't describe yet, as printed by the compiler.
+    Unsupported(String),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum IntTy {
+    Isize,
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum UintTy {
+    Usize,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FloatTy {
+    F32,
+    F64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Mutability {
+    Not,
+    Mut,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Safety {
+    Safe,
+    Unsafe,
+}
+
+/// The signature of a function, with late-bound lifetimes erased.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FnSig {
+    pub inputs: Vec<Ty>,
+    pub output: Ty,
+    pub c_variadic: bool,
+    pub safety: Safety,
+}
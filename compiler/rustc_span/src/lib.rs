@@ -419,6 +419,77 @@ fn cmp(&self, rhs: &Self) -> Ordering {
     }
 }
 
+/// The text of a diagnostic message or label.
+///
+/// Messages are either given as strings, or referred to by an identifier in
+/// the message catalogues of `rustc_errors`, in which case they are translated
+/// into the requested language when the diagnostic is emitted.
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Encodable, Decodable)]
+pub enum DiagnosticMessage {
+    /// A message that is not translated, usually built with `format!`.
+    Str(String),
+    /// The identifier of a message in the catalogues, and optionally the name
+    /// of one of the attributes of that message.
+    FluentIdentifier(String, Option<String>),
+}
+
+impl DiagnosticMessage {
+    /// Refers to the message with the given identifier.
+    pub fn fluent(id: &str) -> DiagnosticMessage {
+        DiagnosticMessage::FluentIdentifier(id.to_owned(), None)
+    }
+
+    /// Refers to the attribute `attr` of this message.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this message is a string.
+    pub fn attr(&self, attr: &str) -> DiagnosticMessage {
+        match self {
+            DiagnosticMessage::FluentIdentifier(id, _) => {
+                DiagnosticMessage::FluentIdentifier(id.clone(), Some(attr.to_owned()))
+            }
+            DiagnosticMessage::Str(_) => panic!("cannot refer to an attribute of a string"),
+        }
+    }
+
+    /// Returns the text of the message if it is a string.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            DiagnosticMessage::Str(s) => Some(s),
+            DiagnosticMessage::FluentIdentifier(..) => None,
+        }
+    }
+
+    /// Returns the text of the message, or the identifier it refers to if it
+    /// has not been translated yet.
+    pub fn as_str_or_identifier(&self) -> &str {
+        match self {
+            DiagnosticMessage::Str(s) | DiagnosticMessage::FluentIdentifier(s, _) => s,
+        }
+    }
+}
+
+impl From<String> for DiagnosticMessage {
+    fn from(s: String) -> DiagnosticMessage {
+        DiagnosticMessage::Str(s)
+    }
+}
+
+impl<'a> From<Cow<'a, str>> for DiagnosticMessage {
+    fn from(s: Cow<'a, str>) -> DiagnosticMessage {
+        DiagnosticMessage::Str(s.into_owned())
+    }
+}
+
+// This covers `&str` and `&String`, and everything else that used to be passed
+// to diagnostic methods taking a `&str` by deref coercion.
+impl<'a, T: ?Sized + AsRef<str>> From<&'a T> for DiagnosticMessage {
+    fn from(s: &'a T) -> DiagnosticMessage {
+        DiagnosticMessage::Str(s.as_ref().to_owned())
+    }
+}
+
 /// A collection of `Span`s.
 ///
 /// Spans have two orthogonal attributes:
@@ -430,7 +501,7 @@ fn cmp(&self, rhs: &Self) -> Ordering {
 #[derive(Clone, Debug, Hash, PartialEq, Eq, Encodable, Decodable)]
 pub struct MultiSpan {
     primary_spans: Vec<Span>,
-    span_labels: Vec<(Span, String)>,
+    span_labels: Vec<(Span, DiagnosticMessage)>,
 }
 
 impl Span {
@@ -920,8 +991,8 @@ pub fn from_spans(mut vec: Vec<Span>) -> MultiSpan {
         MultiSpan { primary_spans: vec, span_labels: vec![] }
     }
 
-    pub fn push_span_label(&mut self, span: Span, label: String) {
-        self.span_labels.push((span, label));
+    pub fn push_span_label(&mut self, span: Span, label: impl Into<DiagnosticMessage>) {
+        self.span_labels.push((span, label.into()));
     }
 
     /// Selects the first primary span (if any).
@@ -974,6 +1045,9 @@ pub fn replace(&mut self, before: Span, after: Span) -> bool {
     /// span `P`, if there is at least one label with span `P`, we return
     /// those labels (marked as primary). But otherwise we return
     /// `SpanLabel` instances with empty labels.
+    ///
+    /// Labels that have not been translated yet are returned as the
+    /// identifier of their message.
     pub fn span_labels(&self) -> Vec<SpanLabel> {
         let is_primary = |span| self.primary_spans.contains(&span);
 
@@ -983,7 +1057,7 @@ pub fn span_labels(&self) -> Vec<SpanLabel> {
             .map(|&(span, ref label)| SpanLabel {
                 span,
                 is_primary: is_primary(span),
-                label: Some(label.clone()),
+                label: Some(label.as_str_or_identifier().to_owned()),
             })
             .collect::<Vec<_>>();
 
@@ -1000,6 +1074,17 @@ pub fn span_labels(&self) -> Vec<SpanLabel> {
     pub fn has_span_labels(&self) -> bool {
         self.span_labels.iter().any(|(sp, _)| !sp.is_dummy())
     }
+
+    /// Returns the spans and messages of the labels, in the order they were
+    /// added.
+    pub fn span_label_messages(&self) -> impl Iterator<Item = (Span, &DiagnosticMessage)> {
+        self.span_labels.iter().map(|(span, label)| (*span, label))
+    }
+
+    /// Returns the messages of the labels, to translate them.
+    pub fn span_label_messages_mut(&mut self) -> impl Iterator<Item = &mut DiagnosticMessage> {
+        self.span_labels.iter_mut().map(|(_, label)| label)
+    }
 }
 
 impl From<Span> for MultiSpan {
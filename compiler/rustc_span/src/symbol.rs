@@ -465,6 +465,7 @@
         deref_mut,
         deref_target,
         derive,
+        descriptors,
         destructuring_assignment,
         diagnostic,
         direct,
@@ -1211,6 +1212,7 @@
         test_2018_feature,
         test_accepted_feature,
         test_case,
+        test_ignore_cfg,
         test_removed_feature,
         test_runner,
         then_with,
@@ -1817,6 +1819,13 @@ fn deref(&self) -> &str {
     }
 }
 
+impl AsRef<str> for SymbolStr {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.string
+    }
+}
+
 impl fmt::Debug for SymbolStr {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         fmt::Debug::fmt(self.string, f)
@@ -1,5 +1,5 @@
 use crate::structured_errors::StructuredDiagnostic;
-use rustc_errors::{Applicability, DiagnosticBuilder, DiagnosticId};
+use rustc_errors::{Applicability, DiagnosticBuilder, DiagnosticId, DiagnosticMessage};
 use rustc_middle::ty::{Ty, TypeFoldable};
 use rustc_session::Session;
 use rustc_span::Span;
@@ -11,6 +11,12 @@ pub struct MissingCastForVariadicArg<'tcx> {
     pub cast_ty: &'tcx str,
 }
 
+impl<'tcx> MissingCastForVariadicArg<'tcx> {
+    fn message() -> DiagnosticMessage {
+        DiagnosticMessage::fluent("typeck-missing-cast-for-variadic-arg")
+    }
+}
+
 impl<'tcx> StructuredDiagnostic<'tcx> for MissingCastForVariadicArg<'tcx> {
     fn session(&self) -> &Session {
         self.sess
@@ -24,34 +30,27 @@ fn diagnostic_common(&self) -> DiagnosticBuilder<'tcx> {
         let mut err = if self.ty.references_error() {
             self.sess.diagnostic().struct_dummy()
         } else {
-            self.sess.struct_span_fatal_with_code(
-                self.span,
-                &format!("can't pass `{}` to variadic function", self.ty),
-                self.code(),
-            )
+            self.sess.struct_span_fatal_with_code(self.span, Self::message(), self.code())
         };
+        err.set_arg("ty", self.ty);
+        err.set_arg("cast_ty", self.cast_ty);
 
         if let Ok(snippet) = self.sess.source_map().span_to_snippet(self.span) {
             err.span_suggestion(
                 self.span,
-                &format!("cast the value to `{}`", self.cast_ty),
+                Self::message().attr("suggestion"),
                 format!("{} as {}", snippet, self.cast_ty),
                 Applicability::MachineApplicable,
             );
         } else {
-            err.help(&format!("cast the value to `{}`", self.cast_ty));
+            err.help(Self::message().attr("help"));
         }
 
         err
     }
 
     fn diagnostic_extended(&self, mut err: DiagnosticBuilder<'tcx>) -> DiagnosticBuilder<'tcx> {
-        err.note(&format!(
-            "certain types, like `{}`, must be casted before passing them to a \
-                variadic function, because of arcane ABI rules dictated by the C \
-                standard",
-            self.ty
-        ));
+        err.note(Self::message().attr("note"));
 
         err
     }
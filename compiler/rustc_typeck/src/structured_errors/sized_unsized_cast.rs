@@ -1,5 +1,5 @@
 use crate::structured_errors::StructuredDiagnostic;
-use rustc_errors::{DiagnosticBuilder, DiagnosticId};
+use rustc_errors::{DiagnosticBuilder, DiagnosticId, DiagnosticMessage};
 use rustc_middle::ty::{Ty, TypeFoldable};
 use rustc_session::Session;
 use rustc_span::Span;
@@ -21,37 +21,22 @@ fn code(&self) -> DiagnosticId {
     }
 
     fn diagnostic_common(&self) -> DiagnosticBuilder<'tcx> {
-        if self.expr_ty.references_error() {
+        let mut err = if self.expr_ty.references_error() {
             self.sess.diagnostic().struct_dummy()
         } else {
             self.sess.struct_span_fatal_with_code(
                 self.span,
-                &format!(
-                    "cannot cast thin pointer `{}` to fat pointer `{}`",
-                    self.expr_ty, self.cast_ty
-                ),
+                DiagnosticMessage::fluent("typeck-sized-unsized-cast"),
                 self.code(),
             )
-        }
+        };
+        err.set_arg("expr_ty", self.expr_ty);
+        err.set_arg("cast_ty", self.cast_ty.as_str());
+        err
     }
 
     fn diagnostic_extended(&self, mut err: DiagnosticBuilder<'tcx>) -> DiagnosticBuilder<'tcx> {
-        err.help(
-            "Thin pointers are \"simple\" pointers: they are purely a reference to a
-memory address.
-
-Fat pointers are pointers referencing \"Dynamically Sized Types\" (also
-called DST). DST don't have a statically known size, therefore they can
-only exist behind some kind of pointers that contain additional
-information. Slices and trait objects are DSTs. In the case of slices,
-the additional information the fat pointer holds is their size.
-
-To fix this error, don't try to cast directly between thin and fat
-pointers.
-
-For more information about casts, take a look at The Book:
-https://doc.rust-lang.org/reference/expressions/operator-expr.html#type-cast-expressions",
-        );
+        err.help(DiagnosticMessage::fluent("typeck-sized-unsized-cast").attr("help"));
         err
     }
 }
@@ -1,5 +1,5 @@
 use crate::structured_errors::StructuredDiagnostic;
-use rustc_errors::{pluralize, Applicability, DiagnosticBuilder, DiagnosticId};
+use rustc_errors::{Applicability, DiagnosticBuilder, DiagnosticId, DiagnosticMessage};
 use rustc_hir as hir;
 use rustc_middle::middle::resolve_lifetime::LifetimeScopeForPath;
 use rustc_middle::ty::{self as ty, TyCtxt};
@@ -257,26 +257,27 @@ fn get_num_default_params(&self) -> usize {
         }
     }
 
-    // Helper function to choose a quantifier word for the number of expected arguments
-    // and to give a bound for the number of expected arguments
+    // Helper function to choose a quantifier for the number of expected arguments and to give
+    // a bound for the number of expected arguments. The quantifier is one of the variant keys
+    // of `typeck-quantifier`.
     fn get_quantifier_and_bound(&self) -> (&'static str, usize) {
         if self.get_num_default_params() == 0 {
             match self.gen_args_info {
                 MissingLifetimes { .. } | ExcessLifetimes { .. } => {
-                    ("", self.num_expected_lifetime_args())
+                    ("exactly", self.num_expected_lifetime_args())
                 }
                 MissingTypesOrConsts { .. } | ExcessTypesOrConsts { .. } => {
-                    ("", self.num_expected_type_or_const_args())
+                    ("exactly", self.num_expected_type_or_const_args())
                 }
             }
         } else {
             match self.gen_args_info {
-                MissingLifetimes { .. } => ("at least ", self.num_expected_lifetime_args()),
+                MissingLifetimes { .. } => ("at-least", self.num_expected_lifetime_args()),
                 MissingTypesOrConsts { .. } => {
-                    ("at least ", self.num_expected_type_or_const_args_including_defaults())
+                    ("at-least", self.num_expected_type_or_const_args_including_defaults())
                 }
-                ExcessLifetimes { .. } => ("at most ", self.num_expected_lifetime_args()),
-                ExcessTypesOrConsts { .. } => ("at most ", self.num_expected_type_or_const_args()),
+                ExcessLifetimes { .. } => ("at-most", self.num_expected_lifetime_args()),
+                ExcessTypesOrConsts { .. } => ("at-most", self.num_expected_type_or_const_args()),
             }
         }
     }
@@ -308,69 +309,35 @@ fn get_type_or_const_args_suggestions_from_param_names(
             .join(", ")
     }
 
-    fn create_error_message(&self) -> String {
-        let def_path = self.tcx.def_path_str(self.def_id);
-        let def_kind = self.tcx.def_kind(self.def_id).descr(self.def_id);
-        let (quantifier, bound) = self.get_quantifier_and_bound();
-        let kind = self.kind();
-        let provided_lt_args = self.num_provided_lifetime_args();
-        let provided_type_or_const_args = self.num_provided_type_or_const_args();
-
-        let get_verb = |num_args| if num_args == 1 { "was" } else { "were" };
-
-        let (provided_args_str, verb) = match self.gen_args_info {
-            MissingLifetimes { .. } | ExcessLifetimes { .. } => (
-                format!("{} lifetime argument{}", provided_lt_args, pluralize!(provided_lt_args)),
-                get_verb(provided_lt_args),
-            ),
-            MissingTypesOrConsts { .. } | ExcessTypesOrConsts { .. } => (
-                format!(
-                    "{} generic argument{}",
-                    provided_type_or_const_args,
-                    pluralize!(provided_type_or_const_args)
-                ),
-                get_verb(provided_type_or_const_args),
-            ),
-        };
-
-        if self.gen_args.span_ext().is_some() {
-            format!(
-                "this {} takes {}{} {} argument{} but {} {} supplied",
-                def_kind,
-                quantifier,
-                bound,
-                kind,
-                pluralize!(bound),
-                provided_args_str.as_str(),
-                verb
-            )
-        } else {
-            format!("missing generics for {} `{}`", def_kind, def_path)
-        }
+    fn message() -> DiagnosticMessage {
+        DiagnosticMessage::fluent("typeck-wrong-number-of-generic-args")
     }
 
     fn start_diagnostics(&self) -> DiagnosticBuilder<'tcx> {
         let span = self.path_segment.ident.span;
-        let msg = self.create_error_message();
+        let msg = if self.gen_args.span_ext().is_some() {
+            Self::message()
+        } else {
+            Self::message().attr("missing-generics")
+        };
 
-        self.tcx.sess.struct_span_err_with_code(span, &msg, self.code())
+        let mut err = self.tcx.sess.struct_span_err_with_code(span, msg, self.code());
+
+        let (quantifier, bound) = self.get_quantifier_and_bound();
+        err.set_arg("def_kind", self.tcx.def_kind(self.def_id).descr(self.def_id));
+        err.set_arg("def_path", self.tcx.def_path_str(self.def_id));
+        err.set_arg("kind", self.kind());
+        err.set_arg("quantifier", quantifier);
+        err.set_arg("expected", bound);
+        err.set_arg("provided", self.num_provided_args());
+        err
     }
 
     /// Builds the `expected 1 type argument / supplied 2 type arguments` message.
     fn notify(&self, err: &mut DiagnosticBuilder<'_>) {
-        let (quantifier, bound) = self.get_quantifier_and_bound();
         let provided_args = self.num_provided_args();
 
-        err.span_label(
-            self.path_segment.ident.span,
-            format!(
-                "expected {}{} {} argument{}",
-                quantifier,
-                bound,
-                self.kind(),
-                pluralize!(bound),
-            ),
-        );
+        err.span_label(self.path_segment.ident.span, Self::message().attr("label"));
 
         // When too many arguments were provided, we don't highlight each of them, because it
         // would overlap with the suggestion to remove them:
@@ -396,14 +363,9 @@ fn notify(&self, err: &mut DiagnosticBuilder<'_>) {
             err.span_label(
                 arg.span(),
                 if i + 1 == provided_args {
-                    format!(
-                        "supplied {} {} argument{}",
-                        provided_args,
-                        self.kind(),
-                        pluralize!(provided_args)
-                    )
+                    Self::message().attr("supplied-label")
                 } else {
-                    String::new()
+                    DiagnosticMessage::from(String::new())
                 },
             );
         }
@@ -456,7 +418,8 @@ fn suggest_adding_lifetime_args(&self, err: &mut DiagnosticBuilder<'_>) {
         debug!("suggest_adding_lifetime_args(path_segment: {:?})", self.path_segment);
         let num_missing_args = self.num_missing_lifetime_args();
         let num_params_to_take = num_missing_args;
-        let msg = format!("add missing {} argument{}", self.kind(), pluralize!(num_missing_args));
+        let msg = Self::message().attr("add-args");
+        err.set_arg("missing", num_missing_args);
 
         // we first try to get lifetime name suggestions from scope or elision information. If none is
         // available we use the parameter defintions
@@ -505,7 +468,7 @@ fn suggest_adding_lifetime_args(&self, err: &mut DiagnosticBuilder<'_>) {
                 let sugg = format!("{}<{}>", ident, suggested_args);
                 debug!("sugg: {:?}", sugg);
 
-                err.span_suggestion_verbose(span, &msg, sugg, Applicability::HasPlaceholders);
+                err.span_suggestion_verbose(span, msg, sugg, Applicability::HasPlaceholders);
             }
 
             AngleBrackets::Available => {
@@ -525,7 +488,7 @@ fn suggest_adding_lifetime_args(&self, err: &mut DiagnosticBuilder<'_>) {
 
                         err.span_suggestion_verbose(
                             last_lt_arg_span,
-                            &msg,
+                            msg,
                             sugg,
                             Applicability::HasPlaceholders,
                         );
@@ -541,7 +504,7 @@ fn suggest_adding_lifetime_args(&self, err: &mut DiagnosticBuilder<'_>) {
 
                         err.span_suggestion_verbose(
                             first_arg_span,
-                            &msg,
+                            msg,
                             sugg,
                             Applicability::HasPlaceholders,
                         );
@@ -557,7 +520,8 @@ fn suggest_adding_lifetime_args(&self, err: &mut DiagnosticBuilder<'_>) {
 
     fn suggest_adding_type_and_const_args(&self, err: &mut DiagnosticBuilder<'_>) {
         let num_missing_args = self.num_missing_type_or_const_args();
-        let msg = format!("add missing {} argument{}", self.kind(), pluralize!(num_missing_args));
+        let msg = Self::message().attr("add-args");
+        err.set_arg("missing", num_missing_args);
 
         let suggested_args =
             self.get_type_or_const_args_suggestions_from_param_names(num_missing_args);
@@ -572,7 +536,7 @@ fn suggest_adding_type_and_const_args(&self, err: &mut DiagnosticBuilder<'_>) {
                 let sugg = format!("{}<{}>", ident, suggested_args);
                 debug!("sugg: {:?}", sugg);
 
-                err.span_suggestion_verbose(span, &msg, sugg, Applicability::HasPlaceholders);
+                err.span_suggestion_verbose(span, msg, sugg, Applicability::HasPlaceholders);
             }
             AngleBrackets::Available => {
                 let gen_args_span = self.gen_args.span().unwrap();
@@ -600,7 +564,7 @@ fn suggest_adding_type_and_const_args(&self, err: &mut DiagnosticBuilder<'_>) {
                 let sugg = format!("{}{}{}", sugg_prefix, suggested_args, sugg_suffix);
                 debug!("sugg: {:?}", sugg);
 
-                err.span_suggestion_verbose(sugg_span, &msg, sugg, Applicability::HasPlaceholders);
+                err.span_suggestion_verbose(sugg_span, msg, sugg, Applicability::HasPlaceholders);
             }
         }
     }
@@ -625,6 +589,10 @@ fn suggest_removing_args_or_generics(&self, err: &mut DiagnosticBuilder<'_>) {
 
         let remove_entire_generics = num_redundant_args >= self.gen_args.args.len();
 
+        err.set_arg("redundant", num_redundant_args);
+        err.set_arg("redundant_lifetimes", num_redundant_lt_args);
+        err.set_arg("redundant_generics", num_redundant_type_or_const_args);
+
         let remove_lifetime_args = |err: &mut DiagnosticBuilder<'_>| {
             let idx_first_redundant_lt_args = self.num_expected_lifetime_args();
             let span_lo_redundant_lt_args =
@@ -647,16 +615,9 @@ fn suggest_removing_args_or_generics(&self, err: &mut DiagnosticBuilder<'_>) {
             };
             debug!("span_redundant_lt_args: {:?}", span_redundant_lt_args);
 
-            let msg_lifetimes = format!(
-                "remove {} {} argument{}",
-                if num_redundant_args == 1 { "this" } else { "these" },
-                "lifetime",
-                pluralize!(num_redundant_lt_args),
-            );
-
             err.span_suggestion(
                 span_redundant_lt_args,
-                &msg_lifetimes,
+                Self::message().attr("remove-lifetime-args"),
                 String::new(),
                 Applicability::MaybeIncorrect,
             );
@@ -680,16 +641,9 @@ fn suggest_removing_args_or_generics(&self, err: &mut DiagnosticBuilder<'_>) {
 
             debug!("span_redundant_type_or_const_args: {:?}", span_redundant_type_or_const_args);
 
-            let msg_types_or_consts = format!(
-                "remove {} {} argument{}",
-                if num_redundant_args == 1 { "this" } else { "these" },
-                "generic",
-                pluralize!(num_redundant_type_or_const_args),
-            );
-
             err.span_suggestion(
                 span_redundant_type_or_const_args,
-                &msg_types_or_consts,
+                Self::message().attr("remove-generic-args"),
                 String::new(),
                 Applicability::MaybeIncorrect,
             );
@@ -704,12 +658,13 @@ fn suggest_removing_args_or_generics(&self, err: &mut DiagnosticBuilder<'_>) {
                 .unwrap()
                 .with_lo(self.path_segment.ident.span.hi());
 
-            let msg = format!(
-                "remove these {}generics",
-                if self.gen_args.parenthesized { "parenthetical " } else { "" },
-            );
+            let msg = if self.gen_args.parenthesized {
+                Self::message().attr("remove-parenthetical-generics")
+            } else {
+                Self::message().attr("remove-generics")
+            };
 
-            err.span_suggestion(span, &msg, String::new(), Applicability::MaybeIncorrect);
+            err.span_suggestion(span, msg, String::new(), Applicability::MaybeIncorrect);
         } else if redundant_lifetime_args && redundant_type_or_const_args {
             remove_lifetime_args(err);
             remove_type_or_const_args(err);
@@ -729,43 +684,23 @@ fn show_definition(&self, err: &mut DiagnosticBuilder<'_>) {
             return;
         };
 
-        let msg = {
-            let def_kind = self.tcx.def_kind(self.def_id).descr(self.def_id);
-            let (quantifier, bound) = self.get_quantifier_and_bound();
-
-            let params = if bound == 0 {
-                String::new()
-            } else {
-                let params = self
-                    .gen_params
-                    .params
-                    .iter()
-                    .skip(self.params_offset)
-                    .take(bound)
-                    .map(|param| {
-                        let span = self.tcx.def_span(param.def_id);
-                        spans.push_span_label(span, String::new());
-                        param
-                    })
-                    .map(|param| format!("`{}`", param.name))
-                    .collect::<Vec<_>>()
-                    .join(", ");
-
-                format!(": {}", params)
-            };
-
-            format!(
-                "{} defined here, with {}{} {} parameter{}{}",
-                def_kind,
-                quantifier,
-                bound,
-                self.kind(),
-                pluralize!(bound),
-                params,
-            )
-        };
+        let (_, bound) = self.get_quantifier_and_bound();
+        let params = self
+            .gen_params
+            .params
+            .iter()
+            .skip(self.params_offset)
+            .take(bound)
+            .map(|param| {
+                let span = self.tcx.def_span(param.def_id);
+                spans.push_span_label(span, String::new());
+                format!("`{}`", param.name)
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        err.set_arg("params", params);
 
-        err.span_note(spans, &msg);
+        err.span_note(spans, Self::message().attr("definition"));
     }
 }
 
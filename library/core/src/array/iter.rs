@@ -76,6 +76,20 @@ impl<T, const N: usize> IntoIter<T, N> {
         }
     }
 
+    /// Creates an iterator over the elements in a partially-initialized buffer.
+    ///
+    /// # Safety
+    ///
+    /// - The `buffer[initialized]` elements must all be initialized.
+    /// - The range must be canonical, with `initialized.start <= initialized.end`.
+    /// - The range must be in-bounds for the buffer, with `initialized.end <= N`.
+    pub(super) unsafe fn new_unchecked(
+        buffer: [MaybeUninit<T>; N],
+        initialized: Range<usize>,
+    ) -> Self {
+        Self { data: buffer, alive: initialized }
+    }
+
     /// Returns an immutable slice of all elements that have not been yielded
     /// yet.
     #[stable(feature = "array_value_iter", since = "1.51.0")]
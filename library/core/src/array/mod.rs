@@ -13,7 +13,10 @@
 use crate::hash::{self, Hash};
 use crate::iter::TrustedLen;
 use crate::mem::{self, MaybeUninit};
-use crate::ops::{Index, IndexMut};
+use crate::ops::{
+    ChangeOutputType, ControlFlow, FromResidual, Index, IndexMut, NeverShortCircuit, Residual,
+    TryV2 as Try,
+};
 use crate::slice::{Iter, IterMut};
 
 mod iter;
@@ -21,6 +24,86 @@
 #[stable(feature = "array_value_iter", since = "1.51.0")]
 pub use iter::IntoIter;
 
+/// Creates an array `[T; N]` where each array element `T` is returned by the
+/// `cb` call.
+///
+/// The closure is called once for each index, in increasing order. If it
+/// panics, the elements created so far are dropped.
+///
+/// # Arguments
+///
+/// * `cb`: Callback where the passed argument is the current array index.
+///
+/// # Example
+///
+/// ```rust
+/// #![feature(array_from_fn)]
+///
+/// let array = core::array::from_fn(|i| i);
+/// assert_eq!(array, [0, 1, 2, 3, 4]);
+///
+/// let squares: [String; 3] = core::array::from_fn(|i| (i * i).to_string());
+/// assert_eq!(squares, ["0", "1", "4"]);
+/// ```
+#[inline]
+#[unstable(feature = "array_from_fn", issue = "none")]
+pub fn from_fn<T, const N: usize, F>(cb: F) -> [T; N]
+where
+    F: FnMut(usize) -> T,
+{
+    // SAFETY: we know for certain that this iterator will yield exactly `N`
+    // items.
+    unsafe { collect_into_array_unchecked(&mut (0..N).map(cb)) }
+}
+
+/// Creates an array `[T; N]` where each fallible array element `T` is returned
+/// by the `cb` call. Unlike [`from_fn`], where the element creation can't
+/// fail, this version will return an error if any element creation was
+/// unsuccessful.
+///
+/// The return type of this function depends on the return type of the
+/// closure. If you return `Result<T, E>` from the closure, you'll get a
+/// `Result<[T; N], E>`. If you return `Option<T>` from the closure, you'll
+/// get an `Option<[T; N]>`. The elements created before the failure are
+/// dropped.
+///
+/// # Arguments
+///
+/// * `cb`: Callback where the passed argument is the current array index.
+///
+/// # Example
+///
+/// ```rust
+/// #![feature(array_try_from_fn)]
+/// use std::convert::TryFrom;
+///
+/// let array: Result<[u8; 5], _> = std::array::try_from_fn(|i| u8::try_from(i));
+/// assert_eq!(array, Ok([0, 1, 2, 3, 4]));
+///
+/// let array: Result<[i8; 200], _> = std::array::try_from_fn(|i| i8::try_from(i));
+/// assert!(array.is_err());
+///
+/// let array: Option<[_; 4]> = std::array::try_from_fn(|i| i.checked_add(100));
+/// assert_eq!(array, Some([100, 101, 102, 103]));
+///
+/// let array: Option<[_; 4]> = std::array::try_from_fn(|i| i.checked_sub(100));
+/// assert_eq!(array, None);
+/// ```
+#[inline]
+#[unstable(feature = "array_try_from_fn", issue = "none")]
+pub fn try_from_fn<R, const N: usize, F>(cb: F) -> ChangeOutputType<R, [R::Output; N]>
+where
+    F: FnMut(usize) -> R,
+    R: Try,
+    R::Residual: Residual<[R::Output; N]>,
+{
+    match try_collect_into_array(&mut (0..N).map(cb)) {
+        Ok(array) => array,
+        // SAFETY: the iterator yields exactly `N` items.
+        Err(_) => unsafe { crate::hint::unreachable_unchecked() },
+    }
+}
+
 /// Converts a reference to `T` into a reference to an array of length 1 (without copying).
 #[stable(feature = "array_from_ref", since = "1.53.0")]
 pub fn from_ref<T>(s: &T) -> &[T; 1] {
@@ -540,15 +623,15 @@ pub fn as_mut_slice(&mut self) -> &mut [T] {
     debug_assert!(N <= iter.size_hint().0);
 
     match collect_into_array(iter) {
-        Some(array) => array,
+        Ok(array) => array,
         // SAFETY: covered by the function contract.
-        None => unsafe { crate::hint::unreachable_unchecked() },
+        Err(_) => unsafe { crate::hint::unreachable_unchecked() },
     }
 }
 
 /// Pulls `N` items from `iter` and returns them as an array. If the iterator
-/// yields fewer than `N` items, `None` is returned and all already yielded
-/// items are dropped.
+/// yields fewer than `N` items, `Err` is returned containing an iterator over
+/// the already yielded items.
 ///
 /// Since the iterator is passed as a mutable reference and this function calls
 /// `next` at most `N` times, the iterator can still be used afterwards to
@@ -556,13 +639,36 @@ pub fn as_mut_slice(&mut self) -> &mut [T] {
 ///
 /// If `iter.next()` panicks, all items already yielded by the iterator are
 /// dropped.
-fn collect_into_array<I, const N: usize>(iter: &mut I) -> Option<[I::Item; N]>
+pub(crate) fn collect_into_array<I, const N: usize>(
+    iter: &mut I,
+) -> Result<[I::Item; N], IntoIter<I::Item, N>>
 where
     I: Iterator,
+{
+    let mut map = iter.map(NeverShortCircuit);
+    try_collect_into_array(&mut map).map(|NeverShortCircuit(array)| array)
+}
+
+/// Pulls `N` items from `iter` and returns them as an array, stopping early if
+/// an item short-circuits (like `None` or `Err`). In that case, all already
+/// yielded items are dropped and the residual is returned, converted to the
+/// `Try` type of the array.
+///
+/// If the iterator yields fewer than `N` items, `Err` is returned containing an
+/// iterator over the already yielded items. Like [`collect_into_array`], this
+/// calls `next` at most `N` times, and drops the already yielded items if
+/// `iter.next()` panicks.
+fn try_collect_into_array<I, T, R, const N: usize>(
+    iter: &mut I,
+) -> Result<R::TryType, IntoIter<T, N>>
+where
+    I: Iterator,
+    I::Item: Try<Output = T, Residual = R>,
+    R: Residual<[T; N]>,
 {
     if N == 0 {
         // SAFETY: An empty array is always inhabited and has no validity invariants.
-        return unsafe { Some(mem::zeroed()) };
+        return unsafe { Ok(Try::from_output(mem::zeroed())) };
     }
 
     struct Guard<T, const N: usize> {
@@ -588,6 +694,13 @@ fn drop(&mut self) {
         Guard { ptr: MaybeUninit::slice_as_mut_ptr(&mut array), initialized: 0 };
 
     while let Some(item) = iter.next() {
+        let item = match item.branch() {
+            // `guard` is dropped here, dropping all already initialized
+            // elements.
+            ControlFlow::Break(residual) => return Ok(FromResidual::from_residual(residual)),
+            ControlFlow::Continue(item) => item,
+        };
+
         // SAFETY: `guard.initialized` starts at 0, is increased by one in the
         // loop and the loop is aborted once it reaches N (which is
         // `array.len()`).
@@ -603,12 +716,16 @@ fn drop(&mut self) {
             // SAFETY: the condition above asserts that all elements are
             // initialized.
             let out = unsafe { MaybeUninit::array_assume_init(array) };
-            return Some(out);
+            return Ok(Try::from_output(out));
         }
     }
 
     // This is only reached if the iterator is exhausted before
-    // `guard.initialized` reaches `N`. Also note that `guard` is dropped here,
-    // dropping all already initialized elements.
-    None
+    // `guard.initialized` reaches `N`. The already initialized elements are
+    // handed over to the returned iterator, which drops them in turn.
+    let alive = 0..guard.initialized;
+    mem::forget(guard);
+
+    // SAFETY: the elements in `alive` were initialized in the loop above.
+    Err(unsafe { IntoIter::new_unchecked(array, alive) })
 }
@@ -0,0 +1,143 @@
+use crate::array;
+use crate::iter::{FusedIterator, Iterator};
+
+/// An iterator over `N` elements of the iterator at a time.
+///
+/// The chunks do not overlap. If `N` does not divide the length of the
+/// iterator, then the last up to `N-1` elements will be omitted.
+///
+/// This `struct` is created by the [`array_chunks`][Iterator::array_chunks]
+/// method on [`Iterator`]. See its documentation for more.
+#[derive(Debug, Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[unstable(feature = "iter_array_chunks", reason = "recently added", issue = "none")]
+pub struct ArrayChunks<I: Iterator, const N: usize> {
+    iter: I,
+    remainder: Option<array::IntoIter<I::Item, N>>,
+}
+
+impl<I, const N: usize> ArrayChunks<I, N>
+where
+    I: Iterator,
+{
+    #[track_caller]
+    pub(in crate::iter) fn new(iter: I) -> Self {
+        assert!(N != 0, "chunk size must be non-zero");
+        Self { iter, remainder: None }
+    }
+
+    /// Returns an iterator over the remaining elements of the original iterator
+    /// that are not going to be returned by this iterator. The returned
+    /// iterator will yield at most `N-1` elements.
+    ///
+    /// Returns `None` if the remainder is not known yet, which is the case
+    /// until this iterator has returned `None`, or, for double-ended
+    /// iterators, until [`next_back`] has been called.
+    ///
+    /// [`next_back`]: DoubleEndedIterator::next_back
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// #![feature(iter_array_chunks)]
+    ///
+    /// let mut iter = (1..=5).array_chunks::<2>();
+    /// assert_eq!(iter.next(), Some([1, 2]));
+    /// assert_eq!(iter.next(), Some([3, 4]));
+    /// assert_eq!(iter.next(), None);
+    ///
+    /// let mut rem = iter.into_remainder().unwrap();
+    /// assert_eq!(rem.next(), Some(5));
+    /// assert_eq!(rem.next(), None);
+    /// ```
+    #[unstable(feature = "iter_array_chunks", reason = "recently added", issue = "none")]
+    #[inline]
+    pub fn into_remainder(self) -> Option<array::IntoIter<I::Item, N>> {
+        self.remainder
+    }
+}
+
+#[unstable(feature = "iter_array_chunks", reason = "recently added", issue = "none")]
+impl<I, const N: usize> Iterator for ArrayChunks<I, N>
+where
+    I: Iterator,
+{
+    type Item = [I::Item; N];
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.iter.next_chunk() {
+            Ok(chunk) => Some(chunk),
+            Err(remainder) => {
+                // Keep the remainder from the first time the inner iterator
+                // ran out; later calls only see an empty one.
+                self.remainder.get_or_insert(remainder);
+                None
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        (lower / N, upper.map(|n| n / N))
+    }
+}
+
+#[unstable(feature = "iter_array_chunks", reason = "recently added", issue = "none")]
+impl<I, const N: usize> DoubleEndedIterator for ArrayChunks<I, N>
+where
+    I: DoubleEndedIterator + ExactSizeIterator,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.next_back_remainder();
+
+        // The remainder has been split off, so the inner iterator now holds a
+        // multiple of `N` elements, which are taken from the back in reverse.
+        let mut chunk = self.iter.by_ref().rev().next_chunk::<N>().ok()?;
+        chunk.reverse();
+        Some(chunk)
+    }
+}
+
+impl<I, const N: usize> ArrayChunks<I, N>
+where
+    I: DoubleEndedIterator + ExactSizeIterator,
+{
+    /// Splits the `len % N` trailing elements off the back of the inner
+    /// iterator into the remainder, unless that has already happened.
+    fn next_back_remainder(&mut self) {
+        if self.remainder.is_some() {
+            return;
+        }
+
+        let rem = self.iter.len() % N;
+        // `take(rem)` yields fewer than `N` elements, so this is always `Err`.
+        let mut remainder = match self.iter.by_ref().rev().take(rem).next_chunk::<N>() {
+            Ok(_) => unreachable!(),
+            Err(remainder) => remainder,
+        };
+        // The elements were taken from the back, so put them back in order.
+        remainder.as_mut_slice().reverse();
+        self.remainder = Some(remainder);
+    }
+}
+
+#[unstable(feature = "iter_array_chunks", reason = "recently added", issue = "none")]
+impl<I, const N: usize> FusedIterator for ArrayChunks<I, N> where I: FusedIterator {}
+
+#[unstable(feature = "iter_array_chunks", reason = "recently added", issue = "none")]
+impl<I, const N: usize> ExactSizeIterator for ArrayChunks<I, N>
+where
+    I: ExactSizeIterator,
+{
+    #[inline]
+    fn len(&self) -> usize {
+        self.iter.len() / N
+    }
+}
+
+// `TrustedLen` is deliberately not implemented: an inner iterator with more
+// than `usize::MAX` elements reports `None` as its upper bound, while the
+// number of chunks may still fit in a `usize`.
@@ -0,0 +1,92 @@
+use crate::fmt;
+use crate::iter::{FusedIterator, Iterator};
+
+/// An iterator over the mapped windows of another iterator.
+///
+/// This `struct` is created by the [`map_windows`] method on [`Iterator`]. See
+/// its documentation for more information.
+///
+/// [`map_windows`]: Iterator::map_windows
+#[derive(Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[unstable(feature = "iter_map_windows", reason = "recently added", issue = "none")]
+pub struct MapWindows<I: Iterator, F, const N: usize> {
+    // `None` once the inner iterator has returned `None`, which makes this
+    // iterator fused.
+    iter: Option<I>,
+    // The current window, or `None` if the first `N` elements have not been
+    // pulled from `iter` yet.
+    window: Option<[I::Item; N]>,
+    f: F,
+}
+
+impl<I: Iterator, F, const N: usize> MapWindows<I, F, N> {
+    #[track_caller]
+    pub(in crate::iter) fn new(iter: I, f: F) -> Self {
+        assert!(N != 0, "array in `Iterator::map_windows` must contain more than 0 elements");
+        Self { iter: Some(iter), window: None, f }
+    }
+}
+
+#[unstable(feature = "iter_map_windows", reason = "recently added", issue = "none")]
+impl<I, F, R, const N: usize> Iterator for MapWindows<I, F, N>
+where
+    I: Iterator,
+    F: FnMut(&[I::Item; N]) -> R,
+{
+    type Item = R;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let iter = self.iter.as_mut()?;
+        let next_window = match self.window.take() {
+            None => iter.next_chunk().ok(),
+            Some(mut window) => iter.next().map(|item| {
+                // Shift out the oldest element and put the new one at the end;
+                // the assignment drops the oldest element.
+                window.rotate_left(1);
+                window[N - 1] = item;
+                window
+            }),
+        };
+
+        match next_window {
+            Some(window) => Some((self.f)(self.window.insert(window))),
+            None => {
+                self.iter = None;
+                None
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let iter = match &self.iter {
+            Some(iter) => iter,
+            None => return (0, Some(0)),
+        };
+
+        let (lower, upper) = iter.size_hint();
+        if self.window.is_some() {
+            // Every further element completes exactly one more window.
+            (lower, upper)
+        } else {
+            // The first window takes `N` elements, every later one takes one.
+            (lower.saturating_sub(N - 1), upper.map(|n| n.saturating_sub(N - 1)))
+        }
+    }
+}
+
+#[unstable(feature = "iter_map_windows", reason = "recently added", issue = "none")]
+impl<I, F, R, const N: usize> FusedIterator for MapWindows<I, F, N>
+where
+    I: Iterator,
+    F: FnMut(&[I::Item; N]) -> R,
+{
+}
+
+#[unstable(feature = "iter_map_windows", reason = "recently added", issue = "none")]
+impl<I: Iterator + fmt::Debug, F, const N: usize> fmt::Debug for MapWindows<I, F, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MapWindows").field("iter", &self.iter).finish()
+    }
+}
@@ -2,6 +2,7 @@
 // This file almost exclusively consists of the definition of `Iterator`. We
 // can't split that into multiple files.
 
+use crate::array;
 use crate::cmp::{self, Ordering};
 use crate::ops::{ControlFlow, Try};
 
@@ -9,9 +10,8 @@
 use super::super::{Chain, Cloned, Copied, Cycle, Enumerate, Filter, FilterMap, Fuse};
 use super::super::{FlatMap, Flatten};
 use super::super::{FromIterator, Intersperse, IntersperseWith, Product, Sum, Zip};
-use super::super::{
-    Inspect, Map, MapWhile, Peekable, Rev, Scan, Skip, SkipWhile, StepBy, Take, TakeWhile,
-};
+use super::super::{ArrayChunks, Inspect, Map, MapWhile, MapWindows, Peekable, Rev, Scan};
+use super::super::{Skip, SkipWhile, StepBy, Take, TakeWhile};
 
 fn _assert_is_object_safe(_: &dyn Iterator<Item = ()>) {}
 
@@ -135,6 +135,47 @@ pub trait Iterator {
     #[stable(feature = "rust1", since = "1.0.0")]
     fn next(&mut self) -> Option<Self::Item>;
 
+    /// Advances the iterator and returns an array containing the next `N` values.
+    ///
+    /// If there are not enough elements to fill the array then `Err` is returned
+    /// containing an iterator over the remaining elements.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// #![feature(iter_next_chunk)]
+    ///
+    /// let mut iter = "lorem".chars();
+    ///
+    /// assert_eq!(iter.next_chunk().unwrap(), ['l', 'o']);              // N is inferred as 2
+    /// assert_eq!(iter.next_chunk().unwrap(), ['r', 'e', 'm']);         // N is inferred as 3
+    /// assert_eq!(iter.next_chunk::<4>().unwrap_err().as_slice(), &[]); // N is explicitly 4
+    /// ```
+    ///
+    /// Split a string and get the first three items.
+    ///
+    /// ```
+    /// #![feature(iter_next_chunk)]
+    ///
+    /// let quote = "not all those who wander are lost";
+    /// let [first, second, third] = quote.split_whitespace().next_chunk().unwrap();
+    /// assert_eq!(first, "not");
+    /// assert_eq!(second, "all");
+    /// assert_eq!(third, "those");
+    /// ```
+    #[inline]
+    #[unstable(feature = "iter_next_chunk", issue = "none")]
+    fn next_chunk<const N: usize>(
+        &mut self,
+    ) -> Result<[Self::Item; N], array::IntoIter<Self::Item, N>>
+    where
+        Self: Sized,
+    {
+        array::collect_into_array(self)
+    }
+
     /// Returns the bounds on the remaining length of the iterator.
     ///
     /// Specifically, `size_hint()` returns a tuple where the first element
@@ -1249,6 +1290,101 @@ fn map_while<B, P>(self, predicate: P) -> MapWhile<Self, P>
         MapWhile::new(self, predicate)
     }
 
+    /// Returns an iterator over `N` elements of the iterator at a time.
+    ///
+    /// The chunks do not overlap. If `N` does not divide the length of the
+    /// iterator, then the last up to `N-1` elements will be omitted and can be
+    /// retrieved from the [`.into_remainder()`][ArrayChunks::into_remainder]
+    /// function of the iterator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is 0.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// #![feature(iter_array_chunks)]
+    ///
+    /// let mut iter = "lorem".chars().array_chunks();
+    /// assert_eq!(iter.next(), Some(['l', 'o']));
+    /// assert_eq!(iter.next(), Some(['r', 'e']));
+    /// assert_eq!(iter.next(), None);
+    /// assert_eq!(iter.into_remainder().unwrap().as_slice(), &['m']);
+    /// ```
+    ///
+    /// ```
+    /// #![feature(iter_array_chunks)]
+    ///
+    /// let data = [1, 1, 2, -2, 6, 0, 3, 1];
+    /// //          ^-----^  ^------^
+    /// for [x, y, z] in data.iter().array_chunks() {
+    ///     assert_eq!(x + y + z, 4);
+    /// }
+    /// ```
+    #[track_caller]
+    #[unstable(feature = "iter_array_chunks", reason = "recently added", issue = "none")]
+    fn array_chunks<const N: usize>(self) -> ArrayChunks<Self, N>
+    where
+        Self: Sized,
+    {
+        ArrayChunks::new(self)
+    }
+
+    /// Calls the given function `f` for each contiguous window of size `N` over
+    /// `self` and returns an iterator over the outputs of `f`.
+    ///
+    /// The windows overlap, and each one is passed to `f` as a reference to an
+    /// array. If the iterator has fewer than `N` elements, `f` is never called
+    /// and the returned iterator is empty. Each element of `self` is pulled
+    /// only once, and is dropped once the last window containing it has been
+    /// passed to `f`.
+    ///
+    /// The returned iterator is fused: once it has returned `None`, the inner
+    /// iterator is dropped and never polled again.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is 0.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// #![feature(iter_map_windows)]
+    ///
+    /// let strings = "abcd".chars()
+    ///     .map_windows(|[x, y]| format!("{}+{}", x, y))
+    ///     .collect::<Vec<String>>();
+    ///
+    /// assert_eq!(strings, vec!["a+b", "b+c", "c+d"]);
+    /// ```
+    ///
+    /// ```
+    /// #![feature(iter_map_windows)]
+    ///
+    /// // Check whether the elements are sorted.
+    /// let is_sorted = [1, 2, 2, 5, 8].iter().map_windows(|[a, b]| a <= b).all(|x| x);
+    /// assert!(is_sorted);
+    ///
+    /// // Fewer elements than the window size.
+    /// let mut it = [1, 2].iter().map_windows(|_: &[_; 3]| unreachable!());
+    /// assert_eq!(it.next(), None);
+    /// ```
+    #[inline]
+    #[track_caller]
+    #[unstable(feature = "iter_map_windows", reason = "recently added", issue = "none")]
+    fn map_windows<F, R, const N: usize>(self, f: F) -> MapWindows<Self, F, N>
+    where
+        Self: Sized,
+        F: FnMut(&[Self::Item; N]) -> R,
+    {
+        MapWindows::new(self, f)
+    }
+
     /// Creates an iterator that skips the first `n` elements.
     ///
     /// `skip(n)` skips elements until `n` elements are skipped or the end of the
@@ -1788,6 +1924,44 @@ fn collect<B: FromIterator<Self::Item>>(self) -> B
         FromIterator::from_iter(self)
     }
 
+    /// Collects the iterator into an array of exactly `N` items.
+    ///
+    /// Returns `None` if the iterator yields fewer or more than `N` items. The
+    /// items already taken from the iterator are dropped in that case. Unlike
+    /// [`next_chunk`], this consumes the iterator, and pulls at most `N + 1`
+    /// items from it.
+    ///
+    /// [`next_chunk`]: Iterator::next_chunk
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// #![feature(iter_collect_array)]
+    ///
+    /// let a = [1, 2, 3];
+    ///
+    /// let doubled: Option<[i32; 3]> = a.iter().map(|&x| x * 2).collect_array();
+    /// assert_eq!(doubled, Some([2, 4, 6]));
+    ///
+    /// // Too few items.
+    /// assert_eq!(a.iter().collect_array::<4>(), None);
+    ///
+    /// // Too many items.
+    /// assert_eq!(a.iter().collect_array::<2>(), None);
+    /// ```
+    #[inline]
+    #[unstable(feature = "iter_collect_array", issue = "none")]
+    #[must_use = "if you really need to exhaust the iterator, consider `.for_each(drop)` instead"]
+    fn collect_array<const N: usize>(mut self) -> Option<[Self::Item; N]>
+    where
+        Self: Sized,
+    {
+        let array = self.next_chunk().ok()?;
+        if self.next().is_some() { None } else { Some(array) }
+    }
+
     /// Consumes an iterator, creating two collections from it.
     ///
     /// The predicate passed to `partition()` can return `true`, or `false`.
@@ -265,6 +265,8 @@
 #[unstable(feature = "async_stream", issue = "79024")]
 pub mod stream;
 pub mod sync;
+#[unstable(feature = "custom_test_frameworks", issue = "50297")]
+pub mod test_case;
 
 pub mod fmt;
 pub mod hash;
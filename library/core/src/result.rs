@@ -1675,3 +1675,8 @@ fn from_residual(residual: Result<convert::Infallible, E>) -> Self {
         }
     }
 }
+
+#[unstable(feature = "try_trait_v2_residual", issue = "84277")]
+impl<T, E> ops::Residual<T> for Result<convert::Infallible, E> {
+    type TryType = Result<T, E>;
+}
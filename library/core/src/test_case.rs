@@ -0,0 +1,82 @@
+//! Descriptors for custom test frameworks.
+//!
+//! By default, the runner named by `#![test_runner(path)]` is handed a slice
+//! of references to the `#[test_case]` items of the crate. With
+//! `#![test_runner(path, descriptors)]`, it is handed a slice of
+//! [`TestCase`]s instead, which also carry the name, location and test
+//! attributes of each item:
+//!
+//! ```ignore (requires --test)
+//! #![feature(custom_test_frameworks)]
+//! #![test_runner(runner, descriptors)]
+//!
+//! use core::test_case::TestCase;
+//!
+//! fn runner(tests: &[&TestCase<'_, dyn Fn()>]) {
+//!     for test in tests {
+//!         if test.ignore {
+//!             println!("{} ... ignored", test.name);
+//!         } else {
+//!             (test.test)();
+//!             println!("{} ... ok", test.name);
+//!         }
+//!     }
+//! }
+//!
+//! #[test_case]
+//! #[ignore = "not yet implemented"]
+//! static FIRST: fn() = || assert_eq!(1 + 1, 2);
+//! ```
+
+use crate::fmt;
+
+/// A `#[test_case]` item, along with what the test harness knows about it.
+#[unstable(feature = "custom_test_frameworks", issue = "50297")]
+pub struct TestCase<'a, T: ?Sized> {
+    /// The path of the item within its crate, such as `tests::it_works`.
+    pub name: &'static str,
+    /// The module the item is defined in, as given by `module_path!()`.
+    pub module_path: &'static str,
+    /// The file the item is defined in, as given by `file!()`.
+    pub file: &'static str,
+    /// The line the item starts on, counting from 1.
+    pub line: u32,
+    /// The column the item starts on, counting from 1.
+    pub column: u32,
+    /// Whether the item has an `#[ignore]` attribute that applies to it.
+    pub ignore: bool,
+    /// The reason given in `#[ignore = "reason"]`, if any.
+    pub ignore_message: Option<&'static str>,
+    /// Whether the item has a `#[should_panic]` attribute.
+    pub should_panic: ShouldPanic,
+    /// The item itself.
+    pub test: &'a T,
+}
+
+#[unstable(feature = "custom_test_frameworks", issue = "50297")]
+impl<T: ?Sized> fmt::Debug for TestCase<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TestCase")
+            .field("name", &self.name)
+            .field("module_path", &self.module_path)
+            .field("file", &self.file)
+            .field("line", &self.line)
+            .field("column", &self.column)
+            .field("ignore", &self.ignore)
+            .field("ignore_message", &self.ignore_message)
+            .field("should_panic", &self.should_panic)
+            .finish_non_exhaustive()
+    }
+}
+
+/// What the `#[should_panic]` attribute of a [`TestCase`] asks for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[unstable(feature = "custom_test_frameworks", issue = "50297")]
+pub enum ShouldPanic {
+    /// There is no `#[should_panic]` attribute.
+    No,
+    /// `#[should_panic]`
+    Yes,
+    /// `#[should_panic(expected = "message")]`
+    YesWithMessage(&'static str),
+}
@@ -0,0 +1,101 @@
+use core::cell::Cell;
+use core::iter;
+
+use super::*;
+
+#[test]
+fn test_iterator_array_chunks_infer() {
+    let xs = [1, 1, 2, -2, 6, 0, 3, 1];
+    for [a, b, c] in xs.iter().copied().array_chunks() {
+        assert_eq!(a + b + c, 4);
+    }
+}
+
+#[test]
+fn test_iterator_array_chunks_clone_and_drop() {
+    let count = Cell::new(0);
+    let mut it = (0..5).map(|_| CountDrop::new(&count)).array_chunks::<3>();
+    assert_eq!(it.by_ref().count(), 1);
+    assert_eq!(count.get(), 3);
+    let mut it2 = it.clone();
+    assert_eq!(count.get(), 3);
+    assert_eq!(it.into_remainder().unwrap().len(), 2);
+    assert_eq!(count.get(), 5);
+    assert!(it2.next().is_none());
+    assert_eq!(it2.into_remainder().unwrap().len(), 2);
+    assert_eq!(count.get(), 7);
+}
+
+#[test]
+fn test_iterator_array_chunks_remainder() {
+    let mut it = (0..11).array_chunks::<4>();
+    assert_eq!(it.next(), Some([0, 1, 2, 3]));
+    assert_eq!(it.next(), Some([4, 5, 6, 7]));
+    assert_eq!(it.next(), None);
+    assert_eq!(it.into_remainder().unwrap().as_slice(), &[8, 9, 10]);
+
+    // Nothing is known about the remainder until the iterator is exhausted.
+    let it = (0..11).array_chunks::<4>();
+    assert!(it.into_remainder().is_none());
+}
+
+#[test]
+fn test_iterator_array_chunks_size_hint() {
+    let it = (0..6).array_chunks::<1>();
+    assert_eq!(it.size_hint(), (6, Some(6)));
+
+    let it = (0..6).array_chunks::<3>();
+    assert_eq!(it.size_hint(), (2, Some(2)));
+
+    let it = (0..6).array_chunks::<5>();
+    assert_eq!(it.size_hint(), (1, Some(1)));
+
+    let it = (0..6).array_chunks::<7>();
+    assert_eq!(it.size_hint(), (0, Some(0)));
+
+    let it = (1..).array_chunks::<2>();
+    assert_eq!(it.size_hint(), (usize::MAX / 2, None));
+
+    let it = (1..).filter(|x| x % 2 != 0).array_chunks::<2>();
+    assert_eq!(it.size_hint(), (0, None));
+}
+
+#[test]
+fn test_iterator_array_chunks_len() {
+    let mut it = (0..7).array_chunks::<3>();
+    assert_eq!(it.len(), 2);
+    it.next();
+    assert_eq!(it.len(), 1);
+    it.next();
+    assert_eq!(it.len(), 0);
+}
+
+#[test]
+fn test_iterator_array_chunks_rev() {
+    let mut it = (0..10).array_chunks::<3>();
+    assert_eq!(it.next_back(), Some([6, 7, 8]));
+    assert_eq!(it.next(), Some([0, 1, 2]));
+    assert_eq!(it.next_back(), Some([3, 4, 5]));
+    assert_eq!(it.next_back(), None);
+    assert_eq!(it.next(), None);
+    assert_eq!(it.into_remainder().unwrap().as_slice(), &[9]);
+
+    let v: Vec<_> = (0..8).array_chunks::<2>().rev().collect();
+    assert_eq!(v, vec![[6, 7], [4, 5], [2, 3], [0, 1]]);
+}
+
+#[test]
+fn test_iterator_array_chunks_fused() {
+    let mut it = NonFused::new(iter::repeat(0).take(5)).fuse().array_chunks::<2>();
+    assert_eq!(it.next(), Some([0, 0]));
+    assert_eq!(it.next(), Some([0, 0]));
+    assert_eq!(it.next(), None);
+    assert_eq!(it.next(), None);
+    assert_eq!(it.into_remainder().unwrap().as_slice(), &[0]);
+}
+
+#[test]
+#[should_panic(expected = "chunk size must be non-zero")]
+fn test_iterator_array_chunks_zero() {
+    let _ = (0..5).array_chunks::<0>();
+}
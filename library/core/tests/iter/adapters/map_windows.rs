@@ -0,0 +1,73 @@
+use core::cell::Cell;
+use core::iter;
+
+use super::*;
+
+#[test]
+fn test_map_windows() {
+    let v: Vec<_> = (0..5).map_windows(|[a, b]| a + b).collect();
+    assert_eq!(v, vec![1, 3, 5, 7]);
+
+    let v: Vec<String> = "abcd".chars().map_windows(|w: &[char; 3]| w.iter().collect()).collect();
+    assert_eq!(v, vec!["abc", "bcd"]);
+
+    let v: Vec<_> = (0..3).map_windows(|w: &[_; 3]| *w).collect();
+    assert_eq!(v, vec![[0, 1, 2]]);
+
+    let mut it = (0..2).map_windows(|_: &[_; 3]| unreachable!());
+    assert_eq!(it.next(), None);
+}
+
+#[test]
+fn test_map_windows_size_hint() {
+    let mut it = (0..6).map_windows(|[a, b, c]| a + b + c);
+    assert_eq!(it.size_hint(), (4, Some(4)));
+    assert_eq!(it.next(), Some(3));
+    assert_eq!(it.size_hint(), (3, Some(3)));
+
+    let it = (0..2).map_windows(|[a, b, c]| a + b + c);
+    assert_eq!(it.size_hint(), (0, Some(0)));
+
+    let it = (0..).map_windows(|[a, b]| a + b);
+    assert_eq!(it.size_hint(), (usize::MAX - 1, None));
+
+    let mut it = (0..3).map_windows(|[a, b]| a + b);
+    assert_eq!(it.by_ref().count(), 2);
+    assert_eq!(it.size_hint(), (0, Some(0)));
+}
+
+#[test]
+fn test_map_windows_drop() {
+    let count = Cell::new(0);
+    let mut it = (0..5).map(|_| CountDrop::new(&count)).map_windows(|_: &[_; 3]| ());
+    assert_eq!(it.next(), Some(()));
+    assert_eq!(count.get(), 0);
+    assert_eq!(it.next(), Some(()));
+    assert_eq!(count.get(), 1);
+    assert_eq!(it.by_ref().count(), 1);
+    assert_eq!(count.get(), 5);
+    drop(it);
+    assert_eq!(count.get(), 5);
+
+    // Elements of an unfinished window are dropped along with the iterator.
+    let count = Cell::new(0);
+    let mut it = (0..5).map(|_| CountDrop::new(&count)).map_windows(|_: &[_; 3]| ());
+    assert_eq!(it.next(), Some(()));
+    drop(it);
+    assert_eq!(count.get(), 3);
+}
+
+#[test]
+fn test_map_windows_fused() {
+    let mut it = NonFused::new(iter::repeat(0).take(3)).map_windows(|[a, b]| a + b);
+    assert_eq!(it.next(), Some(0));
+    assert_eq!(it.next(), Some(0));
+    assert_eq!(it.next(), None);
+    assert_eq!(it.next(), None);
+}
+
+#[test]
+#[should_panic(expected = "array in `Iterator::map_windows` must contain more than 0 elements")]
+fn test_map_windows_zero() {
+    let _ = (0..5).map_windows(|_: &[_; 0]| ());
+}
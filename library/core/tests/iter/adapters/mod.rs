@@ -1,3 +1,4 @@
+mod array_chunks;
 mod chain;
 mod cloned;
 mod copied;
@@ -11,6 +12,7 @@
 mod inspect;
 mod intersperse;
 mod map;
+mod map_windows;
 mod peekable;
 mod scan;
 mod skip;
@@ -183,3 +185,21 @@ fn clone(&self) -> Self {
         ret
     }
 }
+
+/// Counts how many of its instances, including clones, have been dropped.
+#[derive(Debug, Clone)]
+struct CountDrop<'a> {
+    count: &'a Cell<usize>,
+}
+
+impl<'a> CountDrop<'a> {
+    pub fn new(count: &'a Cell<usize>) -> Self {
+        Self { count }
+    }
+}
+
+impl Drop for CountDrop<'_> {
+    fn drop(&mut self) {
+        self.count.set(self.count.get() + 1);
+    }
+}
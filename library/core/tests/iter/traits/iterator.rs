@@ -468,3 +468,27 @@ fn test_collect() {
     let b: Vec<isize> = a.iter().cloned().collect();
     assert!(a == b);
 }
+
+#[test]
+fn test_next_chunk() {
+    let mut it = 0..12;
+    assert_eq!(it.next_chunk().unwrap(), [0, 1, 2, 3]);
+    assert_eq!(it.next_chunk().unwrap(), []);
+    assert_eq!(it.next_chunk().unwrap(), [4, 5, 6, 7, 8, 9]);
+    assert_eq!(it.next_chunk::<4>().unwrap_err().as_slice(), &[10, 11]);
+    assert_eq!(it.next_chunk::<1>().unwrap_err().as_slice(), &[]);
+}
+
+#[test]
+fn test_collect_array() {
+    assert_eq!((0..3).collect_array(), Some([0, 1, 2]));
+    assert_eq!((0..3).collect_array::<4>(), None);
+    assert_eq!((0..3).collect_array::<2>(), None);
+    assert_eq!((0..0).collect_array(), Some([0u8; 0]));
+
+    // At most `N + 1` items are pulled from the iterator.
+    let mut pulled = 0;
+    let array = (0..).inspect(|_| pulled += 1).collect_array::<3>();
+    assert_eq!(array, None);
+    assert_eq!(pulled, 4);
+}
@@ -34,6 +34,8 @@
 #![feature(raw)]
 #![feature(sort_internals)]
 #![feature(slice_partition_at_index)]
+#![feature(array_from_fn)]
+#![feature(array_try_from_fn)]
 #![feature(maybe_uninit_uninit_array)]
 #![feature(maybe_uninit_array_assume_init)]
 #![feature(maybe_uninit_extra)]
@@ -49,11 +51,15 @@
 #![feature(slice_partition_dedup)]
 #![feature(int_error_matching)]
 #![feature(iter_advance_by)]
+#![feature(iter_array_chunks)]
+#![feature(iter_collect_array)]
+#![feature(iter_next_chunk)]
 #![feature(iter_partition_in_place)]
 #![feature(iter_intersperse)]
 #![feature(iter_is_partitioned)]
 #![feature(iter_order_by)]
 #![feature(iter_map_while)]
+#![feature(iter_map_windows)]
 #![feature(const_mut_refs)]
 #![feature(const_pin)]
 #![feature(const_slice_from_raw_parts)]
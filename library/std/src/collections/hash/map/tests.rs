@@ -1,5 +1,6 @@
 use super::Entry::{Occupied, Vacant};
 use super::HashMap;
+use super::OccupiedError;
 use super::RandomState;
 use crate::cell::RefCell;
 use rand::{thread_rng, Rng};
@@ -804,6 +805,27 @@ fn test_vacant_entry_key() {
     assert_eq!(a[key], value);
 }
 
+#[test]
+fn test_try_insert() {
+    let mut a = HashMap::new();
+
+    assert_eq!(a.try_insert(1, "one").unwrap(), &"one");
+    *a.try_insert(2, "two").unwrap() = "TWO";
+    assert_eq!(a[&2], "TWO");
+
+    let err = a.try_insert(1, "uno").unwrap_err();
+    assert_eq!(err.entry.key(), &1);
+    assert_eq!(err.entry.get(), &"one");
+    assert_eq!(err.value, "uno");
+    assert_eq!(err.to_string(), r#"failed to insert "uno", key 1 already exists with value "one""#);
+
+    // The occupied entry can still be used to update the map.
+    let OccupiedError { mut entry, value } = a.try_insert(1, "eins").unwrap_err();
+    entry.insert(value);
+    assert_eq!(a.len(), 2);
+    assert_eq!(a[&1], "eins");
+}
+
 #[test]
 fn test_retain() {
     let mut map: HashMap<i32, i32> = (0..100).map(|x| (x, x * 10)).collect();
@@ -465,6 +465,8 @@
 pub use core::result;
 #[unstable(feature = "async_stream", issue = "79024")]
 pub use core::stream;
+#[unstable(feature = "custom_test_frameworks", issue = "50297")]
+pub use core::test_case;
 #[stable(feature = "i128", since = "1.26.0")]
 #[allow(deprecated, deprecated_in_future)]
 pub use core::u128;
@@ -86,6 +86,10 @@ mod platform {
 ))]
 pub mod ucred;
 
+#[unstable(feature = "clock_instant", issue = "none")]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub mod time;
+
 /// A prelude for conveniently writing platform-specific code.
 ///
 /// Includes all extension traits, and some important type definitions.
@@ -0,0 +1,282 @@
+//! Measuring time against a chosen clock.
+//!
+//! [`Instant`] always uses `CLOCK_MONOTONIC`, which on Linux does not advance
+//! while the system is suspended. A [`ClockInstant`] works like an `Instant`,
+//! but reads the [`Clock`] it is given instead, such as [`Clock::Boot`] to
+//! keep counting across suspend:
+//!
+//! ```no_run
+//! #![feature(clock_instant)]
+//! use std::os::unix::time::{Clock, ClockInstant};
+//! use std::time::Duration;
+//!
+//! let deadline = ClockInstant::now(Clock::Boot) + Duration::from_secs(30);
+//! // ... the machine may be suspended here ...
+//! if ClockInstant::now(Clock::Boot) >= deadline {
+//!     println!("timed out");
+//! }
+//! ```
+//!
+//! [`Instant`]: crate::time::Instant
+
+#[cfg(test)]
+mod tests;
+
+use crate::cmp::Ordering;
+use crate::fmt;
+use crate::ops::{Add, AddAssign, Sub, SubAssign};
+use crate::sys::time;
+use crate::time::Duration;
+
+/// A clock that a [`ClockInstant`] can be read from.
+///
+/// Each clock is one of the clocks of `clock_gettime`. The clocks that measure
+/// elapsed time are monotonic; the CPU-time clocks only advance while the
+/// process or thread is running.
+#[unstable(feature = "clock_instant", issue = "none")]
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Clock {
+    /// `CLOCK_MONOTONIC`, the clock used by [`Instant`].
+    ///
+    /// It is not affected by changes of the system time, but stops while the
+    /// system is suspended.
+    ///
+    /// [`Instant`]: crate::time::Instant
+    Monotonic,
+    /// `CLOCK_MONOTONIC_RAW`, like [`Monotonic`], but not subject to the
+    /// frequency adjustments made by NTP.
+    ///
+    /// [`Monotonic`]: Clock::Monotonic
+    MonotonicRaw,
+    /// `CLOCK_BOOTTIME`, like [`Monotonic`], but it keeps advancing while the
+    /// system is suspended. Requires Linux 2.6.39 or later.
+    ///
+    /// [`Monotonic`]: Clock::Monotonic
+    Boot,
+    /// `CLOCK_PROCESS_CPUTIME_ID`, the CPU time consumed by all threads of
+    /// the current process.
+    ProcessCpuTime,
+    /// `CLOCK_THREAD_CPUTIME_ID`, the CPU time consumed by the current
+    /// thread.
+    ///
+    /// Readings of this clock are only comparable if they were taken on the
+    /// same thread.
+    ThreadCpuTime,
+}
+
+impl Clock {
+    fn as_raw(self) -> libc::clockid_t {
+        match self {
+            Clock::Monotonic => libc::CLOCK_MONOTONIC,
+            Clock::MonotonicRaw => libc::CLOCK_MONOTONIC_RAW,
+            Clock::Boot => libc::CLOCK_BOOTTIME,
+            Clock::ProcessCpuTime => libc::CLOCK_PROCESS_CPUTIME_ID,
+            Clock::ThreadCpuTime => libc::CLOCK_THREAD_CPUTIME_ID,
+        }
+    }
+}
+
+/// A measurement of a chosen [`Clock`].
+///
+/// This is the counterpart of [`Instant`] for clocks other than the one
+/// `Instant` uses, and has the same API. Instants can only be compared with
+/// or subtracted from instants of the same clock: they are unordered with
+/// respect to instants of other clocks, and subtracting them panics.
+///
+/// [`Instant`]: crate::time::Instant
+#[unstable(feature = "clock_instant", issue = "none")]
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct ClockInstant {
+    clock: Clock,
+    t: time::ClockTime,
+}
+
+impl ClockInstant {
+    /// Returns an instant corresponding to "now" on the given clock.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the clock is not supported by the running
+    /// kernel.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(clock_instant)]
+    /// use std::os::unix::time::{Clock, ClockInstant};
+    ///
+    /// let now = ClockInstant::now(Clock::Monotonic);
+    /// ```
+    #[unstable(feature = "clock_instant", issue = "none")]
+    pub fn now(clock: Clock) -> ClockInstant {
+        ClockInstant { clock, t: time::ClockTime::now(clock.as_raw()) }
+    }
+
+    /// Returns the clock this instant was read from.
+    #[unstable(feature = "clock_instant", issue = "none")]
+    pub fn clock(&self) -> Clock {
+        self.clock
+    }
+
+    /// Returns the amount of time elapsed from another instant to this one.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `earlier` is later than `self`, or if the
+    /// two instants were read from different clocks.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(clock_instant)]
+    /// use std::os::unix::time::{Clock, ClockInstant};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// let now = ClockInstant::now(Clock::Boot);
+    /// sleep(Duration::new(1, 0));
+    /// let new_now = ClockInstant::now(Clock::Boot);
+    /// println!("{:?}", new_now.duration_since(now));
+    /// ```
+    #[unstable(feature = "clock_instant", issue = "none")]
+    pub fn duration_since(&self, earlier: ClockInstant) -> Duration {
+        self.assert_same_clock(&earlier);
+        self.checked_duration_since(earlier).expect("supplied instant is later than self")
+    }
+
+    /// Returns the amount of time elapsed from another instant to this one,
+    /// or `None` if that instant is later than this one or was read from a
+    /// different clock.
+    #[unstable(feature = "clock_instant", issue = "none")]
+    pub fn checked_duration_since(&self, earlier: ClockInstant) -> Option<Duration> {
+        if self.clock != earlier.clock {
+            return None;
+        }
+        self.t.checked_sub_time(&earlier.t)
+    }
+
+    /// Returns the amount of time elapsed from another instant to this one,
+    /// or zero duration if that instant is later than this one.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the two instants were read from different
+    /// clocks.
+    #[unstable(feature = "clock_instant", issue = "none")]
+    pub fn saturating_duration_since(&self, earlier: ClockInstant) -> Duration {
+        self.assert_same_clock(&earlier);
+        self.checked_duration_since(earlier).unwrap_or_default()
+    }
+
+    /// Returns the amount of time elapsed on this instant's clock since the
+    /// instant was created.
+    ///
+    /// # Panics
+    ///
+    /// This function may panic if the current time is earlier than this
+    /// instant, which is something that can happen if a `ClockInstant` is
+    /// produced synthetically, or if a [`Clock::ThreadCpuTime`] instant is
+    /// used on another thread.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(clock_instant)]
+    /// use std::os::unix::time::{Clock, ClockInstant};
+    ///
+    /// let start = ClockInstant::now(Clock::ProcessCpuTime);
+    /// let sum: u64 = (0..1_000_000).sum();
+    /// println!("summed to {} using {:?} of CPU time", sum, start.elapsed());
+    /// ```
+    #[unstable(feature = "clock_instant", issue = "none")]
+    pub fn elapsed(&self) -> Duration {
+        ClockInstant::now(self.clock) - *self
+    }
+
+    /// Returns `Some(t)` where `t` is the time `self + duration` if `t` can be represented as
+    /// `ClockInstant` (which means it's inside the bounds of the underlying data structure),
+    /// `None` otherwise.
+    #[unstable(feature = "clock_instant", issue = "none")]
+    pub fn checked_add(&self, duration: Duration) -> Option<ClockInstant> {
+        let t = self.t.checked_add_duration(&duration)?;
+        Some(ClockInstant { clock: self.clock, t })
+    }
+
+    /// Returns `Some(t)` where `t` is the time `self - duration` if `t` can be represented as
+    /// `ClockInstant` (which means it's inside the bounds of the underlying data structure),
+    /// `None` otherwise.
+    #[unstable(feature = "clock_instant", issue = "none")]
+    pub fn checked_sub(&self, duration: Duration) -> Option<ClockInstant> {
+        let t = self.t.checked_sub_duration(&duration)?;
+        Some(ClockInstant { clock: self.clock, t })
+    }
+
+    fn assert_same_clock(&self, other: &ClockInstant) {
+        assert!(
+            self.clock == other.clock,
+            "instants were read from different clocks: {:?} and {:?}",
+            self.clock,
+            other.clock,
+        );
+    }
+}
+
+#[unstable(feature = "clock_instant", issue = "none")]
+impl PartialOrd for ClockInstant {
+    fn partial_cmp(&self, other: &ClockInstant) -> Option<Ordering> {
+        if self.clock == other.clock { Some(self.t.cmp(&other.t)) } else { None }
+    }
+}
+
+#[unstable(feature = "clock_instant", issue = "none")]
+impl Add<Duration> for ClockInstant {
+    type Output = ClockInstant;
+
+    /// # Panics
+    ///
+    /// This function may panic if the resulting point in time cannot be represented by the
+    /// underlying data structure. See [`ClockInstant::checked_add`] for a version without panic.
+    fn add(self, other: Duration) -> ClockInstant {
+        self.checked_add(other).expect("overflow when adding duration to instant")
+    }
+}
+
+#[unstable(feature = "clock_instant", issue = "none")]
+impl AddAssign<Duration> for ClockInstant {
+    fn add_assign(&mut self, other: Duration) {
+        *self = *self + other;
+    }
+}
+
+#[unstable(feature = "clock_instant", issue = "none")]
+impl Sub<Duration> for ClockInstant {
+    type Output = ClockInstant;
+
+    fn sub(self, other: Duration) -> ClockInstant {
+        self.checked_sub(other).expect("overflow when subtracting duration from instant")
+    }
+}
+
+#[unstable(feature = "clock_instant", issue = "none")]
+impl SubAssign<Duration> for ClockInstant {
+    fn sub_assign(&mut self, other: Duration) {
+        *self = *self - other;
+    }
+}
+
+#[unstable(feature = "clock_instant", issue = "none")]
+impl Sub<ClockInstant> for ClockInstant {
+    type Output = Duration;
+
+    fn sub(self, other: ClockInstant) -> Duration {
+        self.duration_since(other)
+    }
+}
+
+#[unstable(feature = "clock_instant", issue = "none")]
+impl fmt::Debug for ClockInstant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ClockInstant").field("clock", &self.clock).field("t", &self.t).finish()
+    }
+}
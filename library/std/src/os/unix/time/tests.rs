@@ -0,0 +1,67 @@
+use super::{Clock, ClockInstant};
+use crate::time::Duration;
+
+const CLOCKS: [Clock; 5] = [
+    Clock::Monotonic,
+    Clock::MonotonicRaw,
+    Clock::Boot,
+    Clock::ProcessCpuTime,
+    Clock::ThreadCpuTime,
+];
+
+#[test]
+fn clock_instant_monotonic() {
+    for &clock in &CLOCKS {
+        let a = ClockInstant::now(clock);
+        let b = ClockInstant::now(clock);
+        assert_eq!(a.clock(), clock);
+        assert!(b >= a, "{:?} went backwards", clock);
+        assert_eq!(a.saturating_duration_since(b), Duration::new(0, 0));
+    }
+}
+
+#[test]
+fn clock_instant_elapsed() {
+    let a = ClockInstant::now(Clock::Boot);
+    crate::thread::sleep(Duration::from_millis(10));
+    assert!(a.elapsed() >= Duration::from_millis(10));
+}
+
+#[test]
+fn clock_instant_math() {
+    let a = ClockInstant::now(Clock::Boot);
+    let b = ClockInstant::now(Clock::Boot);
+    let dur = b.duration_since(a);
+    assert_eq!(b - dur, a);
+    assert_eq!(a + dur, b);
+
+    let second = Duration::new(1, 0);
+    assert_eq!(a - second + second, a);
+    assert_eq!(a.checked_sub(second).unwrap().checked_add(second).unwrap(), a);
+
+    let mut c = a;
+    c += second;
+    c -= second;
+    assert_eq!(c, a);
+
+    let max_duration = Duration::from_secs(u64::MAX);
+    assert_eq!(a.checked_add(max_duration), None);
+    assert_eq!(a.checked_sub(max_duration), None);
+}
+
+#[test]
+fn clock_instant_different_clocks() {
+    let a = ClockInstant::now(Clock::Monotonic);
+    let b = ClockInstant::now(Clock::Boot);
+    assert_ne!(a, b);
+    assert_eq!(a.partial_cmp(&b), None);
+    assert_eq!(b.checked_duration_since(a), None);
+}
+
+#[test]
+#[should_panic]
+fn clock_instant_different_clocks_sub() {
+    let a = ClockInstant::now(Clock::Monotonic);
+    let b = ClockInstant::now(Clock::ProcessCpuTime);
+    let _ = b - a;
+}
@@ -465,6 +465,7 @@ pub fn resume_unwind(payload: Box<dyn Any + Send>) -> ! {
 
 /// Make all future panics abort directly without running the panic hook or unwinding.
 ///
+/// This also applies to [`resume_unwind`], and to panics on other threads.
 /// There is no way to undo this; the effect lasts until the process exits or
 /// execs (or the equivalent).
 ///
@@ -647,7 +647,16 @@ fn rust_panic_with_hook(
 /// This is the entry point for `resume_unwind`.
 /// It just forwards the payload to the panic runtime.
 pub fn rust_panic_without_hook(payload: Box<dyn Any + Send>) -> ! {
-    panic_count::increase();
+    let (must_abort, _) = panic_count::increase();
+
+    // Resuming an unwind is a panic as far as `panic::always_abort()` is
+    // concerned, even though the hook is not run for it.
+    if must_abort {
+        util::dumb_print(format_args!(
+            "resumed unwinding after panic::always_abort(), aborting.\n"
+        ));
+        intrinsics::abort()
+    }
 
     struct RewrapBox(Box<dyn Any + Send>);
 
@@ -986,6 +986,33 @@ pub fn get_envs(&self) -> CommandEnvs<'_> {
         self.inner.get_envs()
     }
 
+    /// Returns whether the environment will be cleared before the
+    /// environment variables from [`Command::get_envs`] are applied.
+    ///
+    /// This is `true` after [`Command::env_clear`] has been called. In that
+    /// case [`Command::get_envs`] only contains the variables that were set
+    /// afterwards, as removing a variable from an already cleared
+    /// environment is a no-op.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #![feature(command_access)]
+    /// use std::ffi::OsStr;
+    /// use std::process::Command;
+    ///
+    /// let mut cmd = Command::new("ls");
+    /// assert!(!cmd.get_env_clear());
+    /// cmd.env("TERM", "dumb").env_clear().env("LANG", "C");
+    /// assert!(cmd.get_env_clear());
+    /// let envs: Vec<(&OsStr, Option<&OsStr>)> = cmd.get_envs().collect();
+    /// assert_eq!(envs, &[(OsStr::new("LANG"), Some(OsStr::new("C")))]);
+    /// ```
+    #[unstable(feature = "command_access", issue = "44434")]
+    pub fn get_env_clear(&self) -> bool {
+        self.inner.get_env_clear()
+    }
+
     /// Returns the working directory for the child process.
     ///
     /// This returns [`None`] if the working directory will not be changed.
@@ -1013,6 +1040,9 @@ impl fmt::Debug for Command {
     /// Format the program and arguments of a Command for display. Any
     /// non-utf8 data is lossily converted using the utf8 replacement
     /// character.
+    ///
+    /// On Unix, the working directory and any changes to the environment
+    /// are also shown, in the style of a shell command line.
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         self.inner.fmt(f)
     }
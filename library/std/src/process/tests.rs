@@ -399,3 +399,23 @@ fn test_command_implements_send_sync() {
     fn take_send_sync_type<T: Send + Sync>(_: T) {}
     take_send_sync_type(Command::new(""))
 }
+
+#[test]
+#[cfg(unix)]
+fn test_command_debug() {
+    let mut command = Command::new("some-boring-name");
+    command.arg("1").arg("2");
+    assert_eq!(format!("{:?}", command), r#""some-boring-name" "1" "2""#);
+
+    command.env("TERM", "dumb").env_remove("TZ").current_dir("/some/path");
+    assert_eq!(
+        format!("{:?}", command),
+        r#"cd "/some/path" && env -u TZ TERM="dumb" "some-boring-name" "1" "2""#
+    );
+
+    command.env_clear().env("LANG", "C");
+    assert_eq!(
+        format!("{:?}", command),
+        r#"cd "/some/path" && env -i LANG="C" "some-boring-name" "1" "2""#
+    );
+}
@@ -195,6 +195,10 @@ pub fn get_envs(&self) -> CommandEnvs<'_> {
         self.env.iter()
     }
 
+    pub fn get_env_clear(&self) -> bool {
+        self.env.does_clear()
+    }
+
     pub fn get_current_dir(&self) -> Option<&Path> {
         self.cwd.as_ref().map(|cs| Path::new(OsStr::from_bytes(cs.as_bytes())))
     }
@@ -403,6 +407,32 @@ pub fn fd(&self) -> Option<c_int> {
 
 impl fmt::Debug for Command {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(ref cwd) = self.cwd {
+            write!(f, "cd {:?} && ", cwd)?;
+        }
+        if self.env.does_clear() {
+            // Any variables set afterwards are printed below, which matches
+            // what `env -i` does with them.
+            write!(f, "env -i ")?;
+        } else {
+            // Removed variables can only be expressed by wrapping the
+            // command in `env`.
+            let mut any_removed = false;
+            for (key, value) in self.get_envs() {
+                if value.is_none() {
+                    if !any_removed {
+                        write!(f, "env ")?;
+                        any_removed = true;
+                    }
+                    write!(f, "-u {} ", key.to_string_lossy())?;
+                }
+            }
+        }
+        for (key, value) in self.get_envs() {
+            if let Some(value) = value {
+                write!(f, "{}={:?} ", key.to_string_lossy(), value)?;
+            }
+        }
         if self.program != self.args[0] {
             write!(f, "[{:?}] ", self.program)?;
         }
@@ -3,6 +3,8 @@
 
 use core::hash::{Hash, Hasher};
 
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub use self::inner::ClockTime;
 pub use self::inner::{Instant, SystemTime, UNIX_EPOCH};
 use crate::convert::TryInto;
 
@@ -361,6 +363,42 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         }
     }
 
+    /// A reading of an arbitrary clock, as exposed by `std::os::unix::time`.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    pub struct ClockTime {
+        t: Timespec,
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    impl ClockTime {
+        pub fn now(clock: clock_t) -> ClockTime {
+            ClockTime { t: now(clock) }
+        }
+
+        pub fn checked_sub_time(&self, other: &ClockTime) -> Option<Duration> {
+            self.t.sub_timespec(&other.t).ok()
+        }
+
+        pub fn checked_add_duration(&self, other: &Duration) -> Option<ClockTime> {
+            Some(ClockTime { t: self.t.checked_add_duration(other)? })
+        }
+
+        pub fn checked_sub_duration(&self, other: &Duration) -> Option<ClockTime> {
+            Some(ClockTime { t: self.t.checked_sub_duration(other)? })
+        }
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    impl fmt::Debug for ClockTime {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("ClockTime")
+                .field("tv_sec", &self.t.t.tv_sec)
+                .field("tv_nsec", &self.t.t.tv_nsec)
+                .finish()
+        }
+    }
+
     #[cfg(not(target_os = "dragonfly"))]
     pub type clock_t = libc::c_int;
     #[cfg(target_os = "dragonfly")]
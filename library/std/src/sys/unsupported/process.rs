@@ -63,6 +63,10 @@ pub fn get_envs(&self) -> CommandEnvs<'_> {
         self.env.iter()
     }
 
+    pub fn get_env_clear(&self) -> bool {
+        self.env.does_clear()
+    }
+
     pub fn get_current_dir(&self) -> Option<&Path> {
         None
     }
@@ -55,6 +55,10 @@ pub fn apply(&self) {
         }
     }
 
+    pub fn does_clear(&self) -> bool {
+        self.clear
+    }
+
     pub fn is_unchanged(&self) -> bool {
         !self.clear && self.vars.is_empty()
     }
@@ -9,8 +9,13 @@
 
 use crate::stats;
 use std::cmp;
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
 use std::io;
+use std::mem;
 use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
@@ -32,6 +37,7 @@ pub fn black_box<T>(dummy: T) -> T {
 pub struct Bencher {
     mode: BenchMode,
     summary: Option<stats::Summary>,
+    samples: Vec<f64>,
     pub bytes: u64,
 }
 
@@ -46,7 +52,9 @@ pub fn iter<T, F>(&mut self, mut inner: F)
             return;
         }
 
-        self.summary = Some(iter(&mut inner));
+        let (summary, samples) = iter_samples(&mut inner);
+        self.summary = Some(summary);
+        self.samples = samples;
     }
 
     pub fn bench<F>(&mut self, mut f: F) -> Option<stats::Summary>
@@ -62,6 +70,8 @@ pub fn bench<F>(&mut self, mut f: F) -> Option<stats::Summary>
 pub struct BenchSamples {
     pub ns_iter_summ: stats::Summary,
     pub mb_s: usize,
+    /// The ns/iter samples `ns_iter_summ` was computed from.
+    pub samples: Vec<f64>,
 }
 
 pub fn fmt_bench_samples(bs: &BenchSamples) -> String {
@@ -120,6 +130,14 @@ fn ns_iter_inner<T, F>(inner: &mut F, k: u64) -> u64
 }
 
 pub fn iter<T, F>(inner: &mut F) -> stats::Summary
+where
+    F: FnMut() -> T,
+{
+    iter_samples(inner).0
+}
+
+/// Like `iter`, but also returns the samples that the summary was computed from.
+fn iter_samples<T, F>(inner: &mut F) -> (stats::Summary, Vec<f64>)
 where
     F: FnMut() -> T,
 {
@@ -166,13 +184,13 @@ pub fn iter<T, F>(inner: &mut F) -> stats::Summary
             && summ.median_abs_dev_pct < 1.0
             && summ.median - summ5.median < summ5.median_abs_dev
         {
-            return summ5;
+            return (summ5, samples.to_vec());
         }
 
         total_run += loop_run;
         // Longest we ever run for is 3s.
         if total_run > Duration::from_secs(3) {
-            return summ5;
+            return (summ5, samples.to_vec());
         }
 
         // If we overflow here just return the results so far. We check a
@@ -182,7 +200,7 @@ pub fn iter<T, F>(inner: &mut F) -> stats::Summary
         n = match n.checked_mul(10) {
             Some(_) => n * 2,
             None => {
-                return summ5;
+                return (summ5, samples.to_vec());
             }
         };
     }
@@ -197,7 +215,7 @@ pub fn benchmark<F>(
 ) where
     F: FnMut(&mut Bencher),
 {
-    let mut bs = Bencher { mode: BenchMode::Auto, summary: None, bytes: 0 };
+    let mut bs = Bencher { mode: BenchMode::Auto, summary: None, samples: Vec::new(), bytes: 0 };
 
     let data = Arc::new(Mutex::new(Vec::new()));
 
@@ -215,14 +233,19 @@ pub fn benchmark<F>(
             let ns_iter = cmp::max(ns_iter_summ.median as u64, 1);
             let mb_s = bs.bytes * 1000 / ns_iter;
 
-            let bs = BenchSamples { ns_iter_summ, mb_s: mb_s as usize };
+            let samples = mem::take(&mut bs.samples);
+            let bs = BenchSamples { ns_iter_summ, mb_s: mb_s as usize, samples };
             TestResult::TrBench(bs)
         }
         Ok(None) => {
             // iter not called, so no data.
             // FIXME: error in this case?
             let samples: &mut [f64] = &mut [0.0_f64; 1];
-            let bs = BenchSamples { ns_iter_summ: stats::Summary::new(samples), mb_s: 0 };
+            let bs = BenchSamples {
+                ns_iter_summ: stats::Summary::new(samples),
+                mb_s: 0,
+                samples: samples.to_vec(),
+            };
             TestResult::TrBench(bs)
         }
         Err(_) => TestResult::TrFailed,
@@ -237,6 +260,171 @@ pub fn run_once<F>(f: F)
 where
     F: FnMut(&mut Bencher),
 {
-    let mut bs = Bencher { mode: BenchMode::Single, summary: None, bytes: 0 };
+    let mut bs = Bencher { mode: BenchMode::Single, summary: None, samples: Vec::new(), bytes: 0 };
     bs.bench(f);
 }
+
+/// The samples of the benchmarks of an earlier run, saved with `--save-baseline`
+/// so that later runs can be compared against them with `--baseline`.
+///
+/// Baselines are stored in the directory given by the `RUST_BENCH_BASELINE_DIR`
+/// environment variable, or in a `bench-baselines` directory next to the
+/// benchmark executable. Each executable has its own file per baseline name,
+/// so that benchmarks of different executables can have the same name.
+#[derive(Debug, Default)]
+pub(crate) struct Baseline {
+    samples: BTreeMap<String, Vec<f64>>,
+}
+
+/// The level below which the p-value of a comparison counts as a change.
+const BASELINE_SIGNIFICANCE: f64 = 0.05;
+
+/// Changes of the median by less than this many percent are treated as noise,
+/// even if they are statistically significant.
+const BASELINE_NOISE_PCT: f64 = 2.0;
+
+const BASELINE_HEADER: &str = "# libtest benchmark baseline, version 1";
+
+impl Baseline {
+    pub fn insert(&mut self, name: &str, samples: &[f64]) {
+        self.samples.insert(name.to_owned(), samples.to_vec());
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    pub fn load(name: &str) -> io::Result<Baseline> {
+        let path = Self::path(name)?;
+        let contents = fs::read_to_string(&path).map_err(|e| {
+            io::Error::new(
+                e.kind(),
+                format!("failed to read benchmark baseline `{}` ({}): {}", name, path.display(), e),
+            )
+        })?;
+        Self::parse(&contents).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid benchmark baseline `{}` ({})", name, path.display()),
+            )
+        })
+    }
+
+    pub fn save(&self, name: &str) -> io::Result<()> {
+        let path = Self::path(name)?;
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write(path, self.serialize())
+    }
+
+    fn path(name: &str) -> io::Result<PathBuf> {
+        if name.is_empty() || name == "." || name == ".." || name.contains(&['/', '\\'][..]) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("invalid benchmark baseline name `{}`", name),
+            ));
+        }
+        let exe = env::current_exe()?;
+        let dir = match env::var_os("RUST_BENCH_BASELINE_DIR") {
+            Some(dir) => PathBuf::from(dir),
+            None => exe.with_file_name("bench-baselines"),
+        };
+        let mut file_name = exe.file_stem().unwrap_or_default().to_owned();
+        file_name.push(".txt");
+        Ok(dir.join(name).join(file_name))
+    }
+
+    // Every line holds the samples of one benchmark, separated by spaces,
+    // followed by a tab and the name of the benchmark.
+    pub fn parse(contents: &str) -> Option<Baseline> {
+        let mut lines = contents.lines();
+        if lines.next()? != BASELINE_HEADER {
+            return None;
+        }
+        let mut samples = BTreeMap::new();
+        for line in lines {
+            let (values, name) = line.split_once('\t')?;
+            let values =
+                values.split(' ').map(|value| value.parse().ok()).collect::<Option<Vec<f64>>>()?;
+            samples.insert(name.to_owned(), values);
+        }
+        Some(Baseline { samples })
+    }
+
+    pub fn serialize(&self) -> String {
+        let mut contents = format!("{}\n", BASELINE_HEADER);
+        // Names that span several lines can't be stored, so they are skipped.
+        for (name, samples) in self.samples.iter().filter(|(name, _)| !name.contains('\n')) {
+            let values = samples.iter().map(|value| value.to_string()).collect::<Vec<_>>();
+            contents.push_str(&format!("{}\t{}\n", values.join(" "), name));
+        }
+        contents
+    }
+
+    /// Compares the samples of the benchmark `name` with the ones in this baseline.
+    pub fn compare(&self, name: &str, bs: &BenchSamples) -> Option<BaselineComparison> {
+        let baseline = self.samples.get(name)?;
+        if baseline.is_empty() || bs.samples.is_empty() {
+            return None;
+        }
+        let baseline_median = stats::Stats::median(&baseline[..]);
+        // There is no relative change from a median of 0, e.g. of a benchmark
+        // whose body was optimized away.
+        if baseline_median == 0.0 {
+            return None;
+        }
+        let test = stats::mann_whitney_u(&bs.samples, baseline);
+        let change_pct = (bs.ns_iter_summ.median - baseline_median) / baseline_median * 100.0;
+        let change =
+            if test.p_value >= BASELINE_SIGNIFICANCE || change_pct.abs() < BASELINE_NOISE_PCT {
+                BaselineChange::None
+            } else if test.z > 0.0 {
+                BaselineChange::Regressed
+            } else {
+                BaselineChange::Improved
+            };
+        Some(BaselineComparison { baseline_median, change_pct, p_value: test.p_value, change })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum BaselineChange {
+    /// The difference is not statistically significant, or is within the noise.
+    None,
+    Regressed,
+    Improved,
+}
+
+/// The result of comparing a benchmark with its baseline.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct BaselineComparison {
+    pub baseline_median: f64,
+    /// The change of the median relative to the baseline, in percent.
+    pub change_pct: f64,
+    /// The p-value of the Mann-Whitney U test of both sample sets.
+    pub p_value: f64,
+    pub change: BaselineChange,
+}
+
+impl BaselineComparison {
+    /// The confidence that the benchmark changed, in percent.
+    pub fn confidence_pct(&self) -> f64 {
+        (1.0 - self.p_value) * 100.0
+    }
+}
+
+pub(crate) fn fmt_baseline_comparison(comparison: &BaselineComparison) -> String {
+    let change = match comparison.change {
+        BaselineChange::None => {
+            return format!("[{:+.2}%, no significant change]", comparison.change_pct);
+        }
+        BaselineChange::Regressed => "regressed",
+        BaselineChange::Improved => "improved",
+    };
+    // Don't round very small p-values to a confidence of 100%.
+    let confidence = if comparison.p_value < 0.001 {
+        ">99.9%".to_owned()
+    } else {
+        format!("{:.1}%", comparison.confidence_pct())
+    };
+    format!("[{:+.2}%, {} with {} confidence]", comparison.change_pct, change, confidence)
+}
@@ -2,9 +2,10 @@
 
 use std::env;
 use std::path::PathBuf;
+use std::time::Duration;
 
 use super::helpers::isatty;
-use super::options::{ColorConfig, Options, OutputFormat, RunIgnored};
+use super::options::{ColorConfig, Options, OutputFormat, RunIgnored, Shard};
 use super::time::TestTimeOptions;
 
 #[derive(Debug)]
@@ -24,6 +25,22 @@ pub struct TestOpts {
     pub test_threads: Option<usize>,
     pub skip: Vec<String>,
     pub time_options: Option<TestTimeOptions>,
+    /// Run the tests in random order.
+    pub shuffle: bool,
+    /// Run the tests in the random order given by this seed. Implies `shuffle`.
+    pub shuffle_seed: Option<u64>,
+    /// Only run the tests that belong to this shard.
+    pub shard: Option<Shard>,
+    /// Kill tests that are still running after this long, and report them as
+    /// failed. Requires running each test in its own process.
+    pub test_timeout: Option<Duration>,
+    /// Run failed tests again up to this many times. Tests that pass on a
+    /// later attempt are reported as flaky.
+    pub retries: usize,
+    /// Save the samples of the benchmarks as the baseline with this name.
+    pub save_baseline: Option<String>,
+    /// Compare the benchmarks with the baseline of this name.
+    pub baseline: Option<String>,
     pub options: Options,
 }
 
@@ -95,8 +112,9 @@ fn optgroups() -> getopts::Options {
             "Configure formatting of output:
             pretty = Print verbose output;
             terse  = Display one character per test;
-            json   = Output a json document",
-            "pretty|terse|json",
+            json   = Output a json document;
+            junit  = Output a JUnit document",
+            "pretty|terse|json|junit",
         )
         .optflag("", "show-output", "Show captured stdout of successful tests")
         .optopt(
@@ -137,6 +155,67 @@ fn optgroups() -> getopts::Options {
 
             `CRITICAL_TIME` here means the limit that should not be exceeded by test.
             ",
+        )
+        .optflag(
+            "",
+            "shuffle",
+            "Run tests in random order. The seed that was used is printed,
+            so that the order can be reproduced with `--shuffle-seed`.
+
+            This can also be enabled with the `RUST_TEST_SHUFFLE`
+            environment variable.",
+        )
+        .optopt(
+            "",
+            "shuffle-seed",
+            "Run tests in random order; seed the random number generator with SEED.
+
+            This can also be set with the `RUST_TEST_SHUFFLE_SEED`
+            environment variable.",
+            "SEED",
+        )
+        .optopt(
+            "",
+            "shard-index",
+            "Only run the tests of the shard with the zero-based index INDEX.
+            Tests are assigned to shards by a stable hash of their name.
+            Requires `--shard-count`.",
+            "INDEX",
+        )
+        .optopt(
+            "",
+            "shard-count",
+            "Split the tests into COUNT shards. Requires `--shard-index`.",
+            "COUNT",
+        )
+        .optopt(
+            "",
+            "test-timeout",
+            "Kill tests that run for longer than SECS seconds and report them
+            as failed. Each test is run in a separate process, except
+            dynamic tests, which are run in-process without a timeout.",
+            "SECS",
+        )
+        .optopt(
+            "",
+            "retries",
+            "Run failed tests again up to N times. Tests that pass on a later
+            attempt are reported as flaky instead of ok.",
+            "N",
+        )
+        .optopt(
+            "",
+            "save-baseline",
+            "Save the samples of the benchmarks as the baseline NAME, to
+            compare later runs against it with `--baseline`.",
+            "NAME",
+        )
+        .optopt(
+            "",
+            "baseline",
+            "Compare the benchmarks with the baseline NAME, which was saved
+            with `--save-baseline`.",
+            "NAME",
         );
     opts
 }
@@ -171,7 +250,8 @@ fn usage(binary: &str, options: &getopts::Options) {
     `#[ignore]`       - When applied to a function which is already attributed as a
                         test, then the test runner will ignore these tests during
                         normal test runs. Running with --ignored or --include-ignored will run
-                        these tests."#,
+                        these tests. A reason may be provided, which is shown in
+                        the output: #[ignore = "reason"]."#,
         usage = options.usage(&message)
     );
 }
@@ -217,6 +297,22 @@ macro_rules! unstable_optflag {
     }};
 }
 
+// Gets the option value and checks if unstable features are enabled.
+macro_rules! unstable_optopt {
+    ($matches:ident, $allow_unstable:ident, $option_name:literal) => {{
+        let opt = $matches.opt_str($option_name);
+        if !$allow_unstable && opt.is_some() {
+            return Err(format!(
+                "The \"{}\" option is only accepted on the nightly compiler with \
+                 -Z unstable-options",
+                $option_name
+            ));
+        }
+
+        opt
+    }};
+}
+
 // Implementation of `parse_opts` that doesn't care about help message
 // and returns a `Result`.
 fn parse_opts_impl(matches: getopts::Matches) -> OptRes {
@@ -226,6 +322,13 @@ fn parse_opts_impl(matches: getopts::Matches) -> OptRes {
     let force_run_in_process = unstable_optflag!(matches, allow_unstable, "force-run-in-process");
     let exclude_should_panic = unstable_optflag!(matches, allow_unstable, "exclude-should-panic");
     let time_options = get_time_options(&matches, allow_unstable)?;
+    let shuffle = get_shuffle(&matches, allow_unstable)?;
+    let shuffle_seed = get_shuffle_seed(&matches, allow_unstable)?;
+    let shard = get_shard(&matches, allow_unstable)?;
+    let test_timeout = get_test_timeout(&matches, allow_unstable)?;
+    let retries = get_retries(&matches, allow_unstable)?;
+    let save_baseline = unstable_optopt!(matches, allow_unstable, "save-baseline");
+    let baseline = unstable_optopt!(matches, allow_unstable, "baseline");
 
     let include_ignored = matches.opt_present("include-ignored");
     let quiet = matches.opt_present("quiet");
@@ -244,6 +347,15 @@ fn parse_opts_impl(matches: getopts::Matches) -> OptRes {
     let color = get_color_config(&matches)?;
     let format = get_format(&matches, quiet, allow_unstable)?;
 
+    // JSON and JUnit reports always record the duration of each test, so
+    // measure it even if `--report-time` wasn't passed.
+    let time_options = match (format, time_options) {
+        (OutputFormat::Json | OutputFormat::Junit, None) => {
+            Some(TestTimeOptions::new_from_env(false, false))
+        }
+        (_, time_options) => time_options,
+    };
+
     let options = Options::new().display_output(matches.opt_present("show-output"));
 
     let test_opts = TestOpts {
@@ -262,6 +374,13 @@ fn parse_opts_impl(matches: getopts::Matches) -> OptRes {
         test_threads,
         skip,
         time_options,
+        shuffle,
+        shuffle_seed,
+        shard,
+        test_timeout,
+        retries,
+        save_baseline,
+        baseline,
         options,
     };
 
@@ -321,6 +440,118 @@ fn get_test_threads(matches: &getopts::Matches) -> OptPartRes<Option<usize>> {
     Ok(test_threads)
 }
 
+fn get_shuffle(matches: &getopts::Matches, allow_unstable: bool) -> OptPartRes<bool> {
+    let mut shuffle = unstable_optflag!(matches, allow_unstable, "shuffle");
+    if !shuffle && allow_unstable {
+        shuffle = match env::var("RUST_TEST_SHUFFLE") {
+            Ok(val) => &val != "0",
+            Err(_) => false,
+        };
+    }
+
+    Ok(shuffle)
+}
+
+fn get_shuffle_seed(matches: &getopts::Matches, allow_unstable: bool) -> OptPartRes<Option<u64>> {
+    let mut shuffle_seed = match unstable_optopt!(matches, allow_unstable, "shuffle-seed") {
+        Some(n_str) => match n_str.parse::<u64>() {
+            Ok(n) => Some(n),
+            Err(e) => {
+                return Err(format!(
+                    "argument for --shuffle-seed must be a number \
+                     (error: {})",
+                    e
+                ));
+            }
+        },
+        None => None,
+    };
+
+    if shuffle_seed.is_none() && allow_unstable {
+        shuffle_seed = match env::var("RUST_TEST_SHUFFLE_SEED") {
+            Ok(val) => match val.parse::<u64>() {
+                Ok(n) => Some(n),
+                Err(_) => {
+                    return Err(format!(
+                        "RUST_TEST_SHUFFLE_SEED is `{}`, should be a number.",
+                        val
+                    ));
+                }
+            },
+            Err(_) => None,
+        };
+    }
+
+    Ok(shuffle_seed)
+}
+
+fn get_shard(matches: &getopts::Matches, allow_unstable: bool) -> OptPartRes<Option<Shard>> {
+    let parse = |option_name: &str, value: Option<String>| match value {
+        Some(n_str) => match n_str.parse::<usize>() {
+            Ok(n) => Ok(Some(n)),
+            Err(e) => {
+                Err(format!("argument for --{} must be a number (error: {})", option_name, e))
+            }
+        },
+        None => Ok(None),
+    };
+    let index = parse("shard-index", unstable_optopt!(matches, allow_unstable, "shard-index"))?;
+    let count = parse("shard-count", unstable_optopt!(matches, allow_unstable, "shard-count"))?;
+
+    let shard = match (index, count) {
+        (None, None) => None,
+        (Some(_), None) | (None, Some(_)) => {
+            return Err("the options --shard-index and --shard-count must be used together".into());
+        }
+        (Some(_), Some(0)) => return Err("argument for --shard-count must not be 0".into()),
+        (Some(index), Some(count)) if index >= count => {
+            return Err(format!(
+                "argument for --shard-index must be less than --shard-count ({} >= {})",
+                index, count
+            ));
+        }
+        (Some(index), Some(count)) => Some(Shard { index, count }),
+    };
+
+    Ok(shard)
+}
+
+fn get_test_timeout(
+    matches: &getopts::Matches,
+    allow_unstable: bool,
+) -> OptPartRes<Option<Duration>> {
+    let test_timeout = match unstable_optopt!(matches, allow_unstable, "test-timeout") {
+        Some(n_str) => match n_str.parse::<u64>() {
+            Ok(0) => return Err("argument for --test-timeout must not be 0".to_string()),
+            Ok(n) => Some(Duration::from_secs(n)),
+            Err(e) => {
+                return Err(format!(
+                    "argument for --test-timeout must be a number > 0 \
+                     (error: {})",
+                    e
+                ));
+            }
+        },
+        None => None,
+    };
+
+    Ok(test_timeout)
+}
+
+fn get_retries(matches: &getopts::Matches, allow_unstable: bool) -> OptPartRes<usize> {
+    let retries = match unstable_optopt!(matches, allow_unstable, "retries") {
+        Some(n_str) => match n_str.parse::<usize>() {
+            Ok(n) => n,
+            Err(e) => {
+                return Err(format!("argument for --retries must be a number (error: {})", e));
+            }
+        },
+        None => 0,
+    };
+
+    Ok(retries)
+}
+
 fn get_format(
     matches: &getopts::Matches,
     quiet: bool,
@@ -336,10 +567,16 @@ fn get_format(
             }
             OutputFormat::Json
         }
+        Some("junit") => {
+            if !allow_unstable {
+                return Err("The \"junit\" format is only accepted on the nightly compiler".into());
+            }
+            OutputFormat::Junit
+        }
 
         Some(v) => {
             return Err(format!(
-                "argument for --format must be pretty, terse, or json (was \
+                "argument for --format must be pretty, terse, json or junit (was \
                  {})",
                 v
             ));
@@ -6,12 +6,16 @@
 use std::time::Instant;
 
 use super::{
-    bench::fmt_bench_samples,
+    bench::{fmt_bench_samples, Baseline},
     cli::TestOpts,
     event::{CompletedTest, TestEvent},
     filter_tests,
-    formatters::{JsonFormatter, OutputFormatter, PrettyFormatter, TerseFormatter},
-    helpers::{concurrency::get_concurrency, metrics::MetricMap},
+    formatters::{JsonFormatter, JunitFormatter, OutputFormatter, PrettyFormatter, TerseFormatter},
+    helpers::{
+        concurrency::get_concurrency,
+        metrics::MetricMap,
+        shuffle::{get_shuffle_seed, shuffle_tests},
+    },
     options::{Options, OutputFormat},
     run_tests,
     test_result::TestResult,
@@ -48,13 +52,19 @@ pub struct ConsoleTestState {
     pub failed: usize,
     pub ignored: usize,
     pub allowed_fail: usize,
+    pub flaky: usize,
     pub filtered_out: usize,
     pub measured: usize,
     pub exec_time: Option<TestSuiteExecTime>,
     pub metrics: MetricMap,
+    /// The baseline to compare the benchmarks with, see `--baseline`.
+    pub baseline: Option<Baseline>,
+    /// The samples of the benchmarks of this run, see `--save-baseline`.
+    pub bench_samples: Baseline,
     pub failures: Vec<(TestDesc, Vec<u8>)>,
     pub not_failures: Vec<(TestDesc, Vec<u8>)>,
     pub time_failures: Vec<(TestDesc, Vec<u8>)>,
+    pub flaky_tests: Vec<(TestDesc, Vec<u8>)>,
     pub options: Options,
 }
 
@@ -64,6 +74,10 @@ pub fn new(opts: &TestOpts) -> io::Result<ConsoleTestState> {
             Some(ref path) => Some(File::create(path)?),
             None => None,
         };
+        let baseline = match opts.baseline {
+            Some(ref name) => Some(Baseline::load(name)?),
+            None => None,
+        };
 
         Ok(ConsoleTestState {
             log_out,
@@ -72,13 +86,17 @@ pub fn new(opts: &TestOpts) -> io::Result<ConsoleTestState> {
             failed: 0,
             ignored: 0,
             allowed_fail: 0,
+            flaky: 0,
             filtered_out: 0,
             measured: 0,
             exec_time: None,
             metrics: MetricMap::new(),
+            baseline,
+            bench_samples: Baseline::default(),
             failures: Vec::new(),
             not_failures: Vec::new(),
             time_failures: Vec::new(),
+            flaky_tests: Vec::new(),
             options: opts.options,
         })
     }
@@ -115,6 +133,8 @@ pub fn write_log_result(
                     TestResult::TrAllowedFail => "failed (allowed)".to_owned(),
                     TestResult::TrBench(ref bs) => fmt_bench_samples(bs),
                     TestResult::TrTimedFail => "failed (time limit exceeded)".to_owned(),
+                    TestResult::TrTimedOut => "failed (timed out)".to_owned(),
+                    TestResult::TrFlaky(failed) => format!("flaky ({} failed attempts)", failed),
                 },
                 test.name,
             )
@@ -126,7 +146,7 @@ pub fn write_log_result(
     }
 
     fn current_test_count(&self) -> usize {
-        self.passed + self.failed + self.ignored + self.measured + self.allowed_fail
+        self.passed + self.failed + self.ignored + self.measured + self.allowed_fail + self.flaky
     }
 }
 
@@ -143,10 +163,16 @@ pub fn list_tests_console(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> io::Res
     let mut ntest = 0;
     let mut nbench = 0;
 
-    for test in filter_tests(&opts, tests) {
+    let shuffle_seed = get_shuffle_seed(opts);
+    let mut tests = filter_tests(&opts, tests);
+    if let Some(shuffle_seed) = shuffle_seed {
+        shuffle_tests(shuffle_seed, &mut tests, |test| &test.desc.name);
+    }
+
+    for test in tests {
         use crate::TestFn::*;
 
-        let TestDescAndFn { desc: TestDesc { name, .. }, testfn } = test;
+        let TestDescAndFn { desc, testfn } = test;
 
         let fntype = match testfn {
             StaticTestFn(..) | DynTestFn(..) => {
@@ -159,8 +185,14 @@ pub fn list_tests_console(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> io::Res
             }
         };
 
-        writeln!(output, "{}: {}", name, fntype)?;
-        st.write_log(|| format!("{} {}\n", fntype, name))?;
+        // The terse listing is parsed by tools, so it stays one name per line.
+        match desc.ignore_message() {
+            Some(message) if desc.ignore && !quiet => {
+                writeln!(output, "{}: {} (ignored: {})", desc.name, fntype, message)?
+            }
+            _ => writeln!(output, "{}: {}", desc.name, fntype)?,
+        }
+        st.write_log(|| format!("{} {}\n", fntype, desc.name))?;
     }
 
     fn plural(count: u32, s: &str) -> String {
@@ -176,6 +208,10 @@ fn plural(count: u32, s: &str) -> String {
         }
 
         writeln!(output, "{}, {}", plural(ntest, "test"), plural(nbench, "benchmark"))?;
+
+        if let Some(shuffle_seed) = shuffle_seed {
+            writeln!(output, "shuffle seed: {}", shuffle_seed)?;
+        }
     }
 
     Ok(())
@@ -198,6 +234,7 @@ fn handle_test_result(st: &mut ConsoleTestState, completed_test: CompletedTest)
                 bs.ns_iter_summ.median,
                 bs.ns_iter_summ.max - bs.ns_iter_summ.min,
             );
+            st.bench_samples.insert(test.name.as_slice(), &bs.samples);
             st.measured += 1
         }
         TestResult::TrFailed => {
@@ -214,6 +251,18 @@ fn handle_test_result(st: &mut ConsoleTestState, completed_test: CompletedTest)
             st.failed += 1;
             st.time_failures.push((test, stdout));
         }
+        TestResult::TrTimedOut => {
+            st.failed += 1;
+            let mut stdout = stdout;
+            stdout.extend_from_slice(
+                b"note: test was killed after exceeding the --test-timeout limit",
+            );
+            st.failures.push((test, stdout));
+        }
+        TestResult::TrFlaky(_) => {
+            st.flaky += 1;
+            st.flaky_tests.push((test, stdout));
+        }
     }
 }
 
@@ -225,9 +274,9 @@ fn on_test_event(
     out: &mut dyn OutputFormatter,
 ) -> io::Result<()> {
     match (*event).clone() {
-        TestEvent::TeFiltered(ref filtered_tests) => {
+        TestEvent::TeFiltered(ref filtered_tests, shuffle_seed) => {
             st.total = filtered_tests.len();
-            out.write_run_start(filtered_tests.len())?;
+            out.write_run_start(filtered_tests.len(), shuffle_seed)?;
         }
         TestEvent::TeFilteredOut(filtered_out) => {
             st.filtered_out = filtered_out;
@@ -276,7 +325,8 @@ pub fn run_tests_console(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> io::Resu
         OutputFormat::Terse => {
             Box::new(TerseFormatter::new(output, opts.use_color(), max_name_len, is_multithreaded))
         }
-        OutputFormat::Json => Box::new(JsonFormatter::new(output)),
+        OutputFormat::Json => Box::new(JsonFormatter::new(output, opts.shard)),
+        OutputFormat::Junit => Box::new(JunitFormatter::new(output)),
     };
     let mut st = ConsoleTestState::new(opts)?;
 
@@ -291,6 +341,16 @@ pub fn run_tests_console(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> io::Resu
 
     assert!(st.current_test_count() == st.total);
 
+    if let Some(ref name) = opts.save_baseline {
+        // Don't overwrite a baseline with an empty one when no benchmarks ran,
+        // e.g. because of a filter or a missing `--bench`.
+        if st.bench_samples.is_empty() {
+            eprintln!("warning: no benchmarks were run, the baseline `{}` was not saved", name);
+        } else {
+            st.bench_samples.save(name)?;
+        }
+    }
+
     out.write_run_finish(&st)
 }
 
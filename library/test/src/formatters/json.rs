@@ -1,20 +1,38 @@
+//! JSON output, with one event per line.
+//!
+//! The events are documented in `src/doc/rustc/src/tests/index.md`. Fields
+//! that are not covered by `FORMAT_VERSION` yet are nested in an `unstable`
+//! object, so that they can change without a new version.
+
 use std::{borrow::Cow, io, io::prelude::Write};
 
 use super::OutputFormatter;
 use crate::{
+    bench::BaselineChange,
     console::{ConsoleTestState, OutputLocation},
+    options::Shard,
     test_result::TestResult,
     time,
     types::TestDesc,
 };
 
+/// The version of the event schema. It is only increased for changes that
+/// are incompatible with existing consumers, not for new fields or events.
+const FORMAT_VERSION: u32 = 1;
+
 pub(crate) struct JsonFormatter<T> {
     out: OutputLocation<T>,
+    shard: Option<Shard>,
 }
 
 impl<T: Write> JsonFormatter<T> {
-    pub fn new(out: OutputLocation<T>) -> Self {
-        Self { out }
+    pub fn new(out: OutputLocation<T>, shard: Option<Shard>) -> Self {
+        Self { out, shard }
+    }
+
+    #[cfg(test)]
+    pub fn output_location(&self) -> &OutputLocation<T> {
+        &self.out
     }
 
     fn writeln_message(&mut self, s: &str) -> io::Result<()> {
@@ -38,6 +56,7 @@ fn write_event(
         exec_time: Option<&time::TestExecTime>,
         stdout: Option<Cow<'_, str>>,
         extra: Option<&str>,
+        unstable: Option<&str>,
     ) -> io::Result<()> {
         // A doc test's name includes a filename which must be escaped for correct json.
         self.write_message(&*format!(
@@ -55,15 +74,31 @@ fn write_event(
         if let Some(extra) = extra {
             self.write_message(&*format!(r#", {}"#, extra))?;
         }
+        if let Some(unstable) = unstable {
+            self.write_message(&*format!(r#", "unstable": {{ {} }}"#, unstable))?;
+        }
         self.writeln_message(" }")
     }
 }
 
 impl<T: Write> OutputFormatter for JsonFormatter<T> {
-    fn write_run_start(&mut self, test_count: usize) -> io::Result<()> {
+    fn write_run_start(&mut self, test_count: usize, shuffle_seed: Option<u64>) -> io::Result<()> {
+        let shuffle_seed_json = if let Some(shuffle_seed) = shuffle_seed {
+            format!(r#", "shuffle_seed": {}"#, shuffle_seed)
+        } else {
+            String::new()
+        };
+        let shard_json = if let Some(Shard { index, count }) = self.shard {
+            format!(r#", "shard": {{ "index": {}, "count": {} }}"#, index, count)
+        } else {
+            String::new()
+        };
         self.writeln_message(&*format!(
-            r#"{{ "type": "suite", "event": "started", "test_count": {} }}"#,
-            test_count
+            "{{ \"type\": \"suite\", \
+             \"event\": \"started\", \
+             \"format_version\": {}, \
+             \"test_count\": {}{}{} }}",
+            FORMAT_VERSION, test_count, shuffle_seed_json, shard_json
         ))
     }
 
@@ -90,12 +125,18 @@ fn write_result(
         };
         match *result {
             TestResult::TrOk => {
-                self.write_event("test", desc.name.as_slice(), "ok", exec_time, stdout, None)
+                self.write_event("test", desc.name.as_slice(), "ok", exec_time, stdout, None, None)
             }
 
-            TestResult::TrFailed => {
-                self.write_event("test", desc.name.as_slice(), "failed", exec_time, stdout, None)
-            }
+            TestResult::TrFailed => self.write_event(
+                "test",
+                desc.name.as_slice(),
+                "failed",
+                exec_time,
+                stdout,
+                None,
+                None,
+            ),
 
             TestResult::TrTimedFail => self.write_event(
                 "test",
@@ -104,6 +145,17 @@ fn write_result(
                 exec_time,
                 stdout,
                 Some(r#""reason": "time limit exceeded""#),
+                None,
+            ),
+
+            TestResult::TrTimedOut => self.write_event(
+                "test",
+                desc.name.as_slice(),
+                "failed",
+                exec_time,
+                stdout,
+                Some(r#""reason": "timed out""#),
+                None,
             ),
 
             TestResult::TrFailedMsg(ref m) => self.write_event(
@@ -113,11 +165,20 @@ fn write_result(
                 exec_time,
                 stdout,
                 Some(&*format!(r#""message": "{}""#, EscapedString(m))),
+                None,
             ),
 
-            TestResult::TrIgnored => {
-                self.write_event("test", desc.name.as_slice(), "ignored", exec_time, stdout, None)
-            }
+            TestResult::TrIgnored => self.write_event(
+                "test",
+                desc.name.as_slice(),
+                "ignored",
+                exec_time,
+                stdout,
+                desc.ignore_message()
+                    .map(|msg| format!(r#""message": "{}""#, EscapedString(msg)))
+                    .as_deref(),
+                None,
+            ),
 
             TestResult::TrAllowedFail => self.write_event(
                 "test",
@@ -126,6 +187,17 @@ fn write_result(
                 exec_time,
                 stdout,
                 None,
+                None,
+            ),
+
+            TestResult::TrFlaky(failed) => self.write_event(
+                "test",
+                desc.name.as_slice(),
+                "flaky",
+                exec_time,
+                stdout,
+                None,
+                Some(&*format!(r#""failed_attempts": {}"#, failed)),
             ),
 
             TestResult::TrBench(ref bs) => {
@@ -138,15 +210,67 @@ fn write_result(
                     format!(r#", "mib_per_second": {}"#, bs.mb_s)
                 };
 
+                let summ = &bs.ns_iter_summ;
+                let stats = format!(
+                    "\"stats\": {{ \"samples\": {}, \
+                     \"min\": {}, \
+                     \"max\": {}, \
+                     \"mean\": {}, \
+                     \"median\": {}, \
+                     \"std_dev\": {}, \
+                     \"std_dev_pct\": {}, \
+                     \"median_abs_dev\": {}, \
+                     \"median_abs_dev_pct\": {}, \
+                     \"quartiles\": [{}, {}, {}], \
+                     \"iqr\": {} }}",
+                    bs.samples.len(),
+                    JsonNumber(summ.min),
+                    JsonNumber(summ.max),
+                    JsonNumber(summ.mean),
+                    JsonNumber(summ.median),
+                    JsonNumber(summ.std_dev),
+                    JsonNumber(summ.std_dev_pct),
+                    JsonNumber(summ.median_abs_dev),
+                    JsonNumber(summ.median_abs_dev_pct),
+                    JsonNumber(summ.quartiles.0),
+                    JsonNumber(summ.quartiles.1),
+                    JsonNumber(summ.quartiles.2),
+                    JsonNumber(summ.iqr),
+                );
+
+                let baseline = match state.baseline {
+                    Some(ref baseline) => match baseline.compare(desc.name.as_slice(), bs) {
+                        Some(comparison) => format!(
+                            ", \"baseline\": {{ \"median\": {}, \
+                             \"change_pct\": {}, \
+                             \"p_value\": {}, \
+                             \"change\": \"{}\" }}",
+                            JsonNumber(comparison.baseline_median),
+                            JsonNumber(comparison.change_pct),
+                            JsonNumber(comparison.p_value),
+                            match comparison.change {
+                                BaselineChange::None => "none",
+                                BaselineChange::Regressed => "regressed",
+                                BaselineChange::Improved => "improved",
+                            }
+                        ),
+                        None => r#", "baseline": null"#.to_owned(),
+                    },
+                    None => String::new(),
+                };
+
                 let line = format!(
                     "{{ \"type\": \"bench\", \
                      \"name\": \"{}\", \
                      \"median\": {}, \
-                     \"deviation\": {}{} }}",
+                     \"deviation\": {}{}, \
+                     \"unstable\": {{ {}{} }} }}",
                     EscapedString(desc.name.as_slice()),
                     median,
                     deviation,
-                    mbps
+                    mbps,
+                    stats,
+                    baseline
                 );
 
                 self.writeln_message(&*line)
@@ -185,12 +309,26 @@ fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
             self.write_message(&time_str)?;
         }
 
+        if state.flaky > 0 {
+            self.write_message(&format!(r#", "unstable": {{ "flaky": {} }}"#, state.flaky))?;
+        }
+
         self.writeln_message(" }")?;
 
         Ok(state.failed == 0)
     }
 }
 
+/// A formatting utility used to print floating point numbers as JSON, which
+/// has no representation for NaN and infinity.
+struct JsonNumber(f64);
+
+impl std::fmt::Display for JsonNumber {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.0.is_finite() { write!(f, "{}", self.0) } else { f.write_str("null") }
+    }
+}
+
 /// A formatting utility used to print strings with characters in need of escaping.
 /// Base code taken form `libserialize::json::escape_str`
 struct EscapedString<S: AsRef<str>>(S);
@@ -0,0 +1,277 @@
+//! JUnit XML output.
+//!
+//! The report is written in one go once the whole suite has finished, because
+//! the `<testsuite>` element carries the totals as attributes.
+//!
+//! Tests are mapped to `<testcase>` elements as follows:
+//! - passing tests (and allowed failures) are empty test cases, unless
+//!   `--show-output` was passed;
+//! - failing tests contain a `<failure>` element;
+//! - captured output that would be shown by the other formatters is stored in
+//!   `<system-out>`;
+//! - flaky tests contain one `<flakyFailure>` element per failed attempt;
+//! - ignored tests contain a `<skipped/>` element;
+//! - the seed of a shuffled run is stored as the `shuffle_seed` property of
+//!   the `<testsuite>`;
+//! - benchmarks use the `benchmark::` class name prefix, their `time` is the
+//!   median time of a single iteration in seconds, and `<system-out>` holds the
+//!   usual `ns/iter` summary line.
+
+use std::{fmt, io, io::prelude::Write, time::Duration};
+
+use super::OutputFormatter;
+use crate::{
+    bench::fmt_bench_samples,
+    console::{ConsoleTestState, OutputLocation},
+    test_result::TestResult,
+    time,
+    types::{TestDesc, TestType},
+};
+
+pub(crate) struct JunitFormatter<T> {
+    out: OutputLocation<T>,
+    results: Vec<(TestDesc, TestResult, Duration, Vec<u8>)>,
+    shuffle_seed: Option<u64>,
+}
+
+impl<T: Write> JunitFormatter<T> {
+    pub fn new(out: OutputLocation<T>) -> Self {
+        Self { out, results: Vec::new(), shuffle_seed: None }
+    }
+
+    #[cfg(test)]
+    pub fn output_location(&self) -> &OutputLocation<T> {
+        &self.out
+    }
+
+    fn writeln_message(&mut self, s: &str) -> io::Result<()> {
+        self.out.write_all(s.as_ref())?;
+        self.out.write_all(b"\n")
+    }
+
+    fn write_testcase(
+        &mut self,
+        desc: &TestDesc,
+        time: f64,
+        body: Option<&str>,
+        output: &[u8],
+    ) -> io::Result<()> {
+        let (class_name, test_name) = parse_class_name(desc);
+        let start = format!(
+            r#"<testcase classname="{}" name="{}" time="{}""#,
+            XmlEscaped(&class_name),
+            XmlEscaped(&test_name),
+            time
+        );
+        if body.is_none() && output.is_empty() {
+            return self.writeln_message(&format!("{}/>", start));
+        }
+
+        self.writeln_message(&format!("{}>", start))?;
+        if let Some(body) = body {
+            self.writeln_message(body)?;
+        }
+        if !output.is_empty() {
+            let output = String::from_utf8_lossy(output);
+            self.writeln_message(&format!("<system-out>{}</system-out>", XmlEscaped(&output)))?;
+        }
+        self.writeln_message("</testcase>")
+    }
+}
+
+impl<T: Write> OutputFormatter for JunitFormatter<T> {
+    fn write_run_start(&mut self, _test_count: usize, shuffle_seed: Option<u64>) -> io::Result<()> {
+        self.shuffle_seed = shuffle_seed;
+        self.writeln_message(r#"<?xml version="1.0" encoding="UTF-8"?>"#)
+    }
+
+    fn write_test_start(&mut self, _desc: &TestDesc) -> io::Result<()> {
+        // Nothing is written until the run has finished.
+        Ok(())
+    }
+
+    fn write_timeout(&mut self, _desc: &TestDesc) -> io::Result<()> {
+        // The report only contains results, so there is nothing to warn about here.
+        Ok(())
+    }
+
+    fn write_result(
+        &mut self,
+        desc: &TestDesc,
+        result: &TestResult,
+        exec_time: Option<&time::TestExecTime>,
+        stdout: &[u8],
+        state: &ConsoleTestState,
+    ) -> io::Result<()> {
+        let display_stdout = state.options.display_output || *result != TestResult::TrOk;
+        let stdout = if display_stdout { stdout.to_vec() } else { Vec::new() };
+        let duration = exec_time.map(|t| t.0).unwrap_or_default();
+        self.results.push((desc.clone(), result.clone(), duration, stdout));
+        Ok(())
+    }
+
+    fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
+        self.writeln_message("<testsuites>")?;
+
+        let suite_time = state.exec_time.as_ref().map(|t| t.0).unwrap_or_default();
+        self.writeln_message(&format!(
+            "<testsuite name=\"test\" package=\"test\" id=\"0\" \
+             errors=\"0\" \
+             failures=\"{}\" \
+             tests=\"{}\" \
+             skipped=\"{}\" \
+             time=\"{}\">",
+            state.failed,
+            state.total,
+            state.ignored,
+            suite_time.as_secs_f64()
+        ))?;
+
+        if let Some(shuffle_seed) = self.shuffle_seed {
+            self.writeln_message("<properties>")?;
+            self.writeln_message(&format!(
+                r#"<property name="shuffle_seed" value="{}"/>"#,
+                shuffle_seed
+            ))?;
+            self.writeln_message("</properties>")?;
+        }
+
+        for (desc, result, duration, stdout) in std::mem::take(&mut self.results) {
+            let time = duration.as_secs_f64();
+            match result {
+                TestResult::TrOk | TestResult::TrAllowedFail => {
+                    self.write_testcase(&desc, time, None, &stdout)?
+                }
+
+                TestResult::TrFailed => {
+                    self.write_testcase(&desc, time, Some(r#"<failure type="assert"/>"#), &stdout)?
+                }
+
+                TestResult::TrFailedMsg(ref m) => self.write_testcase(
+                    &desc,
+                    time,
+                    Some(&format!(r#"<failure type="assert" message="{}"/>"#, XmlEscaped(m))),
+                    &stdout,
+                )?,
+
+                TestResult::TrTimedFail => self.write_testcase(
+                    &desc,
+                    time,
+                    Some(r#"<failure type="timeout" message="time limit exceeded"/>"#),
+                    &stdout,
+                )?,
+
+                TestResult::TrTimedOut => self.write_testcase(
+                    &desc,
+                    time,
+                    Some(r#"<failure type="timeout" message="timed out"/>"#),
+                    &stdout,
+                )?,
+
+                // Rerun failures are recorded the way Maven Surefire does it.
+                TestResult::TrFlaky(failed) => self.write_testcase(
+                    &desc,
+                    time,
+                    Some(&r#"<flakyFailure type="assert"/>"#.repeat(failed)),
+                    &stdout,
+                )?,
+
+                TestResult::TrIgnored => {
+                    self.write_testcase(&desc, time, Some("<skipped/>"), &[])?
+                }
+
+                TestResult::TrBench(ref bs) => {
+                    let (class_name, test_name) = parse_class_name(&desc);
+                    let time = bs.ns_iter_summ.median / 1_000_000_000.0;
+                    self.writeln_message(&format!(
+                        r#"<testcase classname="benchmark::{}" name="{}" time="{}">"#,
+                        XmlEscaped(&class_name),
+                        XmlEscaped(&test_name),
+                        time
+                    ))?;
+                    self.writeln_message(&format!(
+                        "<system-out>{}</system-out>",
+                        XmlEscaped(fmt_bench_samples(bs).trim())
+                    ))?;
+                    self.writeln_message("</testcase>")?;
+                }
+            }
+        }
+
+        self.writeln_message("</testsuite>")?;
+        self.writeln_message("</testsuites>")?;
+
+        Ok(state.failed == 0)
+    }
+}
+
+/// Splits the test name into the JUnit class name and test case name.
+fn parse_class_name(desc: &TestDesc) -> (String, String) {
+    match desc.test_type {
+        TestType::UnitTest => parse_class_name_unit(desc),
+        TestType::DocTest => parse_class_name_doc(desc),
+        TestType::IntegrationTest => parse_class_name_integration(desc),
+        TestType::Unknown => (String::from("unknown"), String::from(desc.name.as_slice())),
+    }
+}
+
+fn parse_class_name_unit(desc: &TestDesc) -> (String, String) {
+    // The module path is the class name, and the function name is the test name.
+    match desc.name.as_slice().rsplit_once("::") {
+        Some((module_path, test_name)) => (String::from(module_path), String::from(test_name)),
+        None => (String::from("crate"), String::from(desc.name.as_slice())),
+    }
+}
+
+fn parse_class_name_doc(desc: &TestDesc) -> (String, String) {
+    // Doc test names look like `src/lib.rs - some::item (line 12)`, so the file
+    // becomes the class name.
+    match desc.name.as_slice().split_once(" - ") {
+        Some((file, test_name)) => (String::from(file), String::from(test_name)),
+        None => (String::from("doctest"), String::from(desc.name.as_slice())),
+    }
+}
+
+fn parse_class_name_integration(desc: &TestDesc) -> (String, String) {
+    (String::from("integration"), String::from(desc.name.as_slice()))
+}
+
+/// A formatting utility that escapes text for use in XML attributes and
+/// character data.
+///
+/// Control characters that XML 1.0 cannot represent at all are replaced with
+/// U+FFFD REPLACEMENT CHARACTER.
+struct XmlEscaped<S: AsRef<str>>(S);
+
+impl<S: AsRef<str>> fmt::Display for XmlEscaped<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = self.0.as_ref();
+        let mut start = 0;
+
+        for (i, c) in s.char_indices() {
+            let escaped = match c {
+                '<' => "&lt;",
+                '>' => "&gt;",
+                '&' => "&amp;",
+                '"' => "&quot;",
+                '\t' | '\n' | '\r' => continue,
+                '\x00'..='\x1f' => "\u{fffd}",
+                _ => continue,
+            };
+
+            if start < i {
+                f.write_str(&s[start..i])?;
+            }
+
+            f.write_str(escaped)?;
+
+            start = i + c.len_utf8();
+        }
+
+        if start != s.len() {
+            f.write_str(&s[start..])?;
+        }
+
+        Ok(())
+    }
+}
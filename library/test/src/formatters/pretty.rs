@@ -2,7 +2,7 @@
 
 use super::OutputFormatter;
 use crate::{
-    bench::fmt_bench_samples,
+    bench::{fmt_baseline_comparison, fmt_bench_samples, BaselineChange, BaselineComparison},
     console::{ConsoleTestState, OutputLocation},
     test_result::TestResult,
     time,
@@ -56,10 +56,35 @@ pub fn write_time_failed(&mut self) -> io::Result<()> {
         self.write_short_result("FAILED (time limit exceeded)", term::color::RED)
     }
 
+    pub fn write_timed_out(&mut self) -> io::Result<()> {
+        self.write_short_result("FAILED (timed out)", term::color::RED)
+    }
+
+    pub fn write_flaky(&mut self) -> io::Result<()> {
+        self.write_short_result("FLAKY", term::color::YELLOW)
+    }
+
     pub fn write_bench(&mut self) -> io::Result<()> {
         self.write_pretty("bench", term::color::CYAN)
     }
 
+    pub fn write_baseline_comparison(
+        &mut self,
+        comparison: Option<BaselineComparison>,
+    ) -> io::Result<()> {
+        let comparison = match comparison {
+            Some(comparison) => comparison,
+            None => return self.write_plain(" [not in baseline]"),
+        };
+        self.write_plain(" ")?;
+        let s = fmt_baseline_comparison(&comparison);
+        match comparison.change {
+            BaselineChange::None => self.write_plain(&s),
+            BaselineChange::Regressed => self.write_pretty(&s, term::color::RED),
+            BaselineChange::Improved => self.write_pretty(&s, term::color::GREEN),
+        }
+    }
+
     pub fn write_short_result(
         &mut self,
         result: &str,
@@ -167,6 +192,10 @@ pub fn write_time_failures(&mut self, state: &ConsoleTestState) -> io::Result<()
         self.write_results(&state.time_failures, "failures (time limit exceeded)")
     }
 
+    pub fn write_flaky_tests(&mut self, state: &ConsoleTestState) -> io::Result<()> {
+        self.write_results(&state.flaky_tests, "flaky tests")
+    }
+
     fn write_test_name(&mut self, desc: &TestDesc) -> io::Result<()> {
         let name = desc.padded_name(self.max_name_len, desc.name.padding());
         self.write_plain(&format!("test {} ... ", name))?;
@@ -176,9 +205,14 @@ fn write_test_name(&mut self, desc: &TestDesc) -> io::Result<()> {
 }
 
 impl<T: Write> OutputFormatter for PrettyFormatter<T> {
-    fn write_run_start(&mut self, test_count: usize) -> io::Result<()> {
+    fn write_run_start(&mut self, test_count: usize, shuffle_seed: Option<u64>) -> io::Result<()> {
         let noun = if test_count != 1 { "tests" } else { "test" };
-        self.write_plain(&format!("\nrunning {} {}\n", test_count, noun))
+        let shuffle_seed_msg = if let Some(shuffle_seed) = shuffle_seed {
+            format!(" (shuffle seed: {})", shuffle_seed)
+        } else {
+            String::new()
+        };
+        self.write_plain(&format!("\nrunning {} {}{}\n", test_count, noun, shuffle_seed_msg))
     }
 
     fn write_test_start(&mut self, desc: &TestDesc) -> io::Result<()> {
@@ -199,7 +233,7 @@ fn write_result(
         result: &TestResult,
         exec_time: Option<&time::TestExecTime>,
         _: &[u8],
-        _: &ConsoleTestState,
+        state: &ConsoleTestState,
     ) -> io::Result<()> {
         if self.is_multithreaded {
             self.write_test_name(desc)?;
@@ -208,16 +242,34 @@ fn write_result(
         match *result {
             TestResult::TrOk => self.write_ok()?,
             TestResult::TrFailed | TestResult::TrFailedMsg(_) => self.write_failed()?,
-            TestResult::TrIgnored => self.write_ignored()?,
+            TestResult::TrIgnored => {
+                self.write_ignored()?;
+                if let Some(message) = desc.ignore_message() {
+                    self.write_plain(&format!(", {}", message))?;
+                }
+            }
             TestResult::TrAllowedFail => self.write_allowed_fail()?,
             TestResult::TrBench(ref bs) => {
                 self.write_bench()?;
                 self.write_plain(&format!(": {}", fmt_bench_samples(bs)))?;
+                if let Some(ref baseline) = state.baseline {
+                    self.write_baseline_comparison(baseline.compare(desc.name.as_slice(), bs))?;
+                }
             }
             TestResult::TrTimedFail => self.write_time_failed()?,
+            TestResult::TrTimedOut => self.write_timed_out()?,
+            TestResult::TrFlaky(_) => self.write_flaky()?,
         }
 
         self.write_time(desc, exec_time)?;
+
+        // The test was run because of `--ignored` or `--include-ignored`.
+        if *result != TestResult::TrIgnored {
+            if let Some(message) = desc.ignore_message() {
+                self.write_plain(&format!(" (ignored by default: {})", message))?;
+            }
+        }
+
         self.write_plain("\n")
     }
 
@@ -233,6 +285,9 @@ fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
         if state.options.display_output {
             self.write_successes(state)?;
         }
+        if !state.flaky_tests.is_empty() {
+            self.write_flaky_tests(state)?;
+        }
         let success = state.failed == 0;
         if !success {
             if !state.failures.is_empty() {
@@ -253,22 +308,23 @@ fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
             self.write_pretty("FAILED", term::color::RED)?;
         }
 
-        let s = if state.allowed_fail > 0 {
+        let mut s = if state.allowed_fail > 0 {
             format!(
-                ". {} passed; {} failed ({} allowed); {} ignored; {} measured; {} filtered out",
+                ". {} passed; {} failed ({} allowed)",
                 state.passed,
                 state.failed + state.allowed_fail,
-                state.allowed_fail,
-                state.ignored,
-                state.measured,
-                state.filtered_out
+                state.allowed_fail
             )
         } else {
-            format!(
-                ". {} passed; {} failed; {} ignored; {} measured; {} filtered out",
-                state.passed, state.failed, state.ignored, state.measured, state.filtered_out
-            )
+            format!(". {} passed; {} failed", state.passed, state.failed)
         };
+        if state.flaky > 0 {
+            s.push_str(&format!("; {} flaky", state.flaky));
+        }
+        s.push_str(&format!(
+            "; {} ignored; {} measured; {} filtered out",
+            state.ignored, state.measured, state.filtered_out
+        ));
 
         self.write_plain(&s)?;
 
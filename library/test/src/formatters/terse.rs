@@ -2,7 +2,7 @@
 
 use super::OutputFormatter;
 use crate::{
-    bench::fmt_bench_samples,
+    bench::{fmt_baseline_comparison, fmt_bench_samples},
     console::{ConsoleTestState, OutputLocation},
     test_result::TestResult,
     time,
@@ -57,6 +57,10 @@ pub fn write_allowed_fail(&mut self) -> io::Result<()> {
         self.write_short_result("a", term::color::YELLOW)
     }
 
+    pub fn write_flaky(&mut self) -> io::Result<()> {
+        self.write_short_result("?", term::color::YELLOW)
+    }
+
     pub fn write_bench(&mut self) -> io::Result<()> {
         self.write_pretty("bench", term::color::CYAN)
     }
@@ -156,6 +160,17 @@ pub fn write_failures(&mut self, state: &ConsoleTestState) -> io::Result<()> {
         Ok(())
     }
 
+    pub fn write_flaky_tests(&mut self, state: &ConsoleTestState) -> io::Result<()> {
+        self.write_plain("\nflaky tests:\n")?;
+        let mut flaky_tests: Vec<_> =
+            state.flaky_tests.iter().map(|(f, _)| f.name.to_string()).collect();
+        flaky_tests.sort();
+        for name in &flaky_tests {
+            self.write_plain(&format!("    {}\n", name))?;
+        }
+        Ok(())
+    }
+
     fn write_test_name(&mut self, desc: &TestDesc) -> io::Result<()> {
         let name = desc.padded_name(self.max_name_len, desc.name.padding());
         self.write_plain(&format!("test {} ... ", name))?;
@@ -165,10 +180,15 @@ fn write_test_name(&mut self, desc: &TestDesc) -> io::Result<()> {
 }
 
 impl<T: Write> OutputFormatter for TerseFormatter<T> {
-    fn write_run_start(&mut self, test_count: usize) -> io::Result<()> {
+    fn write_run_start(&mut self, test_count: usize, shuffle_seed: Option<u64>) -> io::Result<()> {
         self.total_test_count = test_count;
         let noun = if test_count != 1 { "tests" } else { "test" };
-        self.write_plain(&format!("\nrunning {} {}\n", test_count, noun))
+        let shuffle_seed_msg = if let Some(shuffle_seed) = shuffle_seed {
+            format!(" (shuffle seed: {})", shuffle_seed)
+        } else {
+            String::new()
+        };
+        self.write_plain(&format!("\nrunning {} {}{}\n", test_count, noun, shuffle_seed_msg))
     }
 
     fn write_test_start(&mut self, desc: &TestDesc) -> io::Result<()> {
@@ -189,21 +209,31 @@ fn write_result(
         result: &TestResult,
         _: Option<&time::TestExecTime>,
         _: &[u8],
-        _: &ConsoleTestState,
+        state: &ConsoleTestState,
     ) -> io::Result<()> {
         match *result {
             TestResult::TrOk => self.write_ok(),
-            TestResult::TrFailed | TestResult::TrFailedMsg(_) | TestResult::TrTimedFail => {
-                self.write_failed()
-            }
+            TestResult::TrFailed
+            | TestResult::TrFailedMsg(_)
+            | TestResult::TrTimedFail
+            | TestResult::TrTimedOut => self.write_failed(),
             TestResult::TrIgnored => self.write_ignored(),
             TestResult::TrAllowedFail => self.write_allowed_fail(),
+            TestResult::TrFlaky(_) => self.write_flaky(),
             TestResult::TrBench(ref bs) => {
                 if self.is_multithreaded {
                     self.write_test_name(desc)?;
                 }
                 self.write_bench()?;
-                self.write_plain(&format!(": {}\n", fmt_bench_samples(bs)))
+                self.write_plain(&format!(": {}", fmt_bench_samples(bs)))?;
+                if let Some(ref baseline) = state.baseline {
+                    let comparison = match baseline.compare(desc.name.as_slice(), bs) {
+                        Some(comparison) => fmt_baseline_comparison(&comparison),
+                        None => "[not in baseline]".to_owned(),
+                    };
+                    self.write_plain(&format!(" {}", comparison))?;
+                }
+                self.write_plain("\n")
             }
         }
     }
@@ -220,6 +250,9 @@ fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
         if state.options.display_output {
             self.write_outputs(state)?;
         }
+        if !state.flaky_tests.is_empty() {
+            self.write_flaky_tests(state)?;
+        }
         let success = state.failed == 0;
         if !success {
             self.write_failures(state)?;
@@ -234,22 +267,23 @@ fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
             self.write_pretty("FAILED", term::color::RED)?;
         }
 
-        let s = if state.allowed_fail > 0 {
+        let mut s = if state.allowed_fail > 0 {
             format!(
-                ". {} passed; {} failed ({} allowed); {} ignored; {} measured; {} filtered out",
+                ". {} passed; {} failed ({} allowed)",
                 state.passed,
                 state.failed + state.allowed_fail,
-                state.allowed_fail,
-                state.ignored,
-                state.measured,
-                state.filtered_out
+                state.allowed_fail
             )
         } else {
-            format!(
-                ". {} passed; {} failed; {} ignored; {} measured; {} filtered out",
-                state.passed, state.failed, state.ignored, state.measured, state.filtered_out
-            )
+            format!(". {} passed; {} failed", state.passed, state.failed)
         };
+        if state.flaky > 0 {
+            s.push_str(&format!("; {} flaky", state.flaky));
+        }
+        s.push_str(&format!(
+            "; {} ignored; {} measured; {} filtered out",
+            state.ignored, state.measured, state.filtered_out
+        ));
 
         self.write_plain(&s)?;
 
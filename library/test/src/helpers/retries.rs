@@ -0,0 +1,74 @@
+//! Helper module to run failed tests again, as requested by `--retries`.
+
+use std::collections::HashMap;
+use std::mem;
+
+use crate::event::CompletedTest;
+use crate::test_result::TestResult;
+use crate::types::{TestDesc, TestDescAndFn, TestFn, TestId};
+
+/// Keeps track of the tests that can still be run again if they fail.
+pub struct Retries {
+    max_retries: usize,
+    /// Dynamic tests can only be run once, so only static tests are retried.
+    tests: HashMap<TestId, (TestDesc, fn())>,
+    /// The number of failed attempts and the output of the last one.
+    failed_attempts: HashMap<TestId, (usize, Vec<u8>)>,
+}
+
+impl Retries {
+    pub fn new(max_retries: usize, tests: &[(TestId, TestDescAndFn)]) -> Self {
+        let tests = if max_retries > 0 {
+            tests
+                .iter()
+                .filter_map(|(id, test)| match test.testfn {
+                    TestFn::StaticTestFn(f) => Some((*id, (test.desc.clone(), f))),
+                    _ => None,
+                })
+                .collect()
+        } else {
+            HashMap::new()
+        };
+        Retries { max_retries, tests, failed_attempts: HashMap::new() }
+    }
+
+    /// Returns whether the test has already failed before.
+    pub fn is_retry(&self, id: TestId) -> bool {
+        self.failed_attempts.contains_key(&id)
+    }
+
+    /// Returns the test to run again if `completed_test` failed and may still
+    /// be retried. Otherwise its result is final, and a test that only passed
+    /// after failing is turned into `TrFlaky`.
+    pub fn on_completed(
+        &mut self,
+        completed_test: &mut CompletedTest,
+    ) -> Option<(TestId, TestDescAndFn)> {
+        let id = completed_test.id;
+        match completed_test.result {
+            TestResult::TrFailed
+            | TestResult::TrFailedMsg(_)
+            | TestResult::TrTimedFail
+            | TestResult::TrTimedOut => {
+                let (desc, f) = self.tests.get(&id)?;
+                let (failed, stdout) = self.failed_attempts.entry(id).or_default();
+                if *failed >= self.max_retries {
+                    return None;
+                }
+                *failed += 1;
+                *stdout = mem::take(&mut completed_test.stdout);
+                Some((id, TestDescAndFn { desc: desc.clone(), testfn: TestFn::StaticTestFn(*f) }))
+            }
+            TestResult::TrOk => {
+                if let Some((failed, stdout)) = self.failed_attempts.remove(&id) {
+                    // The output of the last failure is more useful than that
+                    // of the successful attempt.
+                    completed_test.result = TestResult::TrFlaky(failed);
+                    completed_test.stdout = stdout;
+                }
+                None
+            }
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,20 @@
+//! Helper module to split the tests of a binary into several shards.
+
+use crate::options::Shard;
+
+/// Returns whether the test with the given name belongs to `shard`.
+///
+/// Tests are assigned by a hash of their name. The hash is computed here
+/// instead of with `DefaultHasher` so that the assignment never changes
+/// between Rust releases, or when tests are added or removed.
+pub fn is_in_shard(shard: Shard, test_name: &str) -> bool {
+    fnv1a(test_name.as_bytes()) % shard.count as u64 == shard.index as u64
+}
+
+// 64-bit FNV-1a, see http://www.isthe.com/chongo/tech/comp/fnv/.
+pub(crate) fn fnv1a(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    bytes.iter().fold(OFFSET_BASIS, |hash, &byte| (hash ^ u64::from(byte)).wrapping_mul(PRIME))
+}
@@ -0,0 +1,75 @@
+//! Helper module to deterministically shuffle the order in which tests run.
+
+use super::shard::fnv1a;
+use crate::cli::TestOpts;
+use crate::types::TestName;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Returns the seed to shuffle the tests with, or `None` if they should run
+/// in their usual order.
+///
+/// If shuffling was requested without an explicit seed, a new one is derived
+/// from the current time, so that it can be printed and reused later.
+pub fn get_shuffle_seed(opts: &TestOpts) -> Option<u64> {
+    opts.shuffle_seed.or_else(|| {
+        if opts.shuffle {
+            Some(
+                SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .expect("Failed to get system time")
+                    .as_nanos() as u64,
+            )
+        } else {
+            None
+        }
+    })
+}
+
+/// Shuffles `tests` in place.
+///
+/// The resulting order only depends on the seed and on the names of the
+/// tests, so running the same tests with the same seed reproduces it. As in
+/// `shard`, nothing is hashed with `DefaultHasher`, so that this also holds
+/// between Rust releases.
+pub fn shuffle_tests<T>(shuffle_seed: u64, tests: &mut [T], name_of: impl Fn(&T) -> &TestName) {
+    // The names are separated by a byte which can't appear in a `str`.
+    let mut test_names = Vec::new();
+    for test in tests.iter() {
+        test_names.extend_from_slice(name_of(test).as_slice().as_bytes());
+        test_names.push(0xff);
+    }
+    let test_names_hash = fnv1a(&test_names);
+    let mut rng = Rng::new(shuffle_seed, test_names_hash);
+    shuffle(&mut rng, tests);
+}
+
+// Fisher-Yates shuffle.
+fn shuffle<T>(rng: &mut Rng, slice: &mut [T]) {
+    for i in 0..slice.len() {
+        let idx = i + rng.rand_range(0..(slice.len() - i) as u64) as usize;
+        slice.swap(i, idx);
+    }
+}
+
+struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    fn new(seed: u64, extra: u64) -> Self {
+        Self { state: seed ^ extra }
+    }
+
+    fn rand_range(&mut self, range: std::ops::Range<u64>) -> u64 {
+        self.rand_u64() % (range.end - range.start) + range.start
+    }
+
+    // SplitMix64, see https://prng.di.unimi.it/splitmix64.c.
+    fn rand_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
@@ -25,6 +25,7 @@
 #![feature(nll)]
 #![feature(available_concurrency)]
 #![feature(bench_black_box)]
+#![feature(custom_test_frameworks)]
 #![feature(internal_output_capture)]
 #![feature(panic_unwind)]
 #![feature(staged_api)]
@@ -35,7 +36,7 @@
 // Public reexports
 pub use self::bench::{black_box, Bencher};
 pub use self::console::run_tests_console;
-pub use self::options::{ColorConfig, Options, OutputFormat, RunIgnored, ShouldPanic};
+pub use self::options::{ColorConfig, Options, OutputFormat, RunIgnored, Shard, ShouldPanic};
 pub use self::types::TestName::*;
 pub use self::types::*;
 pub use self::ColorConfig::*;
@@ -49,8 +50,8 @@ pub mod test {
         cli::{parse_opts, TestOpts},
         filter_tests,
         helpers::metrics::{Metric, MetricMap},
-        options::{Options, RunIgnored, RunStrategy, ShouldPanic},
-        run_test, test_main, test_main_static,
+        options::{Options, RunIgnored, RunStrategy, Shard, ShouldPanic},
+        run_test, test_main, test_main_static, test_main_static_cases,
         test_result::{TestResult, TrFailed, TrFailedMsg, TrIgnored, TrOk},
         time::{TestExecTime, TestTimeOptions},
         types::{
@@ -61,13 +62,16 @@ pub mod test {
 }
 
 use std::{
+    cmp,
     collections::VecDeque,
     env, io,
-    io::prelude::Write,
+    io::prelude::{Read, Write},
+    mem,
     panic::{self, catch_unwind, AssertUnwindSafe, PanicInfo},
     process::{self, Command, Termination},
-    sync::mpsc::{channel, Sender},
+    sync::mpsc::{self, channel, Sender},
     sync::{Arc, Mutex},
+    test_case::{self, TestCase},
     thread,
     time::{Duration, Instant},
 };
@@ -90,6 +94,9 @@ pub mod test {
 use event::{CompletedTest, TestEvent};
 use helpers::concurrency::get_concurrency;
 use helpers::exit_code::get_exit_code;
+use helpers::retries::Retries;
+use helpers::shard::is_in_shard;
+use helpers::shuffle::{get_shuffle_seed, shuffle_tests};
 use options::{Concurrent, RunStrategy};
 use test_result::*;
 use time::TestExecTime;
@@ -102,6 +109,22 @@ pub mod test {
 // The default console test runner. It accepts the command line
 // arguments and a vector of test_descs.
 pub fn test_main(args: &[String], tests: Vec<TestDescAndFn>, options: Option<Options>) {
+    // If we're being run in SpawnedSecondary mode, run the test here.
+    // run_test_in_spawned_subprocess will then exit the process.
+    if let Ok(name) = env::var(SECONDARY_TEST_INVOKER_VAR) {
+        env::remove_var(SECONDARY_TEST_INVOKER_VAR);
+        let TestDescAndFn { desc, testfn } = tests
+            .into_iter()
+            .find(|test| test.desc.name.as_slice() == name)
+            .unwrap_or_else(|| panic!("couldn't find a test with the provided name '{}'", name));
+        let testfn = match testfn {
+            StaticTestFn(f) => f,
+            _ => panic!("only static tests are supported"),
+        };
+        let panic_abort = options.map_or(false, |options| options.panic_abort);
+        run_test_in_spawned_subprocess(desc, Box::new(testfn), panic_abort);
+    }
+
     let mut opts = match cli::parse_opts(args) {
         Some(Ok(o)) => o,
         Some(Err(msg)) => {
@@ -141,6 +164,33 @@ pub fn test_main_static(tests: &[&TestDescAndFn]) {
     test_main(&args, owned_tests, None)
 }
 
+/// A variant of `test_main_static` that takes the descriptors the test harness
+/// creates for `#![test_runner(test::test_main_static_cases, descriptors)]`.
+/// The name, `#[ignore]` and `#[should_panic]` attributes of each test are
+/// taken from its descriptor.
+pub fn test_main_static_cases(tests: &[&TestCase<'_, TestDescAndFn>]) {
+    let args = env::args().collect::<Vec<_>>();
+    let owned_tests: Vec<_> = tests
+        .iter()
+        .map(|case| {
+            let mut test = make_owned_test(&case.test);
+            test.desc.name = StaticTestName(case.name);
+            test.desc.ignore = case.ignore;
+            #[cfg(not(bootstrap))]
+            {
+                test.desc.ignore_message = case.ignore_message;
+            }
+            test.desc.should_panic = match case.should_panic {
+                test_case::ShouldPanic::No => ShouldPanic::No,
+                test_case::ShouldPanic::Yes => ShouldPanic::Yes,
+                test_case::ShouldPanic::YesWithMessage(msg) => ShouldPanic::YesWithMessage(msg),
+            };
+            test
+        })
+        .collect();
+    test_main(&args, owned_tests, None)
+}
+
 /// A variant optimized for invocation with a static test vector.
 /// This will panic (intentionally) when fed any dynamic tests.
 ///
@@ -150,24 +200,6 @@ pub fn test_main_static(tests: &[&TestDescAndFn]) {
 /// This is the entry point for the main function generated by `rustc --test`
 /// when panic=abort.
 pub fn test_main_static_abort(tests: &[&TestDescAndFn]) {
-    // If we're being run in SpawnedSecondary mode, run the test here. run_test
-    // will then exit the process.
-    if let Ok(name) = env::var(SECONDARY_TEST_INVOKER_VAR) {
-        env::remove_var(SECONDARY_TEST_INVOKER_VAR);
-        let test = tests
-            .iter()
-            .filter(|test| test.desc.name.as_slice() == name)
-            .map(make_owned_test)
-            .next()
-            .unwrap_or_else(|| panic!("couldn't find a test with the provided name '{}'", name));
-        let TestDescAndFn { desc, testfn } = test;
-        let testfn = match testfn {
-            StaticTestFn(f) => f,
-            _ => panic!("only static tests are supported"),
-        };
-        run_test_in_spawned_subprocess(desc, Box::new(testfn));
-    }
-
     let args = env::args().collect::<Vec<_>>();
     let owned_tests: Vec<_> = tests.iter().map(make_owned_test).collect();
     test_main(&args, owned_tests, Some(Options::new().panic_abort(true)))
@@ -226,6 +258,8 @@ struct TimeoutEntry {
 
     let tests_len = tests.len();
 
+    let shuffle_seed = get_shuffle_seed(opts);
+
     let mut filtered_tests = filter_tests(opts, tests);
     if !opts.bench_benchmarks {
         filtered_tests = convert_benchmarks_to_tests(filtered_tests);
@@ -246,15 +280,21 @@ struct TimeoutEntry {
 
     let filtered_descs = filtered_tests.iter().map(|t| t.desc.clone()).collect();
 
-    let event = TestEvent::TeFiltered(filtered_descs);
+    let event = TestEvent::TeFiltered(filtered_descs, shuffle_seed);
     notify_about_test_event(event)?;
 
-    let (filtered_tests, filtered_benchs): (Vec<_>, _) = filtered_tests
+    let (mut filtered_tests, filtered_benchs): (Vec<_>, _) = filtered_tests
         .into_iter()
         .enumerate()
         .map(|(i, e)| (TestId(i), e))
         .partition(|(_, e)| matches!(e.testfn, StaticTestFn(_) | DynTestFn(_)));
 
+    if let Some(shuffle_seed) = shuffle_seed {
+        shuffle_tests(shuffle_seed, &mut filtered_tests, |(_, test)| &test.desc.name);
+    }
+
+    let mut retries = Retries::new(opts.retries, &filtered_tests);
+
     let concurrency = opts.test_threads.unwrap_or_else(get_concurrency);
 
     let mut remaining = filtered_tests;
@@ -262,7 +302,9 @@ struct TimeoutEntry {
     let mut pending = 0;
 
     let (tx, rx) = channel::<CompletedTest>();
-    let run_strategy = if opts.options.panic_abort && !opts.force_run_in_process {
+    let run_strategy = if (opts.options.panic_abort && !opts.force_run_in_process)
+        || opts.test_timeout.is_some()
+    {
         RunStrategy::SpawnPrimary
     } else {
         RunStrategy::InProcess
@@ -299,12 +341,19 @@ fn calc_timeout(timeout_queue: &VecDeque<TimeoutEntry>) -> Option<Duration> {
     if concurrency == 1 {
         while !remaining.is_empty() {
             let (id, test) = remaining.pop().unwrap();
-            let event = TestEvent::TeWait(test.desc.clone());
-            notify_about_test_event(event)?;
+            if !retries.is_retry(id) {
+                let event = TestEvent::TeWait(test.desc.clone());
+                notify_about_test_event(event)?;
+            }
             let join_handle =
                 run_test(opts, !opts.run_tests, id, test, run_strategy, tx.clone(), Concurrent::No);
             assert!(join_handle.is_none());
-            let completed_test = rx.recv().unwrap();
+            let mut completed_test = rx.recv().unwrap();
+
+            if let Some(retry) = retries.on_completed(&mut completed_test) {
+                remaining.push(retry);
+                continue;
+            }
 
             let event = TestEvent::TeResult(completed_test);
             notify_about_test_event(event)?;
@@ -316,8 +365,10 @@ fn calc_timeout(timeout_queue: &VecDeque<TimeoutEntry>) -> Option<Duration> {
                 let timeout = time::get_default_test_timeout();
                 let desc = test.desc.clone();
 
-                let event = TestEvent::TeWait(desc.clone());
-                notify_about_test_event(event)?; //here no pad
+                if !retries.is_retry(id) {
+                    let event = TestEvent::TeWait(desc.clone());
+                    notify_about_test_event(event)?; //here no pad
+                }
                 let join_handle = run_test(
                     opts,
                     !opts.run_tests,
@@ -358,6 +409,9 @@ fn calc_timeout(timeout_queue: &VecDeque<TimeoutEntry>) -> Option<Duration> {
 
             let mut completed_test = res.unwrap();
             let running_test = running_tests.remove(&completed_test.id).unwrap();
+            // A retry runs under the same id, so it mustn't be reported as
+            // running for too long because of the time the test took before.
+            timeout_queue.retain(|entry| entry.id != completed_test.id);
             if let Some(join_handle) = running_test.join_handle {
                 if let Err(_) = join_handle.join() {
                     if let TrOk = completed_test.result {
@@ -366,10 +420,15 @@ fn calc_timeout(timeout_queue: &VecDeque<TimeoutEntry>) -> Option<Duration> {
                     }
                 }
             }
+            pending -= 1;
+
+            if let Some(retry) = retries.on_completed(&mut completed_test) {
+                remaining.push(retry);
+                continue;
+            }
 
             let event = TestEvent::TeResult(completed_test);
             notify_about_test_event(event)?;
-            pending -= 1;
         }
     }
 
@@ -407,6 +466,11 @@ pub fn filter_tests(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> Vec<TestDescA
     // Skip tests that match any of the skip filters
     filtered.retain(|test| !opts.skip.iter().any(|sf| matches_filter(test, sf)));
 
+    // Only keep the tests of the requested shard
+    if let Some(shard) = opts.shard {
+        filtered.retain(|test| is_in_shard(shard, test.desc.name.as_slice()));
+    }
+
     // Excludes #[should_panic] tests
     if opts.exclude_should_panic {
         filtered.retain(|test| test.desc.should_panic == ShouldPanic::No);
@@ -476,6 +540,7 @@ struct TestRunOpts {
         pub nocapture: bool,
         pub concurrency: Concurrent,
         pub time: Option<time::TestTimeOptions>,
+        pub timeout: Option<Duration>,
     }
 
     fn run_test_inner(
@@ -505,6 +570,7 @@ fn run_test_inner(
                 opts.time.is_some(),
                 monitor_ch,
                 opts.time,
+                opts.timeout,
             ),
         };
 
@@ -532,8 +598,13 @@ fn run_test_inner(
         }
     }
 
-    let test_run_opts =
-        TestRunOpts { strategy, nocapture: opts.nocapture, concurrency, time: opts.time_options };
+    let mut test_run_opts = TestRunOpts {
+        strategy,
+        nocapture: opts.nocapture,
+        concurrency,
+        time: opts.time_options,
+        timeout: opts.test_timeout,
+    };
 
     match testfn {
         DynBenchFn(bencher) => {
@@ -551,7 +622,12 @@ fn run_test_inner(
         DynTestFn(f) => {
             match strategy {
                 RunStrategy::InProcess => (),
-                _ => panic!("Cannot run dynamic test fn out-of-process"),
+                _ if opts.options.panic_abort => {
+                    panic!("Cannot run dynamic test fn out-of-process")
+                }
+                // A spawned subprocess has no way to find a dynamic test, so
+                // it runs in-process, without a hard `--test-timeout`.
+                _ => test_run_opts.strategy = RunStrategy::InProcess,
             };
             run_test_inner(
                 id,
@@ -621,6 +697,7 @@ fn spawn_test_subprocess(
     report_time: bool,
     monitor_ch: Sender<CompletedTest>,
     time_opts: Option<time::TestTimeOptions>,
+    timeout: Option<Duration>,
 ) {
     let (result, test_output, exec_time) = (|| {
         let args = env::args().collect::<Vec<_>>();
@@ -634,7 +711,11 @@ fn spawn_test_subprocess(
         }
 
         let start = report_time.then(Instant::now);
-        let output = match command.output() {
+        let output = match timeout {
+            Some(timeout) => output_with_timeout(&mut command, nocapture, timeout),
+            None => command.output().map(|output| (output, false)),
+        };
+        let (output, timed_out) = match output {
             Ok(out) => out,
             Err(e) => {
                 let err = format!("Failed to spawn {} as child for test: {:?}", args[0], e);
@@ -651,6 +732,10 @@ fn spawn_test_subprocess(
         formatters::write_stderr_delimiter(&mut test_output, &desc.name);
         test_output.extend_from_slice(&stderr);
 
+        if timed_out {
+            return (TrTimedOut, test_output, exec_time);
+        }
+
         let result = match (|| -> Result<TestResult, String> {
             let exit_code = get_exit_code(status)?;
             Ok(get_result_from_exit_code(&desc, exit_code, &time_opts, &exec_time))
@@ -669,29 +754,140 @@ fn spawn_test_subprocess(
     monitor_ch.send(message).unwrap();
 }
 
-fn run_test_in_spawned_subprocess(desc: TestDesc, testfn: Box<dyn FnOnce() + Send>) -> ! {
-    let builtin_panic_hook = panic::take_hook();
-    let record_result = Arc::new(move |panic_info: Option<&'_ PanicInfo<'_>>| {
-        let test_result = match panic_info {
-            Some(info) => calc_result(&desc, Err(info.payload()), &None, &None),
-            None => calc_result(&desc, Ok(()), &None, &None),
-        };
+/// How long to keep reading the output of a test after it was killed because
+/// of its timeout. Processes it spawned may still hold its pipes, so whatever
+/// they write after this is dropped.
+const KILLED_TEST_OUTPUT_GRACE: Duration = Duration::from_secs(1);
+
+/// Like `Command::output`, but kills the child process if it is still running
+/// after `timeout`. Also returns whether that happened.
+fn output_with_timeout(
+    command: &mut Command,
+    nocapture: bool,
+    timeout: Duration,
+) -> io::Result<(process::Output, bool)> {
+    if !nocapture {
+        command.stdout(process::Stdio::piped());
+        command.stderr(process::Stdio::piped());
+    }
+    command.stdin(process::Stdio::null());
+
+    let deadline = Instant::now() + timeout;
+    let mut child = command.spawn()?;
+    let stdout = PipeReader::spawn(child.stdout.take());
+    let stderr = PipeReader::spawn(child.stderr.take());
+
+    let mut timed_out = false;
+    let status = loop {
+        // Take the time before checking the child: if it is still running
+        // then, it was still running at the deadline.
+        let now = Instant::now();
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if now >= deadline {
+            timed_out = true;
+            // This only fails if the child exited since `try_wait`.
+            let _ = child.kill();
+            break child.wait()?;
+        }
+        thread::sleep(cmp::min(deadline - now, Duration::from_millis(10)));
+    };
+
+    let output_deadline = timed_out.then(|| Instant::now() + KILLED_TEST_OUTPUT_GRACE);
+    let stdout = stdout.finish(output_deadline);
+    let stderr = stderr.finish(output_deadline);
+    Ok((process::Output { status, stdout, stderr }, timed_out))
+}
+
+/// Reads a pipe of a child process on a separate thread, so that the child
+/// can't block on a full pipe while we are waiting for it to exit.
+struct PipeReader {
+    output: Arc<Mutex<Vec<u8>>>,
+    /// Disconnected when the reading thread is done.
+    done: mpsc::Receiver<()>,
+}
+
+impl PipeReader {
+    fn spawn<R: Read + Send + 'static>(pipe: Option<R>) -> PipeReader {
+        let output = Arc::new(Mutex::new(Vec::new()));
+        let (done_tx, done) = mpsc::channel::<()>();
+        if let Some(mut pipe) = pipe {
+            let output = output.clone();
+            thread::spawn(move || {
+                // Dropped, and so disconnected, when the thread is done.
+                let _done_tx = done_tx;
+                let mut buf = [0; 4096];
+                loop {
+                    match pipe.read(&mut buf) {
+                        Ok(0) => break,
+                        Ok(n) => output.lock().unwrap().extend_from_slice(&buf[..n]),
+                        Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                        Err(_) => break,
+                    }
+                }
+            });
+        }
+        PipeReader { output, done }
+    }
+
+    /// Returns the output, once the pipe is closed or `deadline` has passed.
+    fn finish(self, deadline: Option<Instant>) -> Vec<u8> {
+        // Nothing is ever sent: these return once the reading thread is done.
+        match deadline {
+            Some(deadline) => {
+                let _ = self.done.recv_timeout(deadline.saturating_duration_since(Instant::now()));
+            }
+            None => {
+                let _ = self.done.recv();
+            }
+        }
+        mem::take(&mut *self.output.lock().unwrap())
+    }
+}
 
+fn run_test_in_spawned_subprocess(
+    desc: TestDesc,
+    testfn: Box<dyn FnOnce() + Send>,
+    panic_abort: bool,
+) -> ! {
+    fn exit_with(test_result: TestResult) -> ! {
         // We don't support serializing TrFailedMsg, so just
         // print the message out to stderr.
         if let TrFailedMsg(msg) = &test_result {
             eprintln!("{}", msg);
         }
 
-        if let Some(info) = panic_info {
-            builtin_panic_hook(info);
-        }
-
         if let TrOk = test_result {
             process::exit(test_result::TR_OK);
         } else {
             process::exit(test_result::TR_FAILED);
         }
+    }
+
+    // With panic=unwind, a test may catch its own panics, or expect the ones
+    // of its helper threads, so only its result decides, like in-process.
+    // This is the case of tests spawned for `--test-timeout`.
+    if !panic_abort {
+        let result = catch_unwind(AssertUnwindSafe(testfn));
+        exit_with(match result {
+            Ok(()) => calc_result(&desc, Ok(()), &None, &None),
+            Err(e) => calc_result(&desc, Err(e.as_ref()), &None, &None),
+        });
+    }
+
+    let builtin_panic_hook = panic::take_hook();
+    let record_result = Arc::new(move |panic_info: Option<&'_ PanicInfo<'_>>| {
+        let test_result = match panic_info {
+            Some(info) => calc_result(&desc, Err(info.payload()), &None, &None),
+            None => calc_result(&desc, Ok(()), &None, &None),
+        };
+
+        if let Some(info) = panic_info {
+            builtin_panic_hook(info);
+        }
+
+        exit_with(test_result);
     });
     let record_result2 = record_result.clone();
     panic::set_hook(Box::new(move |info| record_result2(Some(&info))));
@@ -39,6 +39,17 @@ pub enum OutputFormat {
     Terse,
     /// JSON output
     Json,
+    /// JUnit output
+    Junit,
+}
+
+/// The part of the tests to run when they are split across several runs
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Shard {
+    /// Zero-based index of the shard to run
+    pub index: usize,
+    /// Number of shards the tests are split into
+    pub count: usize,
 }
 
 /// Whether ignored test should be run or not
@@ -571,6 +571,31 @@ fn test_sum_f64_between_ints_that_sum_to_0() {
     assert_eq!([1e30f64, 1.2f64, -1e30f64].sum(), 1.2);
 }
 
+#[test]
+fn test_mann_whitney_u() {
+    // Compare with `wilcox.test(a, b, exact = FALSE)` in R.
+    let lower = &[1.0, 2.0, 3.0, 4.0, 5.0];
+    let higher = &[6.0, 7.0, 8.0, 9.0, 10.0];
+    let result = mann_whitney_u(lower, higher);
+    assert_eq!(result.u, 0.0);
+    assert_approx_eq!(result.z, -2.5067182458);
+    assert_approx_eq!(result.p_value, 0.0121857804);
+
+    let result = mann_whitney_u(higher, lower);
+    assert_eq!(result.u, 25.0);
+    assert_approx_eq!(result.z, 2.5067182458);
+    assert_approx_eq!(result.p_value, 0.0121857804);
+
+    // With ties.
+    let result = mann_whitney_u(&[1.0, 2.0, 2.0, 3.0, 4.0], &[2.0, 3.0, 5.0, 6.0, 6.0]);
+    assert_eq!(result.u, 4.5);
+    assert_approx_eq!(result.z, -1.5959855298);
+    assert_approx_eq!(result.p_value, 0.1104920241);
+
+    let result = mann_whitney_u(&[3.0, 3.0], &[3.0, 3.0, 3.0]);
+    assert_eq!(result.p_value, 1.0);
+}
+
 #[bench]
 pub fn sum_three_items(b: &mut Bencher) {
     b.iter(|| {
@@ -22,6 +22,10 @@ pub enum TestResult {
     TrAllowedFail,
     TrBench(BenchSamples),
     TrTimedFail,
+    /// The test was killed because it exceeded the `--test-timeout` limit.
+    TrTimedOut,
+    /// The test passed after failing the given number of times.
+    TrFlaky(usize),
 }
 
 /// Creates a `TestResult` depending on the raw result of test execution
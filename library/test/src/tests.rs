@@ -1,10 +1,11 @@
 use super::*;
 
 use crate::{
-    bench::Bencher,
+    bench::{Baseline, BaselineChange, BenchSamples, Bencher},
     console::OutputLocation,
-    formatters::PrettyFormatter,
+    formatters::{JsonFormatter, JunitFormatter, OutputFormatter, PrettyFormatter},
     options::OutputFormat,
+    stats::Summary,
     test::{
         filter_tests,
         parse_opts,
@@ -14,6 +15,7 @@
         MetricMap,
         RunIgnored,
         RunStrategy,
+        Shard,
         ShouldPanic,
         StaticTestName,
         TestDesc,
@@ -48,6 +50,13 @@ fn new() -> TestOpts {
             test_threads: None,
             skip: vec![],
             time_options: None,
+            shuffle: false,
+            shuffle_seed: None,
+            shard: None,
+            test_timeout: None,
+            retries: 0,
+            save_baseline: None,
+            baseline: None,
             options: Options::new(),
         }
     }
@@ -59,6 +68,8 @@ fn one_ignored_one_unignored_test() -> Vec<TestDescAndFn> {
             desc: TestDesc {
                 name: StaticTestName("1"),
                 ignore: true,
+                #[cfg(not(bootstrap))]
+                ignore_message: None,
                 should_panic: ShouldPanic::No,
                 allow_fail: false,
                 test_type: TestType::Unknown,
@@ -69,6 +80,8 @@ fn one_ignored_one_unignored_test() -> Vec<TestDescAndFn> {
             desc: TestDesc {
                 name: StaticTestName("2"),
                 ignore: false,
+                #[cfg(not(bootstrap))]
+                ignore_message: None,
                 should_panic: ShouldPanic::No,
                 allow_fail: false,
                 test_type: TestType::Unknown,
@@ -87,6 +100,8 @@ fn f() {
         desc: TestDesc {
             name: StaticTestName("whatever"),
             ignore: true,
+            #[cfg(not(bootstrap))]
+            ignore_message: None,
             should_panic: ShouldPanic::No,
             allow_fail: false,
             test_type: TestType::Unknown,
@@ -106,6 +121,8 @@ fn f() {}
         desc: TestDesc {
             name: StaticTestName("whatever"),
             ignore: true,
+            #[cfg(not(bootstrap))]
+            ignore_message: None,
             should_panic: ShouldPanic::No,
             allow_fail: false,
             test_type: TestType::Unknown,
@@ -129,6 +146,8 @@ fn f() {
         desc: TestDesc {
             name: StaticTestName("whatever"),
             ignore: false,
+            #[cfg(not(bootstrap))]
+            ignore_message: None,
             should_panic: ShouldPanic::Yes,
             allow_fail: false,
             test_type: TestType::Unknown,
@@ -152,6 +171,8 @@ fn f() {
         desc: TestDesc {
             name: StaticTestName("whatever"),
             ignore: false,
+            #[cfg(not(bootstrap))]
+            ignore_message: None,
             should_panic: ShouldPanic::YesWithMessage("error message"),
             allow_fail: false,
             test_type: TestType::Unknown,
@@ -180,6 +201,8 @@ fn f() {
         desc: TestDesc {
             name: StaticTestName("whatever"),
             ignore: false,
+            #[cfg(not(bootstrap))]
+            ignore_message: None,
             should_panic: ShouldPanic::YesWithMessage(expected),
             allow_fail: false,
             test_type: TestType::Unknown,
@@ -212,6 +235,8 @@ fn f() {
         desc: TestDesc {
             name: StaticTestName("whatever"),
             ignore: false,
+            #[cfg(not(bootstrap))]
+            ignore_message: None,
             should_panic: ShouldPanic::YesWithMessage(expected),
             allow_fail: false,
             test_type: TestType::Unknown,
@@ -236,6 +261,8 @@ fn f() {}
             desc: TestDesc {
                 name: StaticTestName("whatever"),
                 ignore: false,
+                #[cfg(not(bootstrap))]
+                ignore_message: None,
                 should_panic,
                 allow_fail: false,
                 test_type: TestType::Unknown,
@@ -268,6 +295,8 @@ fn f() {}
         desc: TestDesc {
             name: StaticTestName("whatever"),
             ignore: false,
+            #[cfg(not(bootstrap))]
+            ignore_message: None,
             should_panic: ShouldPanic::No,
             allow_fail: false,
             test_type: TestType::Unknown,
@@ -301,6 +330,8 @@ fn f() {}
         desc: TestDesc {
             name: StaticTestName("whatever"),
             ignore: false,
+            #[cfg(not(bootstrap))]
+            ignore_message: None,
             should_panic: ShouldPanic::No,
             allow_fail: false,
             test_type,
@@ -338,6 +369,8 @@ fn typed_test_desc(test_type: TestType) -> TestDesc {
     TestDesc {
         name: StaticTestName("whatever"),
         ignore: false,
+        #[cfg(not(bootstrap))]
+        ignore_message: None,
         should_panic: ShouldPanic::No,
         allow_fail: false,
         test_type,
@@ -449,6 +482,8 @@ pub fn exclude_should_panic_option() {
         desc: TestDesc {
             name: StaticTestName("3"),
             ignore: false,
+            #[cfg(not(bootstrap))]
+            ignore_message: None,
             should_panic: ShouldPanic::Yes,
             allow_fail: false,
             test_type: TestType::Unknown,
@@ -471,6 +506,8 @@ fn tests() -> Vec<TestDescAndFn> {
                 desc: TestDesc {
                     name: StaticTestName(name),
                     ignore: false,
+                    #[cfg(not(bootstrap))]
+                    ignore_message: None,
                     should_panic: ShouldPanic::No,
                     allow_fail: false,
                     test_type: TestType::Unknown,
@@ -563,6 +600,8 @@ fn testfn() {}
                 desc: TestDesc {
                     name: DynTestName((*name).clone()),
                     ignore: false,
+                    #[cfg(not(bootstrap))]
+                    ignore_message: None,
                     should_panic: ShouldPanic::No,
                     allow_fail: false,
                     test_type: TestType::Unknown,
@@ -594,6 +633,188 @@ fn testfn() {}
     }
 }
 
+fn named_tests(count: usize) -> Vec<TestDescAndFn> {
+    (0..count)
+        .map(|i| TestDescAndFn {
+            desc: TestDesc {
+                name: DynTestName(format!("test::t{}", i)),
+                ignore: false,
+                #[cfg(not(bootstrap))]
+                ignore_message: None,
+                should_panic: ShouldPanic::No,
+                allow_fail: false,
+                test_type: TestType::Unknown,
+            },
+            testfn: DynTestFn(Box::new(move || {})),
+        })
+        .collect()
+}
+
+#[test]
+pub fn shuffle_tests_is_deterministic() {
+    let names = |tests: &[TestDescAndFn]| -> Vec<String> {
+        tests.iter().map(|test| test.desc.name.to_string()).collect()
+    };
+
+    let mut left = named_tests(100);
+    let mut right = named_tests(100);
+    let sorted = names(&left);
+
+    helpers::shuffle::shuffle_tests(42, &mut left, |test| &test.desc.name);
+    helpers::shuffle::shuffle_tests(42, &mut right, |test| &test.desc.name);
+
+    assert_eq!(names(&left), names(&right));
+    assert_ne!(names(&left), sorted);
+
+    let mut shuffled = names(&left);
+    shuffled.sort();
+    let mut expected = sorted;
+    expected.sort();
+    assert_eq!(shuffled, expected);
+}
+
+#[test]
+pub fn shuffle_order_is_stable() {
+    // The order for a seed must not change between releases, so that a seed
+    // printed by one toolchain reproduces the same order with another one.
+    let mut tests = named_tests(5);
+    helpers::shuffle::shuffle_tests(42, &mut tests, |test| &test.desc.name);
+    let names: Vec<_> = tests.iter().map(|test| test.desc.name.to_string()).collect();
+    assert_eq!(names, ["test::t1", "test::t2", "test::t3", "test::t0", "test::t4"]);
+}
+
+#[test]
+pub fn shards_partition_tests() {
+    let mut opts = TestOpts::new();
+    opts.run_tests = true;
+
+    let mut seen = Vec::new();
+    for index in 0..3 {
+        opts.shard = Some(Shard { index, count: 3 });
+        let filtered = filter_tests(&opts, named_tests(100));
+        assert!(!filtered.is_empty());
+        seen.extend(filtered.into_iter().map(|test| test.desc.name.to_string()));
+    }
+
+    seen.sort();
+    let mut expected: Vec<_> =
+        named_tests(100).into_iter().map(|test| test.desc.name.to_string()).collect();
+    expected.sort();
+    assert_eq!(seen, expected);
+}
+
+#[test]
+fn parse_shard_options() {
+    let args = |extra: &[&str]| -> Vec<String> {
+        let mut args = vec!["progname".to_string(), "-Zunstable-options".to_string()];
+        args.extend(extra.iter().map(|arg| arg.to_string()));
+        args
+    };
+
+    let opts = parse_opts(&args(&["--shard-index=1", "--shard-count=4"])).unwrap().unwrap();
+    assert_eq!(opts.shard, Some(Shard { index: 1, count: 4 }));
+
+    assert!(parse_opts(&args(&["--shard-index=1"])).unwrap().is_err());
+    assert!(parse_opts(&args(&["--shard-index=4", "--shard-count=4"])).unwrap().is_err());
+    assert!(parse_opts(&args(&["--shard-index=0", "--shard-count=0"])).unwrap().is_err());
+}
+
+#[test]
+fn retries_report_flaky_tests() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static ATTEMPTS: AtomicUsize = AtomicUsize::new(0);
+
+    fn fails_twice() {
+        assert!(ATTEMPTS.fetch_add(1, Ordering::SeqCst) >= 2);
+    }
+
+    fn always_fails() {
+        panic!();
+    }
+
+    let test = |name, f| TestDescAndFn {
+        desc: TestDesc {
+            name: StaticTestName(name),
+            ignore: false,
+            #[cfg(not(bootstrap))]
+            ignore_message: None,
+            should_panic: ShouldPanic::No,
+            allow_fail: false,
+            test_type: TestType::Unknown,
+        },
+        testfn: StaticTestFn(f),
+    };
+
+    let mut opts = TestOpts::new();
+    opts.run_tests = true;
+    opts.test_threads = Some(1);
+    opts.retries = 2;
+
+    let mut results = Vec::new();
+    let mut started = 0;
+    run_tests(
+        &opts,
+        vec![test("always_fails", always_fails), test("fails_twice", fails_twice)],
+        |event| {
+            match event {
+                TestEvent::TeWait(_) => started += 1,
+                TestEvent::TeResult(test) => results.push(test.result),
+                _ => {}
+            }
+            Ok(())
+        },
+    )
+    .unwrap();
+
+    assert_eq!(started, 2);
+    assert_eq!(results, vec![TrFailed, TestResult::TrFlaky(2)]);
+}
+
+#[test]
+fn compare_benchmarks_with_baseline() {
+    let bench = |offset: f64| {
+        let samples: Vec<_> = (0..50).map(|i| 1000.0 + offset + (i % 7) as f64).collect();
+        BenchSamples { ns_iter_summ: Summary::new(&samples), mb_s: 0, samples }
+    };
+
+    let mut baseline = Baseline::default();
+    baseline.insert("bench", &bench(0.0).samples);
+    let baseline = Baseline::parse(&baseline.serialize()).unwrap();
+
+    let same = baseline.compare("bench", &bench(0.0)).unwrap();
+    assert_eq!(same.change, BaselineChange::None);
+    assert_eq!(same.change_pct, 0.0);
+
+    let slower = baseline.compare("bench", &bench(50.0)).unwrap();
+    assert_eq!(slower.change, BaselineChange::Regressed);
+    assert!(slower.change_pct > 4.0 && slower.change_pct < 6.0);
+    assert!(slower.confidence_pct() > 99.0);
+
+    let faster = baseline.compare("bench", &bench(-50.0)).unwrap();
+    assert_eq!(faster.change, BaselineChange::Improved);
+
+    assert!(baseline.compare("other", &bench(0.0)).is_none());
+    assert!(Baseline::parse("1 2 3\tbench\n").is_none());
+
+    let mut zero = Baseline::default();
+    zero.insert("bench", &[0.0; 10]);
+    assert!(zero.compare("bench", &bench(0.0)).is_none());
+}
+
+#[test]
+fn parse_test_timeout_option() {
+    let args = |extra: &str| -> Vec<String> {
+        vec!["progname".to_string(), "-Zunstable-options".to_string(), extra.to_string()]
+    };
+
+    let opts = parse_opts(&args("--test-timeout=30")).unwrap().unwrap();
+    assert_eq!(opts.test_timeout, Some(Duration::from_secs(30)));
+
+    assert!(parse_opts(&args("--test-timeout=0")).unwrap().is_err());
+    assert!(parse_opts(&args("--test-timeout=soon")).unwrap().is_err());
+}
+
 #[test]
 pub fn test_metricmap_compare() {
     let mut m1 = MetricMap::new();
@@ -640,6 +861,8 @@ fn f(_: &mut Bencher) {}
     let desc = TestDesc {
         name: StaticTestName("f"),
         ignore: false,
+        #[cfg(not(bootstrap))]
+        ignore_message: None,
         should_panic: ShouldPanic::No,
         allow_fail: false,
         test_type: TestType::Unknown,
@@ -660,6 +883,8 @@ fn f(b: &mut Bencher) {
     let desc = TestDesc {
         name: StaticTestName("f"),
         ignore: false,
+        #[cfg(not(bootstrap))]
+        ignore_message: None,
         should_panic: ShouldPanic::No,
         allow_fail: false,
         test_type: TestType::Unknown,
@@ -674,6 +899,8 @@ fn should_sort_failures_before_printing_them() {
     let test_a = TestDesc {
         name: StaticTestName("a"),
         ignore: false,
+        #[cfg(not(bootstrap))]
+        ignore_message: None,
         should_panic: ShouldPanic::No,
         allow_fail: false,
         test_type: TestType::Unknown,
@@ -682,6 +909,8 @@ fn should_sort_failures_before_printing_them() {
     let test_b = TestDesc {
         name: StaticTestName("b"),
         ignore: false,
+        #[cfg(not(bootstrap))]
+        ignore_message: None,
         should_panic: ShouldPanic::No,
         allow_fail: false,
         test_type: TestType::Unknown,
@@ -696,14 +925,18 @@ fn should_sort_failures_before_printing_them() {
         failed: 0,
         ignored: 0,
         allowed_fail: 0,
+        flaky: 0,
         filtered_out: 0,
         measured: 0,
         exec_time: None,
         metrics: MetricMap::new(),
+        baseline: None,
+        bench_samples: Default::default(),
         failures: vec![(test_b, Vec::new()), (test_a, Vec::new())],
         options: Options::new(),
         not_failures: Vec::new(),
         time_failures: Vec::new(),
+        flaky_tests: Vec::new(),
     };
 
     out.write_failures(&st).unwrap();
@@ -716,3 +949,145 @@ fn should_sort_failures_before_printing_them() {
     let bpos = s.find("b").unwrap();
     assert!(apos < bpos);
 }
+
+#[test]
+fn json_events_are_versioned() {
+    let desc = TestDesc {
+        name: StaticTestName("m::a"),
+        ignore: false,
+        #[cfg(not(bootstrap))]
+        ignore_message: None,
+        should_panic: ShouldPanic::No,
+        allow_fail: false,
+        test_type: TestType::UnitTest,
+    };
+
+    let shard = Some(Shard { index: 1, count: 3 });
+    let mut out = JsonFormatter::new(OutputLocation::Raw(Vec::new()), shard);
+    let mut st = console::ConsoleTestState::new(&TestOpts::new()).unwrap();
+    st.total = 2;
+    st.passed = 1;
+    st.flaky = 1;
+
+    let time = TestExecTime(Duration::from_millis(1500));
+    out.write_run_start(2, Some(42)).unwrap();
+    out.write_test_start(&desc).unwrap();
+    out.write_result(&desc, &TrOk, Some(&time), b"hidden", &st).unwrap();
+    out.write_result(&desc, &TestResult::TrFlaky(2), Some(&time), b"out", &st).unwrap();
+    assert!(out.write_run_finish(&st).unwrap());
+
+    let s = match out.output_location() {
+        &OutputLocation::Raw(ref m) => String::from_utf8_lossy(&m[..]).into_owned(),
+        &OutputLocation::Pretty(_) => unreachable!(),
+    };
+    let lines: Vec<_> = s.lines().collect();
+    assert_eq!(
+        lines,
+        [
+            concat!(
+                r#"{ "type": "suite", "event": "started", "format_version": 1, "test_count": 2, "#,
+                r#""shuffle_seed": 42, "shard": { "index": 1, "count": 3 } }"#,
+            ),
+            r#"{ "type": "test", "event": "started", "name": "m::a" }"#,
+            r#"{ "type": "test", "name": "m::a", "event": "ok", "exec_time": 1.5 }"#,
+            concat!(
+                r#"{ "type": "test", "name": "m::a", "event": "flaky", "exec_time": 1.5, "#,
+                r#""stdout": "out", "unstable": { "failed_attempts": 2 } }"#,
+            ),
+            concat!(
+                r#"{ "type": "suite", "event": "ok", "passed": 1, "failed": 0, "#,
+                r#""allowed_fail": 0, "ignored": 0, "measured": 0, "filtered_out": 0, "#,
+                r#""unstable": { "flaky": 1 } }"#,
+            ),
+        ]
+    );
+}
+
+#[test]
+#[cfg(not(bootstrap))]
+fn ignore_reasons_are_reported() {
+    fn desc(name: &'static str, ignore: bool) -> TestDesc {
+        TestDesc {
+            name: StaticTestName(name),
+            ignore,
+            ignore_message: Some("needs network"),
+            should_panic: ShouldPanic::No,
+            allow_fail: false,
+            test_type: TestType::UnitTest,
+        }
+    }
+
+    let st = console::ConsoleTestState::new(&TestOpts::new()).unwrap();
+
+    let mut pretty = PrettyFormatter::new(OutputLocation::Raw(Vec::new()), false, 4, true, None);
+    pretty.write_result(&desc("m::a", true), &TrIgnored, None, b"", &st).unwrap();
+    // `--include-ignored` clears `ignore` but keeps the reason.
+    pretty.write_result(&desc("m::b", false), &TrOk, None, b"", &st).unwrap();
+    let s = match pretty.output_location() {
+        &OutputLocation::Raw(ref m) => String::from_utf8_lossy(&m[..]).into_owned(),
+        &OutputLocation::Pretty(_) => unreachable!(),
+    };
+    assert_eq!(
+        s,
+        "test m::a ... ignored, needs network\n\
+         test m::b ... ok (ignored by default: needs network)\n"
+    );
+
+    let mut json = JsonFormatter::new(OutputLocation::Raw(Vec::new()), None);
+    json.write_result(&desc("m::a", true), &TrIgnored, None, b"", &st).unwrap();
+    let s = match json.output_location() {
+        &OutputLocation::Raw(ref m) => String::from_utf8_lossy(&m[..]).into_owned(),
+        &OutputLocation::Pretty(_) => unreachable!(),
+    };
+    assert_eq!(
+        s.lines().collect::<Vec<_>>(),
+        [r#"{ "type": "test", "name": "m::a", "event": "ignored", "message": "needs network" }"#]
+    );
+}
+
+#[test]
+fn junit_report_contains_results() {
+    fn desc(name: &'static str, ignore: bool) -> TestDesc {
+        TestDesc {
+            name: StaticTestName(name),
+            ignore,
+            #[cfg(not(bootstrap))]
+            ignore_message: None,
+            should_panic: ShouldPanic::No,
+            allow_fail: false,
+            test_type: TestType::UnitTest,
+        }
+    }
+
+    let mut out = JunitFormatter::new(OutputLocation::Raw(Vec::new()));
+    let mut st = console::ConsoleTestState::new(&TestOpts::new()).unwrap();
+    st.total = 3;
+    st.passed = 1;
+    st.failed = 1;
+    st.ignored = 1;
+
+    let time = TestExecTime(Duration::from_millis(1500));
+    out.write_run_start(3, None).unwrap();
+    out.write_result(&desc("m::a", false), &TrOk, Some(&time), b"hidden", &st).unwrap();
+    out.write_result(&desc("m::b", false), &TrFailed, Some(&time), b"a < b", &st).unwrap();
+    out.write_result(&desc("c", true), &TrIgnored, None, b"", &st).unwrap();
+    assert!(!out.write_run_finish(&st).unwrap());
+
+    let s = match out.output_location() {
+        &OutputLocation::Raw(ref m) => String::from_utf8_lossy(&m[..]).into_owned(),
+        &OutputLocation::Pretty(_) => unreachable!(),
+    };
+
+    assert!(s.starts_with(r#"<?xml version="1.0" encoding="UTF-8"?>"#));
+    assert!(s.contains(r#"failures="1" tests="3" skipped="1""#));
+    assert!(s.contains(r#"<testcase classname="m" name="a" time="1.5"/>"#));
+    assert!(!s.contains("hidden"));
+    assert!(s.contains(
+        "<testcase classname=\"m\" name=\"b\" time=\"1.5\">\n\
+         <failure type=\"assert\"/>\n\
+         <system-out>a &lt; b</system-out>\n\
+         </testcase>"
+    ));
+    assert!(s.contains("<testcase classname=\"crate\" name=\"c\" time=\"0\">\n<skipped/>\n"));
+    assert!(s.ends_with("</testsuite>\n</testsuites>\n"));
+}
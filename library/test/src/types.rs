@@ -122,6 +122,10 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 pub struct TestDesc {
     pub name: TestName,
     pub ignore: bool,
+    /// The reason given in `#[ignore = "reason"]`, if any. This is kept after
+    /// `--ignored` or `--include-ignored` clears `ignore`.
+    #[cfg(not(bootstrap))]
+    pub ignore_message: Option<&'static str>,
     pub should_panic: options::ShouldPanic,
     pub allow_fail: bool,
     pub test_type: TestType,
@@ -140,6 +144,18 @@ pub fn padded_name(&self, column_count: usize, align: NamePadding) -> String {
             }
         }
     }
+
+    /// Returns the reason the test is ignored for, if one was given.
+    pub fn ignore_message(&self) -> Option<&'static str> {
+        #[cfg(not(bootstrap))]
+        {
+            self.ignore_message
+        }
+        #[cfg(bootstrap)]
+        {
+            None
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -4,6 +4,7 @@
 use rustc_driver::abort_on_err;
 use rustc_errors::emitter::{Emitter, EmitterWriter};
 use rustc_errors::json::JsonEmitter;
+use rustc_errors::sarif::SarifEmitter;
 use rustc_feature::UnstableFeatures;
 use rustc_hir::def::Res;
 use rustc_hir::def_id::{DefId, LocalDefId, LOCAL_CRATE};
@@ -140,8 +141,8 @@ impl<'tcx> DocContext<'tcx> {
 
 /// Creates a new diagnostic `Handler` that can be used to emit warnings and errors.
 ///
-/// If the given `error_format` is `ErrorOutputType::Json` and no `SourceMap` is given, a new one
-/// will be created for the handler.
+/// If the given `error_format` is `ErrorOutputType::Json` or `ErrorOutputType::Sarif` and no
+/// `SourceMap` is given, a new one will be created for the handler.
 crate fn new_handler(
     error_format: ErrorOutputType,
     source_map: Option<Lrc<source_map::SourceMap>>,
@@ -159,7 +160,8 @@ impl<'tcx> DocContext<'tcx> {
                     debugging_opts.terminal_width,
                     false,
                 )
-                .ui_testing(debugging_opts.ui_testing),
+                .ui_testing(debugging_opts.ui_testing)
+                .terminal_urls(debugging_opts.terminal_urls),
             )
         }
         ErrorOutputType::Json { pretty, json_rendered } => {
@@ -178,6 +180,12 @@ impl<'tcx> DocContext<'tcx> {
                 .ui_testing(debugging_opts.ui_testing),
             )
         }
+        ErrorOutputType::Sarif => {
+            let source_map = source_map.unwrap_or_else(|| {
+                Lrc::new(source_map::SourceMap::new(source_map::FilePathMapping::empty()))
+            });
+            Box::new(SarifEmitter::stderr(None, source_map))
+        }
     };
 
     rustc_errors::Handler::with_emitter_and_flags(
@@ -138,7 +138,8 @@ struct BufferEmitter {
 impl Emitter for BufferEmitter {
     fn emit_diagnostic(&mut self, diag: &Diagnostic) {
         let mut buffer = self.buffer.borrow_mut();
-        buffer.messages.push(format!("error from rustc: {}", diag.message[0].0));
+        let message = diag.message[0].0.as_str_or_identifier();
+        buffer.messages.push(format!("error from rustc: {}", message));
         if diag.is_error() {
             buffer.has_errors = true;
         }
@@ -0,0 +1,10 @@
+#![crate_type = "proc-macro"]
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+
+#[proc_macro]
+pub fn abort(_: TokenStream) -> TokenStream {
+    std::process::abort()
+}
@@ -0,0 +1,5 @@
+extern crate abort;
+
+abort::abort!();
+
+fn main() {}
@@ -0,0 +1,7 @@
+#![crate_type = "lib"]
+
+#[cfg(not(changed))]
+pub fn f() {}
+
+#[cfg(changed)]
+pub fn renamed_f() {}
@@ -0,0 +1,7 @@
+#!/usr/bin/env python
+
+import sys
+import xml.etree.ElementTree as ET
+
+# Try to decode the whole output in order to ensure it is a valid XML document
+ET.parse(sys.stdin)
@@ -0,0 +1,4 @@
+fn main() {
+    let unused = 1;
+    let _x: u32 = "not a number";
+}
@@ -0,0 +1,3 @@
+extern crate does_not_exist;
+
+fn main() {}
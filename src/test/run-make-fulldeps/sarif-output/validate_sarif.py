@@ -0,0 +1,28 @@
+#!/usr/bin/env python
+
+# Checks that a file is a single SARIF 2.1.0 log, whose results are exactly
+# the ones given on the command line as `RULE:LEVEL:LINE`, in any order.
+
+import json
+import sys
+
+with open(sys.argv[1]) as f:
+    log = json.load(f)
+
+assert log["version"] == "2.1.0", log["version"]
+assert len(log["runs"]) == 1, log["runs"]
+run = log["runs"][0]
+assert run["tool"]["driver"]["name"] == "rustc", run["tool"]
+rules = [rule["id"] for rule in run["tool"]["driver"]["rules"]]
+
+results = []
+for result in run["results"]:
+    assert rules[result["ruleIndex"]] == result["ruleId"], result
+    assert result["message"]["text"], result
+    location = result["locations"][0]["physicalLocation"]
+    assert location["artifactLocation"]["uri"].endswith(".rs"), location
+    line = location["region"]["startLine"]
+    results.append("{}:{}:{}".format(result["ruleId"], result["level"], line))
+
+expected = sorted(sys.argv[2:])
+assert sorted(results) == expected, "expected {}, got {}".format(expected, results)
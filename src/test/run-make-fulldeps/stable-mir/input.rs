@@ -0,0 +1,21 @@
+pub unsafe fn dangerous() {}
+
+pub fn read(p: *const u32) -> u32 {
+    unsafe { *p }
+}
+
+pub fn call() {
+    unsafe { dangerous() }
+}
+
+pub fn safe(x: &u32) -> u32 {
+    *x + 1
+}
+
+pub fn write_field(p: *mut (u32, u32)) {
+    unsafe { (*p).1 = 2 }
+}
+
+pub fn copy(p: *const u32) -> u32 {
+    unsafe { std::ptr::read(p) }
+}
@@ -0,0 +1,106 @@
+// An analysis tool which lists the unsafe operations of every function of a
+// crate: dereferences of raw pointers, and calls to unsafe functions. It only
+// uses the stable API of `rustc_smir`.
+
+#![feature(rustc_private)]
+
+extern crate rustc_smir;
+
+use rustc_smir::stable_mir::mir::{
+    Body, Operand, Place, ProjectionElem, Rvalue, StatementKind, TerminatorKind,
+};
+use rustc_smir::stable_mir::ty::{Safety, Ty};
+use rustc_smir::stable_mir::Span;
+use rustc_smir::{Compilation, Context};
+
+struct UnsafeOps;
+
+impl rustc_smir::Callbacks for UnsafeOps {
+    fn after_analysis(&mut self, cx: &Context<'_>) -> Compilation {
+        assert_eq!(rustc_smir::stable_mir::VERSION.0, 0);
+        for item in cx.items() {
+            let body = cx.body(&item);
+            for (span, op) in unsafe_ops(cx, &body) {
+                println!("{}: {} at {}:{}:{}", item.name, op, span.file, span.lo.line, span.lo.col);
+            }
+        }
+        Compilation::Stop
+    }
+}
+
+fn unsafe_ops(cx: &Context<'_>, body: &Body) -> Vec<(Span, String)> {
+    let mut ops = Vec::new();
+    for block in &body.blocks {
+        for statement in &block.statements {
+            if let StatementKind::Assign(place, rvalue) = &statement.kind {
+                let mut places = vec![place];
+                places.extend(rvalue_places(rvalue));
+                if places.into_iter().any(|place| derefs_raw_ptr(body, place)) {
+                    ops.push((statement.span.clone(), "dereference of a raw pointer".to_string()));
+                }
+            }
+        }
+        if let TerminatorKind::Call { func: Operand::Constant(func), .. } = &block.terminator.kind {
+            if let Ty::FnDef(def_id, _) = func.ty {
+                if cx.fn_sig(def_id).map_or(false, |sig| sig.safety == Safety::Unsafe) {
+                    let op = format!("call to unsafe function `{}`", cx.def_path(def_id));
+                    ops.push((block.terminator.span.clone(), op));
+                }
+            }
+        }
+    }
+    ops
+}
+
+fn operand_place(operand: &Operand) -> Option<&Place> {
+    match operand {
+        Operand::Copy(place) | Operand::Move(place) => Some(place),
+        Operand::Constant(_) => None,
+    }
+}
+
+fn rvalue_places(rvalue: &Rvalue) -> Vec<&Place> {
+    match rvalue {
+        Rvalue::Use(operand) | Rvalue::Repeat(operand, _) | Rvalue::Cast(_, operand, _) => {
+            operand_place(operand).into_iter().collect()
+        }
+        Rvalue::UnaryOp(_, operand) => operand_place(operand).into_iter().collect(),
+        Rvalue::BinaryOp(_, left, right) | Rvalue::CheckedBinaryOp(_, left, right) => {
+            operand_place(left).into_iter().chain(operand_place(right)).collect()
+        }
+        Rvalue::Aggregate(_, operands) => operands.iter().filter_map(operand_place).collect(),
+        Rvalue::Ref(_, place)
+        | Rvalue::AddressOf(_, place)
+        | Rvalue::Len(place)
+        | Rvalue::Discriminant(place) => vec![place],
+        _ => Vec::new(),
+    }
+}
+
+/// Returns whether `place` dereferences a raw pointer.
+fn derefs_raw_ptr(body: &Body, place: &Place) -> bool {
+    let mut ty = &body.locals[place.local];
+    for elem in &place.projection {
+        match elem {
+            ProjectionElem::Deref => match ty {
+                Ty::RawPtr(..) => return true,
+                Ty::Ref(pointee, _) => ty = pointee,
+                // A `Box`.
+                Ty::Adt(_, args) if !args.is_empty() => ty = &args[0],
+                _ => return false,
+            },
+            ProjectionElem::Field(_, field_ty) => ty = field_ty,
+            ProjectionElem::Index(_) | ProjectionElem::ConstantIndex { .. } => match ty {
+                Ty::Array(elem_ty, _) | Ty::Slice(elem_ty) => ty = elem_ty,
+                _ => return false,
+            },
+            ProjectionElem::Subslice { .. } | ProjectionElem::Downcast(_) => {}
+        }
+    }
+    false
+}
+
+fn main() {
+    let args = std::env::args().collect::<Vec<_>>();
+    std::process::exit(rustc_smir::run(&args, &mut UnsafeOps).map_or(1, |()| 0));
+}
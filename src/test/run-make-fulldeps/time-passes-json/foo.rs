@@ -0,0 +1,9 @@
+mod a {
+    pub fn f() -> u32 {
+        1
+    }
+}
+
+fn main() {
+    println!("{}", a::f());
+}
@@ -0,0 +1,45 @@
+// run-pass
+// compile-flags: --test
+// check-run-results
+
+#![feature(custom_test_frameworks)]
+#![test_runner(runner, descriptors)]
+
+use std::test_case::TestCase;
+
+pub trait IsEven {
+    fn is_even(&self) -> bool;
+}
+
+impl IsEven for u32 {
+    fn is_even(&self) -> bool {
+        self % 2 == 0
+    }
+}
+
+fn runner(tests: &[&TestCase<'_, dyn IsEven>]) {
+    for test in tests {
+        println!(
+            "{} in {} at {}:{}:{}: ignore={} {:?}, should_panic={:?}, even={}",
+            test.name,
+            test.module_path,
+            test.file,
+            test.line,
+            test.column,
+            test.ignore,
+            test.ignore_message,
+            test.should_panic,
+            test.test.is_even(),
+        );
+    }
+}
+
+#[test_case]
+const TWO: u32 = 2;
+
+mod odd {
+    #[test_case]
+    #[ignore = "odd numbers are not ready"]
+    #[should_panic]
+    pub const THREE: u32 = 3;
+}
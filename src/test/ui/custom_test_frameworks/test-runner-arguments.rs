@@ -0,0 +1,6 @@
+// compile-flags: --test
+
+#![feature(custom_test_frameworks)]
+#![test_runner(runner, everything)] //~ ERROR unknown `test_runner` argument
+
+pub fn runner(_: &[&i32]) {}
@@ -0,0 +1,5 @@
+// compile-flags: --test
+
+#[test]
+#[ignore(cfg(unix), reason = "unix")] //~ ERROR conditionally ignoring tests is experimental
+fn conditionally_ignored() {}
@@ -0,0 +1,15 @@
+// Checks the note which `-Z explain-lint-levels` adds to every lint.
+
+// check-pass
+// compile-flags: -Z explain-lint-levels --cap-lints warn
+// compile-flags: -D unused-variables -W nonstandard-style
+
+#![deny(dead_code)]
+
+fn unused_function() {} //~ WARN function is never used
+
+fn main() {
+    let x = 1; //~ WARN unused variable
+    let _CamelCase = (); //~ WARN should have a snake case name
+    while true {} //~ WARN denote infinite loops
+}
@@ -0,0 +1,5 @@
+// Loaded as a module by `lint-config.rs`, whose lint config allows unused variables here.
+
+pub fn f() {
+    let y = 1;
+}
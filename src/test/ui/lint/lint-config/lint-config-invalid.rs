@@ -0,0 +1,5 @@
+// compile-flags: -Z unstable-options
+// compile-flags: --lint-config {{src-base}}/lint/lint-config/lint-config-invalid.toml
+// error-pattern: invalid lint level `dney`
+
+fn main() {}
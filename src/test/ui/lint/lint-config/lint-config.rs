@@ -0,0 +1,11 @@
+// Lint levels can be set by a lint config file, with overrides for the modules in some paths.
+
+// compile-flags: -Z unstable-options --lint-config {{src-base}}/lint/lint-config/lint-config.toml
+
+#[path = "auxiliary/lint-config-generated.rs"]
+mod generated;
+
+fn main() {
+    let x = 1; //~ ERROR unused variable: `x`
+    generated::f();
+}
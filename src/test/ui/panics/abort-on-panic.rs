@@ -52,6 +52,13 @@ fn test_always_abort() {
     should_have_aborted();
 }
 
+fn test_always_abort_resume_unwind() {
+    let payload = panic::catch_unwind(|| { panic!("before always_abort"); }).unwrap_err();
+    panic::always_abort();
+    let _ = panic::catch_unwind(move || { panic::resume_unwind(payload); });
+    should_have_aborted();
+}
+
 fn test_always_abort_thread() {
     let barrier = Arc::new(Barrier::new(2));
     let thr = {
@@ -72,6 +79,7 @@ fn main() {
         ("test", test),
         ("testrust", testrust),
         ("test_always_abort", test_always_abort),
+        ("test_always_abort_resume_unwind", test_always_abort_resume_unwind),
         ("test_always_abort_thread", test_always_abort_thread),
     ];
 
@@ -0,0 +1,5 @@
+// include file for multi-file-import-suggestion.rs
+
+pub fn f() {
+    let _ = super::other::Bar;
+}
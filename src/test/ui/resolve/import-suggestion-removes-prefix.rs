@@ -0,0 +1,15 @@
+// run-rustfix
+// Check that the import of the only candidate is suggested together with the removal of the
+// prefix which failed to resolve, and that applying both fixes the path.
+
+mod a {
+    pub mod b {
+        pub struct Bar;
+    }
+}
+
+mod other {}
+
+fn main() {
+    let _ = other::Bar; //~ ERROR cannot find value `Bar` in module `other`
+}
@@ -0,0 +1,17 @@
+// Check that the suggested import can be in another file than the path it fixes.
+
+// error-pattern: cannot find value `Bar` in module `super::other`
+
+mod inner {
+    pub struct Bar;
+}
+
+mod other {}
+
+mod m {
+    pub struct Foo;
+
+    include!("auxiliary/multi-file-import-suggestion-inc.rs");
+}
+
+fn main() {}
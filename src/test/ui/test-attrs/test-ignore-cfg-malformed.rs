@@ -0,0 +1,15 @@
+// compile-flags: --test
+
+#![feature(test_ignore_cfg)]
+
+#[test]
+#[ignore(reason = "no predicate")] //~ ERROR malformed `ignore` attribute input
+fn missing_predicate() {}
+
+#[test]
+#[ignore(cfg(unix, windows))] //~ ERROR malformed `ignore` attribute input
+fn two_predicates() {}
+
+#[test]
+#[ignore(cfg(unix), because = "unknown key")] //~ ERROR malformed `ignore` attribute input
+fn unknown_key() {}
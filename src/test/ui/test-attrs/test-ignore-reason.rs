@@ -0,0 +1,28 @@
+// run-pass
+// compile-flags: --test
+// run-flags: --test-threads=1
+// check-run-results
+// normalize-stdout-test "finished in \d+\.\d+s" -> "finished in $$TIME"
+// ignore-emscripten no threads support
+
+#![feature(test_ignore_cfg)]
+
+#[test]
+#[ignore]
+fn no_reason() {}
+
+#[test]
+#[ignore = "not yet implemented"]
+fn with_reason() {}
+
+#[test]
+#[ignore(cfg(all()), reason = "always ignored")]
+fn cfg_matches() {}
+
+#[test]
+#[ignore(cfg(all()))]
+fn cfg_matches_no_reason() {}
+
+#[test]
+#[ignore(cfg(any()), reason = "never ignored")]
+fn cfg_does_not_match() {}
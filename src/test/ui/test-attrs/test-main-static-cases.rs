@@ -0,0 +1,26 @@
+// run-pass
+// compile-flags: --test
+// run-flags: --test-threads=1
+// check-run-results
+// normalize-stdout-test "finished in \d+\.\d+s" -> "finished in $$TIME"
+// ignore-emscripten no threads support
+
+#![feature(custom_test_frameworks, test)]
+#![test_runner(test::test_main_static_cases, descriptors)]
+
+extern crate test;
+
+#[test]
+fn it_works() {}
+
+#[test]
+#[ignore = "too slow"]
+fn slow() {}
+
+mod panicking {
+    #[test]
+    #[should_panic(expected = "boom")]
+    fn panics() {
+        panic!("boom");
+    }
+}
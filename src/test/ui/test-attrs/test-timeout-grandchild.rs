@@ -0,0 +1,29 @@
+// compile-flags: --test
+// run-flags: --test-threads=1 -Zunstable-options --test-timeout=1
+// run-fail
+// check-run-results
+// exec-env:RUST_BACKTRACE=0
+// normalize-stdout-test "finished in \d+\.\d+s" -> "finished in $$TIME"
+
+// A test which times out is reported even if a process it spawned keeps its
+// output pipes open.
+
+// only-unix
+// ignore-wasm no subprocess support
+// ignore-emscripten no subprocess support
+// ignore-sgx no subprocess support
+
+#![cfg(test)]
+
+use std::process::Command;
+use std::thread;
+use std::time::Duration;
+
+#[test]
+fn it_hangs_with_a_child() {
+    println!("about to hang");
+    let _child = Command::new("sleep").arg("60").spawn().unwrap();
+    loop {
+        thread::sleep(Duration::from_millis(100));
+    }
+}
@@ -0,0 +1,41 @@
+// compile-flags: --test
+// run-flags: --test-threads=1 -Zunstable-options --test-timeout=1
+// run-fail
+// check-run-results
+// exec-env:RUST_BACKTRACE=0
+// normalize-stdout-test "finished in \d+\.\d+s" -> "finished in $$TIME"
+
+// ignore-wasm no subprocess support
+// ignore-emscripten no subprocess support
+// ignore-sgx no subprocess support
+
+#![cfg(test)]
+
+use std::panic;
+use std::thread;
+use std::time::Duration;
+
+#[test]
+fn it_works() {
+    assert_eq!(1 + 1, 2);
+}
+
+#[test]
+#[should_panic]
+fn it_panics() {
+    assert_eq!(1 + 1, 4);
+}
+
+#[test]
+fn it_catches_panics() {
+    assert!(panic::catch_unwind(|| panic!("caught")).is_err());
+    assert!(thread::spawn(|| panic!("joined")).join().is_err());
+}
+
+#[test]
+fn it_hangs() {
+    println!("about to hang");
+    loop {
+        thread::sleep(Duration::from_millis(100));
+    }
+}
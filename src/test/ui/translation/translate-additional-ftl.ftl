@@ -0,0 +1,6 @@
+# A partial translation of the messages of `rustc_typeck`, for
+# `translate-additional-ftl.rs`.
+
+typeck-missing-cast-for-variadic-arg = impossible de passer `{$ty}` à une fonction variadique
+    .suggestion = convertissez la valeur en `{$cast_ty}`
+    .help = convertissez la valeur en `{$cast_ty}`
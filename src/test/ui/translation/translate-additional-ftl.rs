@@ -0,0 +1,20 @@
+// Check that the messages of the catalogue given with
+// `-Z translate-additional-ftl` are used, and that the built-in English ones
+// are used for the messages it doesn't have.
+
+// compile-flags: -Z translate-additional-ftl={{src-base}}/translation/translate-additional-ftl.ftl
+
+extern "C" {
+    fn printf(c: *const i8, ...);
+}
+
+fn main() {
+    unsafe {
+        printf(::std::ptr::null(), 0f32);
+        //~^ ERROR impossible de passer `f32` à une fonction variadique
+        //~| HELP convertissez la valeur en `c_double`
+    }
+    let v = core::ptr::null::<u8>();
+    v as *const [u8];
+    //~^ ERROR cannot cast thin pointer `*const u8` to fat pointer `*const [u8]`
+}
@@ -0,0 +1,4 @@
+# A catalogue with a syntax error, for `translate-broken-ftl.rs`.
+
+this line is not a message
+typeck-missing-cast-for-variadic-arg = impossible de passer `{$ty}` à une fonction variadique
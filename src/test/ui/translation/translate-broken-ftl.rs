@@ -0,0 +1,15 @@
+// Check that a catalogue with syntax errors is reported, and that the
+// diagnostics are then emitted with the built-in English messages.
+
+// compile-flags: -Z translate-additional-ftl={{src-base}}/translation/translate-broken-ftl.ftl
+
+extern "C" {
+    fn printf(c: *const i8, ...);
+}
+
+fn main() {
+    unsafe {
+        printf(::std::ptr::null(), 0f32);
+        //~^ ERROR can't pass `f32` to variadic function
+    }
+}
@@ -0,0 +1,16 @@
+// Check that a language without catalogues in the sysroot is reported, and
+// that the diagnostics are then emitted with the built-in English messages.
+
+// compile-flags: -Z translate-lang=xx-missing
+// normalize-stderr-test: "looked in `[^`]*`" -> "looked in `$$LOCALE_DIR`"
+
+extern "C" {
+    fn printf(c: *const i8, ...);
+}
+
+fn main() {
+    unsafe {
+        printf(::std::ptr::null(), 0f32);
+        //~^ ERROR can't pass `f32` to variadic function
+    }
+}
@@ -506,6 +506,20 @@ pub fn test_opts(config: &Config) -> test::TestOpts {
         options: test::Options::new(),
         time_options: None,
         force_run_in_process: false,
+        #[cfg(not(bootstrap))]
+        shuffle: false,
+        #[cfg(not(bootstrap))]
+        shuffle_seed: None,
+        #[cfg(not(bootstrap))]
+        shard: None,
+        #[cfg(not(bootstrap))]
+        test_timeout: None,
+        #[cfg(not(bootstrap))]
+        retries: 0,
+        #[cfg(not(bootstrap))]
+        save_baseline: None,
+        #[cfg(not(bootstrap))]
+        baseline: None,
     }
 }
 
@@ -662,6 +676,8 @@ fn make_test(config: &Config, testpaths: &TestPaths, inputs: &Stamp) -> Vec<test
                 desc: test::TestDesc {
                     name: make_test_name(config, testpaths, revision),
                     ignore,
+                    #[cfg(not(bootstrap))]
+                    ignore_message: None,
                     should_panic,
                     allow_fail: false,
                     test_type: test::TestType::Unknown,